@@ -1,5 +1,6 @@
 use crate::infra::analysis_log::jsonl_repository::JsonlAnalysisLogRepository;
 use crate::infra::analysis_log::session_repository::SessionAnalysisLogRepository;
+use crate::infra::cache::csv_export::CsvExportRegistry;
 use crate::infra::cache::repository::DatasetCacheRepository;
 use crate::infra::r::analyzer::RAnalyzer;
 use crate::infra::reader::DataResolver;
@@ -10,6 +11,7 @@ use crate::usecase::analysis_log::service::{
     SessionAnalysisLogService,
 };
 use crate::usecase::import::service::ImportService;
+use crate::usecase::statistics::service::StatisticsService;
 use tauri::Manager;
 
 const ANALYSIS_LOG_MAX_FILE_SIZE_BYTES: u64 = 5 * 1024 * 1024;
@@ -21,6 +23,8 @@ pub(crate) struct AppState {
     pub persistent_analysis_log_service: AnalysisLogService<JsonlAnalysisLogRepository>,
     pub session_analysis_log_service: SessionAnalysisLogService<SessionAnalysisLogRepository>,
     pub import_service: ImportService<DataResolver, DatasetCacheRepository>,
+    pub statistics_service: StatisticsService<DatasetCacheRepository>,
+    pub csv_exports: CsvExportRegistry,
 }
 
 impl AppState {
@@ -40,6 +44,8 @@ impl AppState {
                                                          analysis_log_writer),
                   persistent_analysis_log_service: AnalysisLogService::new(persistent_log_repository),
                   session_analysis_log_service: SessionAnalysisLogService::new(session_log_repository),
-                  import_service: ImportService::new(DataResolver, DatasetCacheRepository) })
+                  import_service: ImportService::new(DataResolver, DatasetCacheRepository),
+                  statistics_service: StatisticsService::new(DatasetCacheRepository),
+                  csv_exports: CsvExportRegistry::default() })
     }
 }
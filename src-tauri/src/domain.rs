@@ -1,3 +1,6 @@
 pub(crate) mod analysis;
 pub(crate) mod analysis_log;
 pub(crate) mod input;
+pub(crate) mod rng;
+pub(crate) mod statistics;
+pub(crate) mod transform;
@@ -6,6 +6,7 @@ pub(crate) enum AnalysisErrorKind {
     DatasetNotFound,
     AnalysisLogFailure,
     RExecutionFailure,
+    RMemoryLimitExceeded,
     InvalidAnalysisResult,
 }
 
@@ -16,6 +17,7 @@ impl AnalysisErrorKind {
             AnalysisErrorKind::DatasetNotFound => "ANALYSIS_DATASET_NOT_FOUND",
             AnalysisErrorKind::AnalysisLogFailure => "ANALYSIS_LOG_FAILURE",
             AnalysisErrorKind::RExecutionFailure => "ANALYSIS_R_EXECUTION_FAILURE",
+            AnalysisErrorKind::RMemoryLimitExceeded => "ANALYSIS_R_MEMORY_LIMIT_EXCEEDED",
             AnalysisErrorKind::InvalidAnalysisResult => "ANALYSIS_INVALID_RESULT",
         }
     }
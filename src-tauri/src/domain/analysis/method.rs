@@ -5,16 +5,37 @@ pub(crate) struct Method(&'static str);
 
 impl Method {
     pub(crate) const ANOVA: Self = Self("anova");
+    pub(crate) const AUTO_COMPARE: Self = Self("auto_compare");
+    pub(crate) const BOX_COX: Self = Self("box_cox");
+    pub(crate) const CHI_SQUARE: Self = Self("chi_square");
+    pub(crate) const CONFIDENCE_BANDS: Self = Self("confidence_bands");
     pub(crate) const CORRELATION: Self = Self("correlation");
     pub(crate) const DESCRIPTIVE: Self = Self("descriptive");
     pub(crate) const FACTOR: Self = Self("factor");
+    pub(crate) const FORECAST: Self = Self("forecast");
+    pub(crate) const INFLUENCE: Self = Self("influence");
+    pub(crate) const KAPLAN_MEIER: Self = Self("kaplan_meier");
+    pub(crate) const KMEANS: Self = Self("kmeans");
+    pub(crate) const MCNEMAR: Self = Self("mcnemar");
+    pub(crate) const PAIRWISE_EFFECT_SIZES: Self = Self("pairwise_effect_sizes");
+    pub(crate) const PAIRWISE_T_TESTS: Self = Self("pairwise_t_tests");
     pub(crate) const POWER: Self = Self("power");
+    pub(crate) const QUANTILES: Self = Self("quantiles");
     pub(crate) const REGRESSION: Self = Self("regression");
     pub(crate) const RELIABILITY: Self = Self("reliability");
+    pub(crate) const ROLLING_TTEST: Self = Self("rolling_ttest");
+    pub(crate) const TARGET_CORRELATION: Self = Self("target_correlation");
+    pub(crate) const T_TEST: Self = Self("t_test");
+    pub(crate) const YUEN_T_TEST: Self = Self("yuen_t_test");
 
     pub(crate) fn as_str(self) -> &'static str {
         self.0
     }
+
+    /// Methods whose R implementation computes each column independently and can report them one by one.
+    pub(crate) fn streams_by_column(self) -> bool {
+        self == Self::DESCRIPTIVE || self == Self::QUANTILES
+    }
 }
 
 impl FromStr for Method {
@@ -24,12 +45,28 @@ impl FromStr for Method {
         let normalized = value.trim();
         match normalized {
             "anova" => Ok(Method::ANOVA),
+            "auto_compare" => Ok(Method::AUTO_COMPARE),
+            "box_cox" => Ok(Method::BOX_COX),
+            "chi_square" => Ok(Method::CHI_SQUARE),
+            "confidence_bands" => Ok(Method::CONFIDENCE_BANDS),
             "correlation" => Ok(Method::CORRELATION),
             "descriptive" => Ok(Method::DESCRIPTIVE),
             "factor" => Ok(Method::FACTOR),
+            "forecast" => Ok(Method::FORECAST),
+            "influence" => Ok(Method::INFLUENCE),
+            "kaplan_meier" => Ok(Method::KAPLAN_MEIER),
+            "kmeans" => Ok(Method::KMEANS),
+            "mcnemar" => Ok(Method::MCNEMAR),
+            "pairwise_effect_sizes" => Ok(Method::PAIRWISE_EFFECT_SIZES),
+            "pairwise_t_tests" => Ok(Method::PAIRWISE_T_TESTS),
             "power" => Ok(Method::POWER),
+            "quantiles" => Ok(Method::QUANTILES),
             "regression" => Ok(Method::REGRESSION),
             "reliability" => Ok(Method::RELIABILITY),
+            "rolling_ttest" => Ok(Method::ROLLING_TTEST),
+            "t_test" => Ok(Method::T_TEST),
+            "target_correlation" => Ok(Method::TARGET_CORRELATION),
+            "yuen_t_test" => Ok(Method::YUEN_T_TEST),
             _ => Err(format!("Unsupported method: {}", value)),
         }
     }
@@ -3,6 +3,7 @@ use serde::{
     Serialize,
 };
 
+use crate::domain::input::metadata::ColumnMetadataMap;
 use crate::domain::input::table::ParsedDataTable;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -22,6 +23,9 @@ impl DescriptiveResult {
 pub(crate) struct CorrelationResult {
     pub correlation: ParsedDataTable,
     pub t_values: ParsedDataTable,
+    /// Square matrix of pairwise p-values (null diagonal); only present when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_values: Option<ParsedDataTable>,
 }
 
 impl CorrelationResult {
@@ -30,6 +34,9 @@ impl CorrelationResult {
             .validate()
             .map_err(|e| format!("correlation: {}", e))?;
         self.t_values.validate().map_err(|e| format!("t_values: {}", e))?;
+        if let Some(p_values) = self.p_values.as_ref() {
+            p_values.validate().map_err(|e| format!("p_values: {}", e))?;
+        }
         Ok(())
     }
 }
@@ -39,6 +46,9 @@ pub(crate) struct RegressionResult {
     pub model_summary: ParsedDataTable,
     pub coefficients: ParsedDataTable,
     pub anova: ParsedDataTable,
+    /// Per-row fitted values, residuals and leverage; only present when diagnostics were requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<ParsedDataTable>,
 }
 
 impl RegressionResult {
@@ -50,6 +60,64 @@ impl RegressionResult {
             .validate()
             .map_err(|e| format!("coefficients: {}", e))?;
         self.anova.validate().map_err(|e| format!("anova: {}", e))?;
+        if let Some(diagnostics) = self.diagnostics.as_ref() {
+            diagnostics.validate()
+                       .map_err(|e| format!("diagnostics: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct InfluenceResult {
+    /// Leverage, Cook's distance and the influential flag for every input row.
+    pub diagnostics: ParsedDataTable,
+    /// The influential rows only, with the thresholds they exceed.
+    pub flagged: ParsedDataTable,
+}
+
+impl InfluenceResult {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        self.diagnostics
+            .validate()
+            .map_err(|e| format!("diagnostics: {}", e))?;
+        self.flagged.validate().map_err(|e| format!("flagged: {}", e))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct KMeansResult {
+    /// The cluster of every input row; empty for rows with a missing value in the selected columns.
+    pub assignments: ParsedDataTable,
+    /// Size, center and within-cluster sum of squares of each cluster.
+    pub centers: ParsedDataTable,
+}
+
+impl KMeansResult {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        self.assignments
+            .validate()
+            .map_err(|e| format!("assignments: {}", e))?;
+        self.centers.validate().map_err(|e| format!("centers: {}", e))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct BoxCoxResult {
+    /// Estimated lambda with its 95% interval, the shift, and skewness before and after.
+    pub summary: ParsedDataTable,
+    /// The transformed value of every input row; empty for rows with a missing value.
+    pub transformed: ParsedDataTable,
+}
+
+impl BoxCoxResult {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        self.summary.validate().map_err(|e| format!("summary: {}", e))?;
+        self.transformed
+            .validate()
+            .map_err(|e| format!("transformed: {}", e))?;
         Ok(())
     }
 }
@@ -116,6 +184,9 @@ pub(crate) enum AnalysisResult {
     Descriptive { descriptive: DescriptiveResult },
     Correlation { correlation: CorrelationResult },
     Regression { regression: RegressionResult },
+    Influence { influence: InfluenceResult },
+    KMeans { kmeans: KMeansResult },
+    BoxCox { boxcox: BoxCoxResult },
     Factor { factor: Box<FactorResult> },
     Anova { anova: AnovaResult },
 }
@@ -127,12 +198,26 @@ impl AnalysisResult {
             AnalysisResult::Descriptive { descriptive } => descriptive.validate(),
             AnalysisResult::Correlation { correlation } => correlation.validate(),
             AnalysisResult::Regression { regression } => regression.validate(),
+            AnalysisResult::Influence { influence } => influence.validate(),
+            AnalysisResult::KMeans { kmeans } => kmeans.validate(),
+            AnalysisResult::BoxCox { boxcox } => boxcox.validate(),
             AnalysisResult::Factor { factor } => factor.validate(),
             AnalysisResult::Anova { anova } => anova.validate(),
         }
     }
 }
 
+/// Rows for one column of a per-column analysis, reported while the rest is still running.
+/// The final result contains the same rows (possibly reordered by the analysis options).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct AnalysisPartial {
+    pub column: String,
+    /// 1-based position of the column among `total` columns.
+    pub index: usize,
+    pub total: usize,
+    pub table: ParsedDataTable,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct AnalysisRunResult {
@@ -141,4 +226,6 @@ pub(crate) struct AnalysisRunResult {
     pub result: AnalysisResult,
     pub n: Option<u32>,
     pub n_note: Option<String>,
+    /// Units / descriptions of the analysed columns, for display only.
+    pub column_metadata: ColumnMetadataMap,
 }
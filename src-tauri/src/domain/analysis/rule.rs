@@ -46,6 +46,39 @@ pub(crate) fn option_bool_from_value(value: &Value) -> Option<bool> {
     }
 }
 
+/// Sets the count option `key` to its given value clamped to `cap`, or to `default` when it is
+/// missing or not an unsigned integer. Zero is kept, so a handler that needs at least one rejects it
+/// in `validate_options` instead of running with a substituted count.
+pub(crate) fn clamped_count_option(options: &mut Map<String, Value>,
+                                   key: &str,
+                                   default: u64,
+                                   cap: u64) {
+    let count = options.get(key)
+                       .and_then(Value::as_u64)
+                       .unwrap_or(default)
+                       .min(cap);
+    options.insert(key.to_string(), Value::from(count));
+}
+
+/// Option of every analysis that caps the memory of its Rscript process, in MB (Unix only).
+pub(crate) const MEMORY_LIMIT_OPTION: &str = "memory_limit_mb";
+
+/// The run's memory limit, or `None` when the option is not set.
+pub(crate) fn memory_limit_mb(options: &Value) -> Option<u64> {
+    options.get(MEMORY_LIMIT_OPTION).and_then(Value::as_u64)
+}
+
+/// Rejects a memory limit that is set but not a positive whole number of MB, so that a bad value
+/// never lets the analysis run without the cap the caller asked for.
+pub(crate) fn validate_memory_limit(options: &Value) -> Result<(), String> {
+    match options.get(MEMORY_LIMIT_OPTION) {
+        None | Some(Value::Null) => Ok(()),
+        Some(value) if value.as_u64().is_some_and(|limit_mb| limit_mb > 0) => Ok(()),
+        Some(value) => Err(format!("{} must be a positive integer (MB), got {}",
+                                   MEMORY_LIMIT_OPTION, value)),
+    }
+}
+
 pub(crate) fn sort_table_rows_by_factor_group(table: &mut ParsedDataTable) {
     table.rows.sort_by(|left, right| compare_factor_rows(left, right));
 }
@@ -85,6 +118,27 @@ fn compare_factor_rows(left: &[Value],
     first_cell_text(left).cmp(&first_cell_text(right))
 }
 
+/// Sorts rows by the absolute value of `column_index` in descending order.
+/// Cells may carry significance stars (e.g. "0.512**"); rows without a number keep their order at the end.
+pub(crate) fn sort_table_rows_by_abs_value(table: &mut ParsedDataTable,
+                                           column_index: usize) {
+    let abs_value = |row: &[Value]| {
+        row.get(column_index)
+           .and_then(|value| match value {
+               Value::String(text) => text.trim().trim_end_matches('*').parse::<f64>().ok(),
+               _ => option_f64_from_value(value),
+           })
+           .map(f64::abs)
+    };
+    table.rows
+         .sort_by(|left, right| match (abs_value(left), abs_value(right)) {
+             (Some(left), Some(right)) => right.partial_cmp(&left).unwrap_or(Ordering::Equal),
+             (Some(_), None) => Ordering::Less,
+             (None, Some(_)) => Ordering::Greater,
+             (None, None) => Ordering::Equal,
+         });
+}
+
 #[derive(Debug, Clone, Copy)]
 struct FactorProfile {
     dominant_factor: Option<usize>,
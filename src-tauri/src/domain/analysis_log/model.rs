@@ -5,6 +5,7 @@ use serde::{
 use serde_json::Value;
 
 use crate::domain::analysis::model::AnalysisResult;
+use crate::domain::input::metadata::ColumnMetadataMap;
 
 pub(crate) const ANALYSIS_LOG_SCHEMA_VERSION: u32 = 1;
 
@@ -32,6 +33,9 @@ pub(crate) struct AnalysisLogRecord {
     pub n: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub n_note: Option<String>,
+    /// Stored as `columnMetadata: { "<column>": { "unit": ..., "description": ... } }`.
+    #[serde(default, skip_serializing_if = "ColumnMetadataMap::is_empty")]
+    pub column_metadata: ColumnMetadataMap,
 }
 
 impl AnalysisLogRecord {
@@ -1,4 +1,11 @@
+pub(crate) mod metadata;
 pub(crate) mod numeric;
+pub(crate) mod paste;
+pub(crate) mod read_options;
+pub(crate) mod sheet;
 pub(crate) mod source_kind;
 pub(crate) mod string_mixed;
+pub(crate) mod synthetic;
 pub(crate) mod table;
+pub(crate) mod verify;
+pub(crate) mod workbook;
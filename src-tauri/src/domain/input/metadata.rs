@@ -0,0 +1,120 @@
+use indexmap::IndexMap;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use crate::domain::input::table::ParsedDataTable;
+
+/// Workbook sheet that is read as column metadata when none is supplied explicitly.
+pub(crate) const METADATA_SHEET_NAME: &str = "metadata";
+
+const COLUMN_HEADERS: [&str; 3] = ["column", "列", "変数"];
+const UNIT_HEADERS: [&str; 2] = ["unit", "単位"];
+const DESCRIPTION_HEADERS: [&str; 2] = ["description", "説明"];
+
+/// Display-only information about a column. It never changes analysis results.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub(crate) struct ColumnMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Column name -> metadata, in column order.
+pub(crate) type ColumnMetadataMap = IndexMap<String, ColumnMetadata>;
+
+/// Reads a metadata sheet with a column-name column and optional unit / description columns
+/// (English or Japanese headers, case-insensitive). Rows without a column name are skipped.
+pub(crate) fn column_metadata_from_table(table: &ParsedDataTable) -> Result<ColumnMetadataMap, String> {
+    let column_index =
+        find_header(&table.headers, &COLUMN_HEADERS).ok_or_else(|| {
+                                                        format!("metadata sheet needs a '{}' column",
+                                                                COLUMN_HEADERS[0])
+                                                    })?;
+    let unit_index = find_header(&table.headers, &UNIT_HEADERS);
+    let description_index = find_header(&table.headers, &DESCRIPTION_HEADERS);
+
+    let mut metadata = ColumnMetadataMap::new();
+    for row in &table.rows {
+        let Some(column) = cell_text(row.get(column_index)) else {
+            continue;
+        };
+        let entry =
+            ColumnMetadata { unit: unit_index.and_then(|index| cell_text(row.get(index))),
+                             description: description_index.and_then(|index| cell_text(row.get(index))) };
+        metadata.insert(column, entry);
+    }
+    Ok(metadata)
+}
+
+/// Keeps only the entries for `variables`, in variable order.
+pub(crate) fn select_column_metadata(metadata: &ColumnMetadataMap,
+                                     variables: &[String])
+                                     -> ColumnMetadataMap {
+    variables.iter()
+             .filter_map(|variable| {
+                 metadata.get(variable)
+                         .map(|entry| (variable.clone(), entry.clone()))
+             })
+             .collect()
+}
+
+fn find_header(headers: &[String],
+               candidates: &[&str])
+               -> Option<usize> {
+    headers.iter().position(|header| {
+                      let header = header.trim();
+                      candidates.iter()
+                                .any(|candidate| header.eq_ignore_ascii_case(candidate))
+                  })
+}
+
+/// Trimmed text of a scalar cell; `None` for missing, null and blank cells.
+pub(crate) fn cell_text(cell: Option<&Value>) -> Option<String> {
+    let text = match cell? {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(value) => value.to_string(),
+        _ => return None,
+    };
+    if text.is_empty() { None } else { Some(text) }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::domain::input::table::ParsedDataTable;
+
+    use super::{
+        ColumnMetadata,
+        column_metadata_from_table,
+        select_column_metadata,
+    };
+
+    #[test]
+    fn reads_units_and_descriptions_from_a_metadata_sheet() {
+        let table =
+            ParsedDataTable { headers: vec!["列".to_string(), "単位".to_string(), "説明".to_string()],
+                              rows: vec![vec!["length".into(), "mm".into(), "全長".into()],
+                                         vec!["temp".into(), "°C".into(), Value::Null],
+                                         vec![Value::Null, "kg".into(), Value::Null]],
+                              note: None,
+                              title: None,
+                              column_types: Vec::new() };
+
+        let metadata = column_metadata_from_table(&table).unwrap();
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata["length"],
+                   ColumnMetadata { unit: Some("mm".to_string()),
+                                    description: Some("全長".to_string()) });
+        assert_eq!(metadata["temp"].description, None);
+
+        let selected = select_column_metadata(&metadata, &["temp".to_string(), "other".to_string()]);
+        assert_eq!(selected.keys().collect::<Vec<_>>(), vec!["temp"]);
+    }
+}
@@ -1,13 +1,28 @@
 use indexmap::IndexMap;
 
+use crate::domain::input::metadata::ColumnMetadataMap;
+
 pub(crate) type NumericDataset = IndexMap<String, Vec<Option<f64>>>;
 
+/// Largest integer magnitude that `f64` represents exactly (2^53).
+/// Integers beyond this range (e.g. long IDs) change when converted to `f64`. The numeric path
+/// keeps the nearest `f64` and reports the cell through `CellWarnings`; the string-mixed path and
+/// `parse_table` keep them as-is.
+pub(crate) const MAX_EXACT_F64_INTEGER: i64 = 1 << 53;
+
+const INEXACT_INTEGER_REASON: &str = "integer exceeds the exact f64 range (|n| <= 2^53) and was rounded; IDs \
+                                      should not be used as numeric variables";
+
+/// Cell warnings listed individually before the rest are only counted.
+const MAX_LISTED_CELL_WARNINGS: usize = 20;
+
 #[derive(Clone, Debug)]
 pub(crate) struct NumericDatasetEntry {
     pub dataset: NumericDataset,
     pub path: String,
     pub sheet: String,
     pub variables: Vec<String>,
+    pub column_metadata: ColumnMetadataMap,
 }
 
 #[derive(Clone, Copy)]
@@ -35,6 +50,64 @@ impl<'a> NumericCellContext<'a> {
         format!("Numeric dataset validation error at row {} col {} ({}): {}",
                 row_no, col_no, self.header, reason)
     }
+
+    pub(crate) fn warning(&self,
+                          reason: &str)
+                          -> String {
+        format!("Numeric dataset warning at row {} col {} ({}): {}",
+                self.row_index + 2,
+                self.col_index + 1,
+                self.header,
+                reason)
+    }
+}
+
+/// Per-cell warnings raised while a numeric dataset is built. The cells still load; only the
+/// first few are listed so that a long ID column does not flood the result.
+#[derive(Debug, Default)]
+pub(crate) struct CellWarnings {
+    listed: Vec<String>,
+    omitted: usize,
+}
+
+impl CellWarnings {
+    /// Warns when `raw` is an integer beyond the exact `f64` range.
+    pub(crate) fn check_integer_text(&mut self,
+                                     raw: &str,
+                                     context: NumericCellContext<'_>) {
+        if raw.trim()
+              .parse::<i128>()
+              .is_ok_and(|integer| integer.unsigned_abs() > MAX_EXACT_F64_INTEGER as u128)
+        {
+            self.push(context.warning(INEXACT_INTEGER_REASON));
+        }
+    }
+
+    /// Warns when `value` is beyond the exact `f64` range.
+    pub(crate) fn check_integer(&mut self,
+                                value: i64,
+                                context: NumericCellContext<'_>) {
+        if value.unsigned_abs() > MAX_EXACT_F64_INTEGER as u64 {
+            self.push(context.warning(INEXACT_INTEGER_REASON));
+        }
+    }
+
+    fn push(&mut self,
+            warning: String) {
+        if self.listed.len() < MAX_LISTED_CELL_WARNINGS {
+            self.listed.push(warning);
+        } else {
+            self.omitted += 1;
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<String> {
+        if self.omitted > 0 {
+            self.listed
+                .push(format!("{} more cell warnings were not listed", self.omitted));
+        }
+        self.listed
+    }
 }
 
 pub(crate) fn parse_numeric_string(value: &str,
@@ -60,3 +133,58 @@ pub(crate) fn parse_finite_number(value: f64,
         Err(context.error("value is not finite"))
     }
 }
+
+/// Renders the dataset as CSV: a header row with the columns in insertion order, then one line
+/// per row with missing values as empty cells. Quoting follows the `csv` crate (RFC 4180).
+pub(crate) fn numeric_dataset_to_csv(dataset: &NumericDataset) -> Result<String, String> {
+    let row_count = dataset.values().next().map_or(0, Vec::len);
+    if let Some((name, values)) = dataset.iter().find(|(_, values)| values.len() != row_count) {
+        return Err(format!("column '{}' has {} rows, expected {}",
+                           name,
+                           values.len(),
+                           row_count));
+    }
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(dataset.keys())
+          .map_err(|e| format!("failed to write CSV header: {}", e))?;
+    for row_index in 0..row_count {
+        let record = dataset.values().map(|values| match values[row_index] {
+                                         Some(value) => value.to_string(),
+                                         None => String::new(),
+                                     });
+        writer.write_record(record)
+              .map_err(|e| format!("failed to write CSV row {}: {}", row_index + 1, e))?;
+    }
+    let bytes = writer.into_inner()
+                      .map_err(|e| format!("failed to finish CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("CSV is not valid UTF-8: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        NumericDataset,
+        numeric_dataset_to_csv,
+    };
+
+    #[test]
+    fn missing_values_become_blank_cells_in_insertion_order() {
+        let mut dataset = NumericDataset::new();
+        dataset.insert("score".to_string(), vec![Some(1.5), None, Some(3.0)]);
+        dataset.insert("age, years".to_string(), vec![None, Some(20.0), Some(-0.25)]);
+
+        let csv = numeric_dataset_to_csv(&dataset).unwrap();
+
+        assert_eq!(csv, "score,\"age, years\"\n1.5,\n,20\n3,-0.25\n");
+    }
+
+    #[test]
+    fn columns_of_different_lengths_are_rejected() {
+        let mut dataset = NumericDataset::new();
+        dataset.insert("a".to_string(), vec![Some(1.0)]);
+        dataset.insert("b".to_string(), vec![Some(1.0), Some(2.0)]);
+
+        assert!(numeric_dataset_to_csv(&dataset).is_err());
+    }
+}
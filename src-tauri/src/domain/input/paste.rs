@@ -0,0 +1,150 @@
+use csv::ReaderBuilder;
+use serde_json::Value;
+
+use crate::domain::input::numeric::MAX_EXACT_F64_INTEGER;
+use crate::domain::input::read_options::{
+    TableReadOptions,
+    missing_token_note,
+};
+use crate::domain::input::table::{
+    ParsedDataTable,
+    infer_column_types,
+    join_notes,
+    number_cell,
+    validate_unique_headers,
+};
+
+/// Parses tab-separated text copied from a spreadsheet into a table.
+/// Cells that read as finite numbers become numbers and everything else stays text; blank cells
+/// and missing-value tokens become null as in file import. Without `has_header`, and for blank
+/// header cells, columns are named by `options.blank_header_strategy`. Rows shorter than the
+/// widest row are padded with nulls and reported in the note.
+pub(crate) fn parse_pasted_tsv(text: &str,
+                               has_header: bool,
+                               options: &TableReadOptions)
+                               -> Result<ParsedDataTable, String> {
+    // Excel quotes cells that contain tabs or line breaks, so the text is read as quoted TSV.
+    let mut reader = ReaderBuilder::new().delimiter(b'\t')
+                                         .has_headers(false)
+                                         .flexible(true)
+                                         .from_reader(text.as_bytes());
+    let mut records = reader.records()
+                            .map(|record| {
+                                record.map(|record| record.iter().map(str::to_string).collect::<Vec<_>>())
+                                      .map_err(|e| format!("Failed to read pasted row: {}", e))
+                            })
+                            .collect::<Result<Vec<_>, String>>()?;
+    if records.is_empty() {
+        return Err("Pasted text is empty".to_string());
+    }
+
+    let width = records.iter().map(Vec::len).max().unwrap_or(0);
+    let header_cells = if has_header { records.remove(0) } else { Vec::new() };
+    let headers: Vec<String> =
+        (0..width).map(|index| match header_cells.get(index).map(|cell| cell.trim()) {
+                      Some(name) if !name.is_empty() => name.to_string(),
+                      _ => options.blank_header_name(index, 0),
+                  })
+                  .collect();
+    validate_unique_headers(&headers)?;
+
+    let mut converted_tokens = 0;
+    let mut padded_rows = 0;
+    let rows: Vec<Vec<Value>> = records.into_iter()
+                                       .map(|record| {
+                                           if record.len() < width {
+                                               padded_rows += 1;
+                                           }
+                                           let mut row: Vec<Value> =
+                                               record.iter()
+                                                     .map(|cell| {
+                                                         if options.is_missing_token(cell) {
+                                                             converted_tokens += 1;
+                                                             return Value::Null;
+                                                         }
+                                                         pasted_cell_to_json_value(cell)
+                                                     })
+                                                     .collect();
+                                           row.resize(width, Value::Null);
+                                           row
+                                       })
+                                       .collect();
+
+    let padded_note = (padded_rows > 0).then(|| {
+                          format!("列数が {} 未満の {} 行は、不足する列を欠損として補いました",
+                                  width, padded_rows)
+                      });
+    let column_types = infer_column_types(width, &rows);
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: join_notes([missing_token_note(converted_tokens), padded_note]),
+                         title: None,
+                         column_types })
+}
+
+/// Integers beyond the exact `f64` range (e.g. long IDs) stay text so that they are not altered.
+fn pasted_cell_to_json_value(cell: &str) -> Value {
+    let trimmed = cell.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    if trimmed.parse::<i128>()
+              .is_ok_and(|integer| integer.unsigned_abs() > MAX_EXACT_F64_INTEGER as u128)
+    {
+        return Value::String(cell.to_string());
+    }
+    match trimmed.parse::<f64>() {
+        Ok(number) if number.is_finite() => number_cell(Some(number)),
+        _ => Value::String(cell.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::parse_pasted_tsv;
+    use crate::domain::input::read_options::TableReadOptions;
+
+    #[test]
+    fn headered_paste_infers_numbers_and_missing_tokens() {
+        let text = "id\tscore\tgroup\r\n1\t3.5\ta\r\n2\tNA\tb\r\n";
+
+        let table = parse_pasted_tsv(text, true, &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["id", "score", "group"]);
+        assert_eq!(table.rows,
+                   vec![vec![Value::from(1.0), Value::from(3.5), Value::from("a")],
+                        vec![Value::from(2.0), Value::Null, Value::from("b")]]);
+        assert!(table.note.unwrap().contains("1 個のセルを欠損"));
+    }
+
+    #[test]
+    fn headerless_paste_gets_generated_column_names() {
+        let text = "1\tx\n2\ty\n";
+
+        let table = parse_pasted_tsv(text, false, &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["col_1", "col_2"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0], vec![Value::from(1.0), Value::from("x")]);
+        assert!(table.note.is_none());
+
+        assert!(parse_pasted_tsv("", false, &TableReadOptions::default()).is_err());
+    }
+
+    #[test]
+    fn ragged_paste_is_padded_to_the_widest_row() {
+        let text = "a\tb\n1\n2\t3\t4\n\"multi\nline\"\t\n";
+
+        let table = parse_pasted_tsv(text, true, &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["a", "b", "col_3"]);
+        assert_eq!(table.rows,
+                   vec![vec![Value::from(1.0), Value::Null, Value::Null],
+                        vec![Value::from(2.0), Value::from(3.0), Value::from(4.0)],
+                        vec![Value::from("multi\nline"), Value::Null, Value::Null]]);
+        table.validate().unwrap();
+        assert!(table.note.unwrap().contains("2 行"));
+    }
+}
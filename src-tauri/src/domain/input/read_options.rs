@@ -0,0 +1,198 @@
+use serde::Deserialize;
+
+/// Tokens treated as missing when `missing_tokens` is not given.
+pub(crate) const DEFAULT_MISSING_TOKENS: [&str; 5] = ["NA", "N/A", "null", "-", "."];
+
+/// How a boolean cell in the header row is turned into a column name.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BoolHeaderStrategy {
+    /// Legacy behavior: every boolean header becomes the literal "TRUE/FALSE".
+    Placeholder,
+    /// Use the cell's actual value ("TRUE" or "FALSE").
+    #[default]
+    Literal,
+    /// Treat the cell like a blank header (see `BlankHeaderStrategy`).
+    ColumnN,
+}
+
+/// How a blank header cell is named.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BlankHeaderStrategy {
+    /// `col_{n}` with the 1-based column position.
+    #[default]
+    ColumnN,
+    /// Spreadsheet column letters (`A`, `B`, ..., `Z`, `AA`, ...) of the cell's sheet column, so a
+    /// table starting in column C names its first blank header `C`. Text sources start at `A`.
+    ExcelLetter,
+    /// `blank_header_prefix` followed by the 1-based column position.
+    CustomPrefix,
+}
+
+/// What an Excel error cell (`#DIV/0!`, `#N/A` and so on) becomes when a sheet is read.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CellErrorPolicy {
+    /// Keep the error symbol as text (e.g. "#DIV/0!"). Numeric datasets still reject it.
+    #[default]
+    Symbol,
+    /// Read the cell as missing.
+    Null,
+    /// Reject the sheet, naming the first error cell.
+    Fail,
+}
+
+/// Options that change how a sheet is turned into headers and cells.
+/// Every import path (table preview and dataset builders) must receive the same options
+/// so that column names match between `parse_table` and the dataset commands.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct TableReadOptions {
+    pub bool_header_strategy: BoolHeaderStrategy,
+    pub blank_header_strategy: BlankHeaderStrategy,
+    /// Used by `BlankHeaderStrategy::CustomPrefix`.
+    pub blank_header_prefix: String,
+    /// Cell texts (compared after trimming) that are read as missing, in addition to blank cells.
+    pub missing_tokens: Vec<String>,
+    pub missing_tokens_case_sensitive: bool,
+    pub cell_error_policy: CellErrorPolicy,
+    /// 1-based sheet row (as numbered in Excel) holding the column names; the rows above it, such
+    /// as a title, are skipped. `None` uses the first non-empty row. Only supported for XLSX sheets.
+    pub header_row: Option<usize>,
+    /// A1-style rectangle (e.g. `B2:F200`) to read instead of the whole sheet; its first row is
+    /// the header. Parts outside the sheet's data are ignored. Only supported for XLSX sheets,
+    /// and not together with `header_row`.
+    pub range: Option<String>,
+}
+
+impl Default for TableReadOptions {
+    fn default() -> Self {
+        Self { bool_header_strategy: BoolHeaderStrategy::default(),
+               blank_header_strategy: BlankHeaderStrategy::default(),
+               blank_header_prefix: "col_".to_string(),
+               missing_tokens: DEFAULT_MISSING_TOKENS.iter()
+                                                     .map(|token| token.to_string())
+                                                     .collect(),
+               missing_tokens_case_sensitive: false,
+               cell_error_policy: CellErrorPolicy::default(),
+               header_row: None,
+               range: None }
+    }
+}
+
+impl TableReadOptions {
+    /// Returns true when the (non-blank) cell text matches one of the missing-value tokens.
+    /// The whole cell must match, so "NA" is missing but "NATO" is not.
+    pub(crate) fn is_missing_token(&self,
+                                   value: &str)
+                                   -> bool {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return false;
+        }
+        self.missing_tokens.iter().any(|token| {
+                                      if self.missing_tokens_case_sensitive {
+                                          token.trim() == trimmed
+                                      } else {
+                                          token.trim().eq_ignore_ascii_case(trimmed)
+                                      }
+                                  })
+    }
+
+    /// Name of a blank header cell at the 0-based `col_index` of a table whose first column is the
+    /// 0-based sheet column `first_column` (0 for text sources). Only Excel letters use the sheet
+    /// column; the other strategies number the table's own columns.
+    /// The name is not checked against the other headers; callers still validate uniqueness.
+    pub(crate) fn blank_header_name(&self,
+                                    col_index: usize,
+                                    first_column: usize)
+                                    -> String {
+        match self.blank_header_strategy {
+            BlankHeaderStrategy::ColumnN => format!("col_{}", col_index + 1),
+            BlankHeaderStrategy::ExcelLetter => excel_column_letter(first_column + col_index),
+            BlankHeaderStrategy::CustomPrefix => format!("{}{}", self.blank_header_prefix, col_index + 1),
+        }
+    }
+
+    /// The parsed `range`; `None` when the whole sheet is read.
+    pub(crate) fn cell_range(&self) -> Result<Option<CellRange>, String> {
+        self.range.as_deref().map(CellRange::parse).transpose()
+    }
+
+    /// Returns true for blank cell text or a missing-value token.
+    pub(crate) fn is_missing_text(&self,
+                                  value: &str)
+                                  -> bool {
+        value.trim().is_empty() || self.is_missing_token(value)
+    }
+}
+
+/// Rectangle of sheet cells with 0-based (row, column) corners, both inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct CellRange {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+impl CellRange {
+    /// Parses an A1-style range such as `B2:F200`. Letters may be lower case, `$` markers are
+    /// ignored, and the corners may be given in either order.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!("Invalid range '{}': expected two A1-style corners such as B2:F200",
+                    text.trim())
+        };
+        let (first, second) = text.split_once(':').ok_or_else(invalid)?;
+        let first = parse_cell_reference(first).ok_or_else(invalid)?;
+        let second = parse_cell_reference(second).ok_or_else(invalid)?;
+        Ok(Self { start: (first.0.min(second.0), first.1.min(second.1)),
+                  end: (first.0.max(second.0), first.1.max(second.1)) })
+    }
+}
+
+/// 0-based (row, column) of an A1-style cell reference: `B2` -> (1, 1).
+fn parse_cell_reference(text: &str) -> Option<(u32, u32)> {
+    let text = text.trim().replace('$', "");
+    let (letters, digits) = text.split_at(text.find(|c: char| !c.is_ascii_alphabetic())?);
+    // Three letters cover Excel's last column (XFD).
+    if letters.is_empty() || letters.len() > 3 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let col = letters.bytes().fold(0, |col, b| {
+                                 col * 26 + u32::from(b.to_ascii_uppercase() - b'A') + 1
+                             });
+    let row = digits.parse::<u32>().ok()?.checked_sub(1)?;
+    Some((row, col - 1))
+}
+
+/// Bijective base-26 column letters: 0 -> "A", 25 -> "Z", 26 -> "AA".
+fn excel_column_letter(col_index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = col_index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push(b'A' + rem as u8);
+        n = (n - 1) / 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).expect("ASCII letters")
+}
+
+/// Note shown with a parsed table when missing-value tokens were converted to null.
+pub(crate) fn missing_token_note(converted: usize) -> Option<String> {
+    if converted == 0 {
+        return None;
+    }
+    Some(format!("欠損値トークン (NA など) を含む {} 個のセルを欠損として読み込みました",
+                 converted))
+}
+
+/// Note shown with a parsed table when Excel error cells were read as missing.
+pub(crate) fn cell_error_note(converted: usize) -> Option<String> {
+    if converted == 0 {
+        return None;
+    }
+    Some(format!("Excel のエラー値 (#DIV/0! など) を含む {} 個のセルを欠損として読み込みました",
+                 converted))
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+
+/// A workbook sheet and whether Excel shows it in the sheet tabs.
+/// Hidden and "very hidden" sheets are both reported as `visible: false`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct SheetInfo {
+    pub name: String,
+    pub visible: bool,
+}
+
+/// How a sheet is shown in Excel's sheet tabs.
+/// `VeryHidden` sheets can only be unhidden from VBA, not from the Unhide dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SheetVisibility {
+    Visible,
+    Hidden,
+    VeryHidden,
+}
+
+/// Sheet-level metadata for `get_sheet_properties`.
+/// `protected` and `tab_color` are `None` when the file format does not expose them (e.g. `.xls`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SheetProperties {
+    pub name: String,
+    pub visibility: SheetVisibility,
+    pub protected: Option<bool>,
+    /// `#RRGGBB`; only explicit RGB tab colors are reported, theme or indexed colors are `None`.
+    pub tab_color: Option<String>,
+}
+
+/// Pre-flight summary of one sheet for `validate_workbook`.
+/// An unreadable sheet is reported with `readable: false` and the reason in `error`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SheetReadiness {
+    pub name: String,
+    pub readable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub has_duplicate_headers: bool,
+    pub duplicate_headers: Vec<String>,
+    pub row_count: usize,
+    pub column_count: usize,
+    /// Columns with at least one value where every non-missing cell is numeric.
+    pub numeric_column_count: usize,
+    /// Cells below the header row that hold a number, in any column.
+    pub numeric_cell_count: usize,
+    /// Header cells that hold non-empty text rather than a number, date or blank.
+    pub text_header_count: usize,
+}
+
+/// How likely one sheet is to hold the workbook's main data table, for `guess_data_sheet`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataSheetScore {
+    pub name: String,
+    pub score: f64,
+    /// Data rows times header columns.
+    pub used_cells: usize,
+    pub numeric_fraction: f64,
+    /// Every header cell is distinct, non-empty text.
+    pub plausible_header: bool,
+}
+
+/// The best data sheet candidate and the scores of every sheet, in workbook order.
+/// `best` is `None` when no sheet has any data rows.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataSheetGuess {
+    pub best: Option<String>,
+    pub sheets: Vec<DataSheetScore>,
+}
+
+/// Scores each sheet by the log of its used-range size, weighted up by its share of numeric cells
+/// and halved when the first row does not look like a header. Unreadable sheets score 0.
+/// Ties go to the earlier sheet.
+pub(crate) fn guess_data_sheet(sheets: &[SheetReadiness]) -> DataSheetGuess {
+    let scores: Vec<DataSheetScore> = sheets.iter().map(score_data_sheet).collect();
+    let best = scores.iter()
+                     .filter(|sheet| sheet.score > 0.0)
+                     .fold(None::<&DataSheetScore>, |best, sheet| match best {
+                         Some(best) if best.score >= sheet.score => Some(best),
+                         _ => Some(sheet),
+                     })
+                     .map(|sheet| sheet.name.clone());
+    DataSheetGuess { best, sheets: scores }
+}
+
+fn score_data_sheet(sheet: &SheetReadiness) -> DataSheetScore {
+    let used_cells = sheet.row_count * sheet.column_count;
+    let numeric_fraction = if used_cells == 0 {
+        0.0
+    } else {
+        sheet.numeric_cell_count as f64 / used_cells as f64
+    };
+    let plausible_header = sheet.column_count > 0
+                           && sheet.text_header_count == sheet.column_count
+                           && !sheet.has_duplicate_headers;
+    let score = if sheet.readable && used_cells > 0 {
+        let header_weight = if plausible_header { 1.0 } else { 0.5 };
+        (used_cells as f64).ln_1p() * (1.0 + numeric_fraction) * header_weight
+    } else {
+        0.0
+    };
+    DataSheetScore { name: sheet.name.clone(),
+                     score,
+                     used_cells,
+                     numeric_fraction,
+                     plausible_header }
+}
@@ -3,6 +3,7 @@ use std::path::Path;
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum DataSourceKind {
     Csv,
+    Tsv,
     Xlsx,
 }
 
@@ -13,6 +14,7 @@ impl DataSourceKind {
                                  .map(|value| value.to_ascii_lowercase());
         match ext.as_deref() {
             Some("csv") => Ok(DataSourceKind::Csv),
+            Some("tsv") => Ok(DataSourceKind::Tsv),
             Some("xlsx") | Some("xls") => Ok(DataSourceKind::Xlsx),
             Some(other) => Err(format!("Unsupported file type: .{}", other)),
             None => Err("Unsupported file type: missing extension".to_string()),
@@ -22,6 +24,7 @@ impl DataSourceKind {
     pub(crate) fn as_str(&self) -> &'static str {
         match self {
             DataSourceKind::Csv => "csv",
+            DataSourceKind::Tsv => "tsv",
             DataSourceKind::Xlsx => "xlsx",
         }
     }
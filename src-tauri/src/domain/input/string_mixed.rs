@@ -1,11 +1,35 @@
 use indexmap::IndexMap;
 use serde::Serialize;
 
+use crate::domain::input::metadata::ColumnMetadataMap;
+use crate::domain::input::numeric::{
+    NumericCellContext,
+    parse_numeric_string,
+};
+
 /// Dataset where every cell is stored as an optional string.
 /// Suitable for analyses that mix numeric and categorical variables (e.g., ANOVA).
 /// R receives the raw strings and handles type conversion (e.g., as.factor()).
 pub(crate) type StringMixedDataset = IndexMap<String, Vec<Option<String>>>;
 
+/// Reads the cells of one column as numbers, the same way a numeric dataset is built: missing
+/// and blank cells are `None`, and text that is not a finite number is an error naming the cell.
+/// `col_index` and `column` are the column's position and name in the dataset.
+pub(crate) fn parse_numeric_cells(raw_values: &[Option<String>],
+                                  col_index: usize,
+                                  column: &str)
+                                  -> Result<Vec<Option<f64>>, String> {
+    raw_values.iter()
+              .enumerate()
+              .map(|(row_index, raw)| match raw {
+                  Some(raw) => {
+                      parse_numeric_string(raw, NumericCellContext::new(row_index, col_index, column))
+                  },
+                  None => Ok(None),
+              })
+              .collect()
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub(crate) struct StringMixedDatasetEntry {
     #[serde(flatten)]
@@ -16,4 +40,6 @@ pub(crate) struct StringMixedDatasetEntry {
     pub sheet: String,
     #[serde(skip)]
     pub variables: Vec<String>,
+    #[serde(skip)]
+    pub column_metadata: ColumnMetadataMap,
 }
@@ -0,0 +1,235 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::table::{
+    ParsedDataTable,
+    number_cell,
+};
+use crate::domain::rng::SplitMix64;
+
+/// Upper bound on generated rows, so that a typo cannot freeze the UI.
+pub(crate) const MAX_SAMPLE_ROWS: usize = 1_000_000;
+
+/// What to generate: `rows` rows of the given columns, reproducible from `seed`.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct SampleDatasetSpec {
+    pub rows: usize,
+    pub seed: u64,
+    pub columns: Vec<SampleColumnSpec>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct SampleColumnSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub distribution: SampleDistribution,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "distribution", rename_all = "lowercase")]
+pub(crate) enum SampleDistribution {
+    Normal {
+        mean: f64,
+        sd: f64,
+    },
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    Exponential {
+        rate: f64,
+    },
+    /// Levels drawn with the given proportions (relative weights, equal when omitted).
+    Categorical {
+        levels: Vec<String>,
+        #[serde(default)]
+        proportions: Option<Vec<f64>>,
+    },
+}
+
+/// Generates a table from `spec`. The same spec and seed always give the same table.
+///
+/// Columns are filled one after another from a single SplitMix64 stream, so appending a column
+/// leaves the earlier ones unchanged. Normal values use the Box-Muller transform and exponential
+/// values the inverse CDF.
+pub(crate) fn generate_sample_dataset(spec: &SampleDatasetSpec) -> Result<ParsedDataTable, String> {
+    if spec.rows == 0 || spec.rows > MAX_SAMPLE_ROWS {
+        return Err(format!("rows must be between 1 and {}", MAX_SAMPLE_ROWS));
+    }
+    if spec.columns.is_empty() {
+        return Err("at least one column is required".to_string());
+    }
+    for (index, column) in spec.columns.iter().enumerate() {
+        if column.name.trim().is_empty() {
+            return Err(format!("column {} has no name", index + 1));
+        }
+        if spec.columns[..index].iter()
+                                .any(|earlier| earlier.name == column.name)
+        {
+            return Err(format!("column '{}' is specified more than once", column.name));
+        }
+        validate_distribution(&column.distribution).map_err(|e| format!("column '{}': {}", column.name, e))?;
+    }
+
+    let mut rng = SplitMix64::new(spec.seed);
+    let columns: Vec<Vec<Value>> = spec.columns
+                                       .iter()
+                                       .map(|column| {
+                                           (0..spec.rows).map(|_| draw(&column.distribution, &mut rng))
+                                                         .collect()
+                                       })
+                                       .collect();
+    let rows =
+        (0..spec.rows).map(|row_index| columns.iter().map(|column| column[row_index].clone()).collect())
+                      .collect();
+
+    Ok(ParsedDataTable { headers: spec.columns.iter().map(|column| column.name.clone()).collect(),
+                         rows,
+                         note: Some(format!("乱数シード {} で生成したサンプルデータです", spec.seed)),
+                         title: Some("サンプルデータ".to_string()),
+                         column_types: Vec::new() })
+}
+
+fn validate_distribution(distribution: &SampleDistribution) -> Result<(), String> {
+    match distribution {
+        SampleDistribution::Normal { mean, sd } => {
+            if !mean.is_finite() || !sd.is_finite() || *sd <= 0.0 {
+                return Err("normal needs a finite mean and a positive sd".to_string());
+            }
+        },
+        SampleDistribution::Uniform { min, max } => {
+            if !min.is_finite() || !max.is_finite() || min >= max {
+                return Err("uniform needs finite bounds with min < max".to_string());
+            }
+        },
+        SampleDistribution::Exponential { rate } => {
+            if !rate.is_finite() || *rate <= 0.0 {
+                return Err("exponential needs a positive rate".to_string());
+            }
+        },
+        SampleDistribution::Categorical { levels, proportions } => {
+            if levels.is_empty() {
+                return Err("categorical needs at least one level".to_string());
+            }
+            if let Some(proportions) = proportions {
+                if proportions.len() != levels.len() {
+                    return Err(format!("{} proportions were given for {} levels",
+                                       proportions.len(),
+                                       levels.len()));
+                }
+                if proportions.iter().any(|p| !p.is_finite() || *p < 0.0) {
+                    return Err("proportions must not be negative".to_string());
+                }
+                if proportions.iter().sum::<f64>() <= 0.0 {
+                    return Err("proportions must not all be zero".to_string());
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn draw(distribution: &SampleDistribution,
+        rng: &mut SplitMix64)
+        -> Value {
+    match distribution {
+        SampleDistribution::Normal { mean, sd } => {
+            // 1 - u lies in (0, 1], so the logarithm is finite.
+            let radius = (-2.0 * (1.0 - rng.next_f64()).ln()).sqrt();
+            let angle = std::f64::consts::TAU * rng.next_f64();
+            number_cell(Some(mean + sd * radius * angle.cos()))
+        },
+        SampleDistribution::Uniform { min, max } => number_cell(Some(min + (max - min) * rng.next_f64())),
+        SampleDistribution::Exponential { rate } => number_cell(Some(-(1.0 - rng.next_f64()).ln() / rate)),
+        SampleDistribution::Categorical { levels, proportions } => {
+            let weights: Vec<f64> = match proportions {
+                Some(proportions) => proportions.clone(),
+                None => vec![1.0; levels.len()],
+            };
+            let mut target = rng.next_f64() * weights.iter().sum::<f64>();
+            let index =
+                weights.iter()
+                       .position(|weight| {
+                           target -= weight;
+                           target < 0.0
+                       })
+                       .unwrap_or_else(|| weights.iter().rposition(|weight| *weight > 0.0).unwrap_or(0));
+            Value::from(levels[index].as_str())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use super::{
+        SampleDatasetSpec,
+        generate_sample_dataset,
+    };
+
+    fn spec(seed: u64) -> SampleDatasetSpec {
+        serde_json::from_value(json!({
+            "rows": 5000,
+            "seed": seed,
+            "columns": [
+                { "name": "score", "distribution": "normal", "mean": 50.0, "sd": 10.0 },
+                { "name": "wait", "distribution": "exponential", "rate": 0.5 },
+                { "name": "group", "distribution": "categorical",
+                  "levels": ["A", "B"], "proportions": [3.0, 1.0] }
+            ]
+        })).unwrap()
+    }
+
+    fn numbers(rows: &[Vec<Value>],
+               col_index: usize)
+               -> Vec<f64> {
+        rows.iter().map(|row| row[col_index].as_f64().unwrap()).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_table() {
+        let first = generate_sample_dataset(&spec(7)).unwrap();
+        let second = generate_sample_dataset(&spec(7)).unwrap();
+        let other = generate_sample_dataset(&spec(8)).unwrap();
+
+        assert_eq!(first.headers, vec!["score", "wait", "group"]);
+        assert_eq!(first.rows, second.rows);
+        assert_ne!(first.rows, other.rows);
+        first.validate().unwrap();
+    }
+
+    #[test]
+    fn values_follow_the_requested_distributions() {
+        let table = generate_sample_dataset(&spec(2024)).unwrap();
+        let n = table.rows.len() as f64;
+
+        let score = numbers(&table.rows, 0);
+        let mean = score.iter().sum::<f64>() / n;
+        let sd = (score.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        assert!((mean - 50.0).abs() < 0.5, "mean {}", mean);
+        assert!((sd - 10.0).abs() < 0.5, "sd {}", sd);
+
+        let wait = numbers(&table.rows, 1);
+        assert!(wait.iter().all(|x| *x >= 0.0));
+        assert!((wait.iter().sum::<f64>() / n - 2.0).abs() < 0.15);
+
+        let share_a = table.rows.iter().filter(|row| row[2] == "A").count() as f64 / n;
+        assert!((share_a - 0.75).abs() < 0.03, "share {}", share_a);
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let mut invalid = spec(1);
+        invalid.columns[0] =
+            serde_json::from_value(json!({ "name": "score", "distribution": "normal", "mean": 0.0, "sd": 0.0 }))
+                .unwrap();
+
+        let err = generate_sample_dataset(&invalid).unwrap_err();
+        assert!(err.contains("column 'score'"), "{}", err);
+        assert!(err.contains("positive sd"), "{}", err);
+    }
+}
@@ -1,3 +1,7 @@
+use chrono::{
+    NaiveDate,
+    NaiveDateTime,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -5,6 +9,166 @@ use serde::{
 use serde_json::Value;
 use std::collections::HashSet;
 
+use crate::domain::input::numeric::{
+    NumericCellContext,
+    parse_numeric_string,
+};
+
+/// Metadata written as a YAML front-matter block above an exported Markdown table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MarkdownFrontMatter {
+    pub analysis_type: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub parameters: serde_json::Map<String, Value>,
+}
+
+/// How `ParsedDataTable::normalize_widths` repairs rows whose length differs from the headers.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WidthStrategy {
+    /// Fill short rows with null. Extra cells may only be dropped when they are all null.
+    Pad,
+    /// Fill short rows with null and drop extra cells, whatever they hold.
+    Truncate,
+}
+
+/// Number of rows `ParsedDataTable::normalize_widths` changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WidthAdjustment {
+    pub padded: usize,
+    pub truncated: usize,
+}
+
+impl WidthAdjustment {
+    /// User-facing summary, or `None` when no row was changed.
+    pub(crate) fn note(&self) -> Option<String> {
+        (self.padded + self.truncated > 0).then(|| {
+                                              format!("列数が見出しと合わない {} 行を補正しました (不足を欠損で補完 {} 行、余分なセルを削除 {} 行)",
+                                                      self.padded + self.truncated,
+                                                      self.padded,
+                                                      self.truncated)
+                                          })
+    }
+}
+
+/// What the values of a column are, as inferred by `infer_column_types`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ColumnType {
+    /// Numbers, at least one of them fractional.
+    Numeric,
+    /// Whole numbers only.
+    Integer,
+    Boolean,
+    Date,
+    Text,
+    /// Values of more than one of the other types.
+    Mixed,
+}
+
+/// Date (and date-time) spellings recognized in text cells.
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%Y/%m/%d"];
+const DATE_TIME_FORMATS: [&str; 6] = ["%Y-%m-%d %H:%M:%S",
+                                      "%Y-%m-%d %H:%M",
+                                      "%Y-%m-%dT%H:%M:%S",
+                                      "%Y-%m-%dT%H:%M",
+                                      "%Y/%m/%d %H:%M:%S",
+                                      "%Y/%m/%d %H:%M"];
+
+impl ColumnType {
+    /// Type of a single cell; `None` for null and blank cells. Text is typed by its content, so
+    /// `"12"` is an integer and `"2024-01-15"` a date.
+    pub(crate) fn of_cell(cell: &Value) -> Option<Self> {
+        match cell {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Number(number) if number.is_i64() || number.is_u64() => Some(Self::Integer),
+            Value::Number(_) => Some(Self::Numeric),
+            Value::String(text) => {
+                let text = text.trim();
+                if text.is_empty() {
+                    None
+                } else if text.parse::<i128>().is_ok() {
+                    Some(Self::Integer)
+                } else if text.parse::<f64>().is_ok_and(f64::is_finite) {
+                    Some(Self::Numeric)
+                } else if text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false") {
+                    Some(Self::Boolean)
+                } else if is_date_text(text) {
+                    Some(Self::Date)
+                } else {
+                    Some(Self::Text)
+                }
+            },
+            Value::Array(_) | Value::Object(_) => Some(Self::Text),
+        }
+    }
+
+    /// Type of a column holding values of both types: integers and fractional numbers are
+    /// `Numeric` together, any other disagreement is `Mixed`.
+    fn merge(self,
+             other: Self)
+             -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Integer | Self::Numeric, Self::Integer | Self::Numeric) => Self::Numeric,
+            _ => Self::Mixed,
+        }
+    }
+}
+
+fn is_date_text(text: &str) -> bool {
+    DATE_FORMATS.iter()
+                .any(|format| NaiveDate::parse_from_str(text, format).is_ok())
+    || DATE_TIME_FORMATS.iter()
+                        .any(|format| NaiveDateTime::parse_from_str(text, format).is_ok())
+}
+
+/// Folds cell types into the types of `width` columns. Readers that know more about a cell than
+/// its JSON value (e.g. an Excel date) pass the type themselves.
+pub(crate) struct ColumnTypeInference {
+    columns: Vec<Option<ColumnType>>,
+}
+
+impl ColumnTypeInference {
+    pub(crate) fn new(width: usize) -> Self {
+        Self { columns: vec![None; width] }
+    }
+
+    /// Records one cell; cells beyond the width and missing cells (`None`) are ignored.
+    pub(crate) fn observe(&mut self,
+                          col_index: usize,
+                          cell_type: Option<ColumnType>) {
+        if let (Some(column), Some(cell_type)) = (self.columns.get_mut(col_index), cell_type) {
+            *column = Some(column.map_or(cell_type, |current| current.merge(cell_type)));
+        }
+    }
+
+    /// A column without any value is `Text`.
+    pub(crate) fn finish(self) -> Vec<ColumnType> {
+        self.columns
+            .into_iter()
+            .map(|column| column.unwrap_or(ColumnType::Text))
+            .collect()
+    }
+}
+
+/// Infers the type of every column from all of its cells (see `ColumnType::of_cell`).
+pub(crate) fn infer_column_types(width: usize,
+                                 rows: &[Vec<Value>])
+                                 -> Vec<ColumnType> {
+    let mut inference = ColumnTypeInference::new(width);
+    for row in rows {
+        for (col_index, cell) in row.iter().enumerate() {
+            inference.observe(col_index, ColumnType::of_cell(cell));
+        }
+    }
+    inference.finish()
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct ParsedDataTable {
     pub headers: Vec<String>,
@@ -13,30 +177,250 @@ pub(crate) struct ParsedDataTable {
     pub note: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Inferred type of every column, in header order. Tables read from a file or pasted text
+    /// carry them; computed result tables leave the list empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_types: Vec<ColumnType>,
 }
 
 impl ParsedDataTable {
+    /// Position of the column named `name`.
+    pub(crate) fn column_index(&self,
+                               name: &str)
+                               -> Result<usize, String> {
+        self.headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| format!("column '{}' is not in the dataset", name))
+    }
+
     pub(crate) fn validate(&self) -> Result<(), String> {
         let width = self.headers.len();
+        if !self.column_types.is_empty() && self.column_types.len() != width {
+            return Err(format!("ParsedDataTable validation error: column_types length {} != headers {}",
+                               self.column_types.len(),
+                               width));
+        }
         for (row_index, row) in self.rows.iter().enumerate() {
-            if row.len() != width {
-                return Err(format!("ParsedDataTable validation error: row {} length {} != headers {}",
-                                   row_index,
-                                   row.len(),
+            validate_row(row, row_index, width)?;
+        }
+        Ok(())
+    }
+
+    /// Infers the column types again after the cells changed. A table without types stays
+    /// without, so result tables are not typed by accident.
+    pub(crate) fn refresh_column_types(&mut self) {
+        if !self.column_types.is_empty() {
+            self.column_types = infer_column_types(self.headers.len(), &self.rows);
+        }
+    }
+
+    /// Brings every row to the header count, so that a slightly ragged table passes `validate`.
+    /// With `Pad`, a row holding a non-null cell beyond the headers is an error and the table is
+    /// left unchanged, since repairing it would lose data.
+    pub(crate) fn normalize_widths(&mut self,
+                                   strategy: WidthStrategy)
+                                   -> Result<WidthAdjustment, String> {
+        let width = self.headers.len();
+        if strategy == WidthStrategy::Pad {
+            let overflowing: Vec<String> =
+                self.rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| row.iter().skip(width).any(|cell| !cell.is_null()))
+                    .map(|(row_index, _)| row_index.to_string())
+                    .collect();
+            if !overflowing.is_empty() {
+                return Err(format!("rows [{}] have values beyond the {} headers; use truncate to drop them",
+                                   overflowing.join(", "),
                                    width));
             }
-            for (col_index, cell) in row.iter().enumerate() {
-                match cell {
-                    Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {},
-                    _ => {
-                        return Err(format!("ParsedDataTable validation error: rows[{}][{}] has unsupported type",
-                                           row_index, col_index));
-                    },
-                }
+        }
+
+        let mut adjustment = WidthAdjustment::default();
+        for row in &mut self.rows {
+            if row.len() < width {
+                row.resize(width, Value::Null);
+                adjustment.padded += 1;
+            } else if row.len() > width {
+                row.truncate(width);
+                adjustment.truncated += 1;
             }
         }
+        Ok(adjustment)
+    }
+
+    /// Renders the table as a GitHub-flavored Markdown table.
+    /// Columns whose non-null cells are all numbers are right-aligned; null cells are left empty.
+    pub(crate) fn to_markdown(&self) -> String {
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+        lines.push(markdown_row(self.headers.iter().map(|header| escape_markdown_cell(header))));
+
+        let separators = (0..self.headers.len()).map(|col_index| {
+                                                    if self.is_numeric_column(col_index) {
+                                                        "---:".to_string()
+                                                    } else {
+                                                        "---".to_string()
+                                                    }
+                                                });
+        lines.push(markdown_row(separators));
+
+        for row in &self.rows {
+            lines.push(markdown_row(row.iter().map(markdown_cell)));
+        }
+
+        let mut markdown = lines.join("\n");
+        markdown.push('\n');
+        markdown
+    }
+
+    /// Renders the table like `to_markdown`, preceded by a `---`-delimited YAML front-matter block
+    /// so that static-site generators can read the analysis type, timestamp and parameters.
+    pub(crate) fn to_markdown_with_frontmatter(&self,
+                                               meta: &MarkdownFrontMatter)
+                                               -> String {
+        let mut lines = vec!["---".to_string(),
+                             format!("analysis_type: {}",
+                                     yaml_value(&Value::from(meta.analysis_type.as_str()))),
+                             format!("timestamp: {}", yaml_value(&Value::from(meta.timestamp.as_str())))];
+        if let Some(title) = &self.title {
+            lines.push(format!("title: {}", yaml_value(&Value::from(title.as_str()))));
+        }
+        if meta.parameters.is_empty() {
+            lines.push("parameters: {}".to_string());
+        } else {
+            lines.push("parameters:".to_string());
+            for (key, value) in &meta.parameters {
+                lines.push(format!("  {}: {}",
+                                   yaml_value(&Value::from(key.as_str())),
+                                   yaml_value(value)));
+            }
+        }
+        lines.push("---".to_string());
+
+        let mut markdown = lines.join("\n");
+        markdown.push_str("\n\n");
+        markdown.push_str(&self.to_markdown());
+        markdown
+    }
+
+    fn is_numeric_column(&self,
+                         col_index: usize)
+                         -> bool {
+        let mut has_number = false;
+        for row in &self.rows {
+            match row.get(col_index) {
+                Some(Value::Number(_)) => has_number = true,
+                Some(Value::Null) | None => {},
+                Some(_) => return false,
+            }
+        }
+        has_number
+    }
+}
+
+/// Writes a value in JSON syntax, which YAML 1.2 reads as the same scalar or flow collection.
+/// Strings are therefore always double-quoted with `"`, `\\` and control characters escaped,
+/// so values such as `a: b`, `#comment` or `yes` cannot change the meaning of the front matter.
+fn yaml_value(value: &Value) -> String {
+    serde_json::to_string(value).expect("JSON values always serialize")
+}
+
+fn markdown_row(cells: impl Iterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// CSV output of a table whose rows arrive in chunks: a header row, then one line per row with
+/// null cells empty. The whole text is never held in memory; `csv::Writer` buffers the output
+/// itself. Quoting follows the `csv` crate (RFC 4180), as in `numeric_dataset_to_csv`.
+pub(crate) struct CsvTableWriter<W: std::io::Write> {
+    writer: csv::Writer<W>,
+    width: usize,
+    rows_written: usize,
+}
+
+impl<W: std::io::Write> CsvTableWriter<W> {
+    pub(crate) fn new(writer: W,
+                      headers: &[String])
+                      -> Result<Self, String> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(headers)
+              .map_err(|e| format!("failed to write CSV header: {}", e))?;
+        Ok(Self { writer,
+                  width: headers.len(),
+                  rows_written: 0 })
+    }
+
+    /// Appends `rows` after the rows already written. Each row is checked like
+    /// `ParsedDataTable::validate` before anything of it is written.
+    pub(crate) fn write_rows(&mut self,
+                             rows: &[Vec<Value>])
+                             -> Result<(), String> {
+        for row in rows {
+            validate_row(row, self.rows_written, self.width)?;
+            self.writer.write_record(row.iter().map(csv_cell)).map_err(|e| {
+                                                                   format!("failed to write CSV row {}: {}",
+                                                                           self.rows_written + 1,
+                                                                           e)
+                                                               })?;
+            self.rows_written += 1;
+        }
         Ok(())
     }
+
+    /// Flushes the output and returns the number of rows written, header excluded.
+    pub(crate) fn finish(mut self) -> Result<usize, String> {
+        self.writer
+            .flush()
+            .map_err(|e| format!("failed to finish CSV: {}", e))?;
+        Ok(self.rows_written)
+    }
+}
+
+fn validate_row(row: &[Value],
+                row_index: usize,
+                width: usize)
+                -> Result<(), String> {
+    if row.len() != width {
+        return Err(format!("ParsedDataTable validation error: row {} length {} != headers {}",
+                           row_index,
+                           row.len(),
+                           width));
+    }
+    for (col_index, cell) in row.iter().enumerate() {
+        match cell {
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {},
+            _ => {
+                return Err(format!("ParsedDataTable validation error: rows[{}][{}] has unsupported type",
+                                   row_index, col_index));
+            },
+        }
+    }
+    Ok(())
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn markdown_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => escape_markdown_cell(text),
+        other => other.to_string(),
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell: pipes and line breaks.
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
 }
 
 #[derive(Clone, Debug)]
@@ -45,6 +429,43 @@ pub(crate) struct NormalizedRows {
     pub note: Option<String>,
 }
 
+/// Converts an optional statistic into a table cell; undefined or non-finite values become null.
+pub(crate) fn number_cell(value: Option<f64>) -> Value {
+    value.and_then(serde_json::Number::from_f64)
+         .map(Value::Number)
+         .unwrap_or(Value::Null)
+}
+
+/// Joins optional notes into a single note, keeping their order.
+pub(crate) fn join_notes(notes: impl IntoIterator<Item = Option<String>>) -> Option<String> {
+    let notes: Vec<String> = notes.into_iter().flatten().collect();
+    if notes.is_empty() {
+        None
+    } else {
+        Some(notes.join(" / "))
+    }
+}
+
+/// Reads a column as numbers; null and blank cells are missing, anything non-numeric is an error.
+pub(crate) fn column_values(table: &ParsedDataTable,
+                            col_index: usize,
+                            column: &str)
+                            -> Result<Vec<Option<f64>>, String> {
+    table.rows
+         .iter()
+         .enumerate()
+         .map(|(row_index, row)| match row.get(col_index) {
+             None | Some(Value::Null) => Ok(None),
+             Some(Value::Number(number)) => Ok(number.as_f64()),
+             Some(Value::String(text)) => {
+                 parse_numeric_string(text, NumericCellContext::new(row_index, col_index, column))
+             },
+             Some(other) => Err(format!("column '{}' has a non-numeric value {} at rows[{}]",
+                                        column, other, row_index)),
+         })
+         .collect()
+}
+
 pub(crate) fn collect_ordered_selected_columns(headers: &[String],
                                                variables: &[String])
                                                -> Result<Vec<(String, usize)>, String> {
@@ -102,7 +523,8 @@ pub(crate) fn normalize_rows(rows: Vec<Vec<Value>>,
                      note }
 }
 
-pub(crate) fn validate_unique_headers(headers: &[String]) -> Result<(), String> {
+/// Header names that occur more than once, each listed once in first-duplicate order.
+pub(crate) fn duplicate_headers(headers: &[String]) -> Vec<String> {
     let mut seen: HashSet<&str> = HashSet::new();
     let mut duplicate_seen: HashSet<&str> = HashSet::new();
     let mut duplicates: Vec<String> = Vec::new();
@@ -113,10 +535,211 @@ pub(crate) fn validate_unique_headers(headers: &[String]) -> Result<(), String>
             duplicates.push(header.clone());
         }
     }
+    duplicates
+}
 
+pub(crate) fn validate_unique_headers(headers: &[String]) -> Result<(), String> {
+    let duplicates = duplicate_headers(headers);
     if !duplicates.is_empty() {
         return Err(format!("Sheet headers are duplicated: {}", duplicates.join(", ")));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        ColumnType,
+        CsvTableWriter,
+        MarkdownFrontMatter,
+        ParsedDataTable,
+        WidthAdjustment,
+        WidthStrategy,
+        infer_column_types,
+    };
+
+    fn table(headers: &[&str],
+             rows: Vec<Vec<Value>>)
+             -> ParsedDataTable {
+        ParsedDataTable { headers: headers.iter().map(|v| v.to_string()).collect(),
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn pad_fills_short_rows_and_drops_only_null_overflow() {
+        let mut ragged = table(&["a", "b", "c"],
+                               vec![vec![Value::from(1)],
+                                    vec![Value::from(1), Value::from(2), Value::from(3)],
+                                    vec![Value::from(1), Value::from(2), Value::from(3), Value::Null]]);
+        assert!(ragged.validate().is_err());
+
+        let adjustment = ragged.normalize_widths(WidthStrategy::Pad).unwrap();
+
+        assert_eq!(adjustment,
+                   WidthAdjustment { padded: 1,
+                                     truncated: 1 });
+        assert!(adjustment.note().unwrap().contains("合わない 2 行"));
+        assert_eq!(ragged.rows[0], vec![Value::from(1), Value::Null, Value::Null]);
+        ragged.validate().unwrap();
+    }
+
+    #[test]
+    fn truncate_drops_extra_cells_that_pad_refuses_to_lose() {
+        let mut ragged = table(&["a", "b"],
+                               vec![vec![Value::from("x"), Value::from(1), Value::from("extra")],
+                                    vec![Value::from("y"), Value::from(2)]]);
+
+        let err = ragged.clone().normalize_widths(WidthStrategy::Pad).unwrap_err();
+        assert!(err.contains("rows [0]"), "{}", err);
+
+        let adjustment = ragged.normalize_widths(WidthStrategy::Truncate).unwrap();
+        assert_eq!(adjustment,
+                   WidthAdjustment { padded: 0,
+                                     truncated: 1 });
+        assert_eq!(ragged.rows[0], vec![Value::from("x"), Value::from(1)]);
+        ragged.validate().unwrap();
+    }
+
+    #[test]
+    fn markdown_escapes_pipes_and_renders_null_as_empty() {
+        let table = table(&["name|label", "value"],
+                          vec![vec!["a|b".into(), 1.into()], vec![Value::Null, Value::Null]]);
+
+        let markdown = table.to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines[0], "| name\\|label | value |");
+        assert_eq!(lines[2], "| a\\|b | 1 |");
+        assert_eq!(lines[3], "|  |  |");
+    }
+
+    #[test]
+    fn markdown_right_aligns_numeric_columns_only() {
+        let table = table(&["name", "mean", "mixed"],
+                          vec![vec!["x".into(), 1.5.into(), 2.into()],
+                               vec!["y".into(), Value::Null, "n/a".into()]]);
+
+        let markdown = table.to_markdown();
+
+        assert_eq!(markdown.lines().nth(1), Some("| --- | ---: | --- |"));
+    }
+
+    #[test]
+    fn streamed_csv_is_the_expected_rfc_4180_text() {
+        let rows = vec![vec![Value::from("a, \"b\"\nc"),
+                             Value::from(0.0),
+                             Value::Null,
+                             Value::Bool(true)],
+                        vec![Value::from("plain"),
+                             Value::from(2.5),
+                             Value::from(7),
+                             Value::Bool(false)]];
+        let table = table(&["label", "ratio", "count", "flag"], rows);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("table.csv");
+
+        let mut writer = CsvTableWriter::new(std::fs::File::create(&path).unwrap(), &table.headers).unwrap();
+        writer.write_rows(&table.rows).unwrap();
+        writer.finish().unwrap();
+
+        let expected = "label,ratio,count,flag\n\"a, \"\"b\"\"\nc\",0.0,,true\nplain,2.5,7,false\n";
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn csv_written_in_chunks_matches_one_write_of_all_rows() {
+        let rows: Vec<Vec<Value>> = (0..250).map(|i| {
+                                                vec![Value::from(format!("r{}, \"{}\"", i, i)),
+                                                     Value::from(i as f64 / 4.0),
+                                                     if i % 7 == 0 { Value::Null } else { Value::from(i) }]
+                                            })
+                                            .collect();
+        let headers = ["label".to_string(), "x".to_string(), "n".to_string()];
+        let mut whole = Vec::new();
+        let mut writer = CsvTableWriter::new(&mut whole, &headers).unwrap();
+        writer.write_rows(&rows).unwrap();
+        writer.finish().unwrap();
+
+        let mut chunked = Vec::new();
+        let mut writer = CsvTableWriter::new(&mut chunked, &headers).unwrap();
+        for chunk in rows.chunks(64) {
+            writer.write_rows(chunk).unwrap();
+        }
+
+        assert_eq!(writer.finish().unwrap(), 250);
+        assert_eq!(chunked, whole);
+    }
+
+    #[test]
+    fn front_matter_is_valid_yaml_followed_by_the_table() {
+        let mut table = table(&["n"], vec![vec![3.into()]]);
+        table.title = Some("記述統計".to_string());
+        let mut parameters = serde_json::Map::new();
+        parameters.insert("label".to_string(), "a: \"b\" # c\nnext".into());
+        parameters.insert("digits".to_string(), 3.into());
+        parameters.insert("variables".to_string(), vec!["x", "y"].into());
+        let meta = MarkdownFrontMatter { analysis_type: "descriptive".to_string(),
+                                         timestamp: "2026-01-02T03:04:05+09:00".to_string(),
+                                         parameters };
+
+        let markdown = table.to_markdown_with_frontmatter(&meta);
+        let (front, body) = markdown.strip_prefix("---\n")
+                                    .and_then(|rest| rest.split_once("\n---\n\n"))
+                                    .expect("front matter is delimited by ---");
+        let docs = yaml_rust2::YamlLoader::load_from_str(front).unwrap();
+        let yaml = &docs[0];
+
+        assert_eq!(yaml["analysis_type"].as_str(), Some("descriptive"));
+        assert_eq!(yaml["timestamp"].as_str(), Some("2026-01-02T03:04:05+09:00"));
+        assert_eq!(yaml["title"].as_str(), Some("記述統計"));
+        assert_eq!(yaml["parameters"]["label"].as_str(), Some("a: \"b\" # c\nnext"));
+        assert_eq!(yaml["parameters"]["digits"].as_i64(), Some(3));
+        assert_eq!(yaml["parameters"]["variables"][1].as_str(), Some("y"));
+        assert_eq!(body, table.to_markdown());
+    }
+
+    #[test]
+    fn column_types_follow_every_cell_of_the_column() {
+        let rows = vec![vec![Value::from(1),
+                             Value::from(1),
+                             Value::from("2024-01-15"),
+                             Value::from(true),
+                             Value::from("a")],
+                        vec![Value::from("2"),
+                             Value::from(2.5),
+                             Value::from("2024/02/29 09:30"),
+                             Value::from(" FALSE "),
+                             Value::from(3)],
+                        vec![Value::Null,
+                             Value::from(""),
+                             Value::Null,
+                             Value::Null,
+                             Value::Null]];
+
+        assert_eq!(infer_column_types(6, &rows),
+                   vec![ColumnType::Integer,
+                        ColumnType::Numeric,
+                        ColumnType::Date,
+                        ColumnType::Boolean,
+                        ColumnType::Mixed,
+                        ColumnType::Text]);
+    }
+
+    #[test]
+    fn column_types_are_optional_in_payloads_but_must_match_the_headers() {
+        let payload = r#"{"headers":["a"],"rows":[[1]]}"#;
+        let mut parsed: ParsedDataTable = serde_json::from_str(payload).unwrap();
+        assert!(parsed.column_types.is_empty());
+        assert!(parsed.validate().is_ok());
+        assert!(!serde_json::to_string(&parsed).unwrap().contains("column_types"));
+
+        parsed.column_types = vec![ColumnType::Integer, ColumnType::Text];
+        assert!(parsed.validate().is_err());
+    }
+}
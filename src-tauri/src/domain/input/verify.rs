@@ -0,0 +1,24 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Relative tolerance used when comparing a parsed number with the source value.
+pub(crate) const NUMBER_TOLERANCE: f64 = 1e-9;
+
+/// A parsed cell that no longer agrees with the value stored in the source file.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ParseMismatch {
+    /// 1-based row in the source: the sheet row for a spreadsheet, the record number for
+    /// delimited text (the header record is row 1).
+    pub row: usize,
+    pub column: String,
+    pub parsed: Value,
+    /// The source value as text.
+    pub source: String,
+}
+
+pub(crate) fn numbers_match(parsed: f64,
+                            source: f64)
+                            -> bool {
+    (parsed - source).abs() <= NUMBER_TOLERANCE * source.abs().max(1.0)
+}
@@ -0,0 +1,210 @@
+use rust_xlsxwriter::{
+    Workbook,
+    Worksheet,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::table::ParsedDataTable;
+
+/// Longest sheet name Excel accepts.
+const SHEET_NAME_MAX_CHARS: usize = 31;
+
+/// Characters Excel does not allow in a sheet name.
+const SHEET_NAME_INVALID_CHARS: [char; 7] = [':', '\\', '/', '?', '*', '[', ']'];
+
+/// An analysis result to be written to its own sheet, named after `name`.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct NamedTable {
+    pub name: String,
+    pub table: ParsedDataTable,
+}
+
+/// Writes `table` to `worksheet`: the headers in the first row, then one row per table row.
+/// Null cells are left empty. A note is written one blank row below the data.
+pub(crate) fn write_table_sheet(worksheet: &mut Worksheet,
+                                table: &ParsedDataTable)
+                                -> Result<(), String> {
+    table.validate()?;
+    for (col_index, header) in table.headers.iter().enumerate() {
+        worksheet.write_string(0, sheet_col(col_index)?, header)
+                 .map_err(|e| format!("failed to write header '{}': {}", header, e))?;
+    }
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let sheet_row = sheet_row(row_index + 1)?;
+        for (col_index, cell) in row.iter().enumerate() {
+            let sheet_col = sheet_col(col_index)?;
+            let written = match cell {
+                Value::Null => continue,
+                Value::Bool(value) => worksheet.write_boolean(sheet_row, sheet_col, *value),
+                Value::Number(value) => match value.as_f64() {
+                    Some(value) => worksheet.write_number(sheet_row, sheet_col, value),
+                    None => worksheet.write_string(sheet_row, sheet_col, value.to_string()),
+                },
+                Value::String(text) => worksheet.write_string(sheet_row, sheet_col, text),
+                _ => unreachable!("validated cells are scalars"),
+            };
+            written.map_err(|e| format!("failed to write rows[{}][{}]: {}", row_index, col_index, e))?;
+        }
+    }
+    if let Some(note) = &table.note {
+        worksheet.write_string(sheet_row(table.rows.len() + 2)?, 0, note)
+                 .map_err(|e| format!("failed to write note: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Builds an xlsx workbook with one sheet per result, in order, and returns its bytes.
+/// Sheet names come from `sheet_names`.
+pub(crate) fn results_workbook(results: &[NamedTable]) -> Result<Vec<u8>, String> {
+    if results.is_empty() {
+        return Err("at least one result is required".to_string());
+    }
+    let names = sheet_names(results.iter().map(|result| result.name.as_str()));
+
+    let mut workbook = Workbook::new();
+    for (result, name) in results.iter().zip(&names) {
+        let worksheet = workbook.add_worksheet()
+                                .set_name(name)
+                                .map_err(|e| format!("invalid sheet name '{}': {}", name, e))?;
+        write_table_sheet(worksheet, &result.table).map_err(|e| format!("sheet '{}': {}", name, e))?;
+    }
+    workbook.save_to_buffer()
+            .map_err(|e| format!("failed to build workbook: {}", e))
+}
+
+/// Turns result names into valid, distinct sheet names, deterministically from the input order.
+///
+/// Characters Excel rejects (`: \ / ? * [ ]`) become `_`, leading and trailing apostrophes are
+/// dropped and names are cut to 31 characters. A name left blank becomes `結果{n}` (its 1-based
+/// position). Names that collide with an earlier one, ignoring case as Excel does, get a ` (2)`,
+/// ` (3)`, ... suffix, shortening the name when needed to stay within 31 characters.
+pub(crate) fn sheet_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut used: Vec<String> = Vec::new();
+    let mut resolved = Vec::new();
+    for (index, name) in names.into_iter().enumerate() {
+        let cleaned: String = name.chars()
+                                  .map(|c| {
+                                      if SHEET_NAME_INVALID_CHARS.contains(&c) {
+                                          '_'
+                                      } else {
+                                          c
+                                      }
+                                  })
+                                  .collect();
+        let mut base =
+            trim_sheet_name(&truncate_chars(trim_sheet_name(&cleaned), SHEET_NAME_MAX_CHARS)).to_string();
+        if base.is_empty() {
+            base = format!("結果{}", index + 1);
+        }
+        // "History" is reserved by Excel.
+        if base.eq_ignore_ascii_case("history") {
+            base.push('_');
+        }
+
+        let mut candidate = base.clone();
+        let mut suffix = 2;
+        while used.contains(&candidate.to_lowercase()) {
+            let tail = format!(" ({})", suffix);
+            let head = truncate_chars(&base, SHEET_NAME_MAX_CHARS - tail.chars().count());
+            candidate = format!("{}{}", trim_sheet_name(&head), tail);
+            suffix += 1;
+        }
+        used.push(candidate.to_lowercase());
+        resolved.push(candidate);
+    }
+    resolved
+}
+
+/// Drops surrounding whitespace and apostrophes, which Excel rejects at either end of a name.
+fn trim_sheet_name(name: &str) -> &str {
+    name.trim_matches(|c: char| c.is_whitespace() || c == '\'')
+}
+
+fn truncate_chars(text: &str,
+                  max_chars: usize)
+                  -> String {
+    text.chars().take(max_chars).collect()
+}
+
+fn sheet_row(index: usize) -> Result<u32, String> {
+    u32::try_from(index).map_err(|_| format!("row {} is out of range for a worksheet", index))
+}
+
+fn sheet_col(index: usize) -> Result<u16, String> {
+    u16::try_from(index).map_err(|_| format!("column {} is out of range for a worksheet", index))
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{
+        Data,
+        Reader,
+        Xlsx,
+    };
+    use serde_json::Value;
+
+    use super::{
+        NamedTable,
+        results_workbook,
+        sheet_names,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn named(name: &str,
+             rows: Vec<Vec<Value>>)
+             -> NamedTable {
+        NamedTable { name: name.to_string(),
+                     table: ParsedDataTable { headers: vec!["変数".to_string(), "値".to_string()],
+                                              rows,
+                                              note: None,
+                                              title: None,
+                                              column_types: Vec::new() } }
+    }
+
+    #[test]
+    fn three_results_are_read_back_as_three_sheets() {
+        let mut with_note = named("t検定", vec![vec![Value::from("t"), Value::from(2.5)]]);
+        with_note.table.note = Some("注記".to_string());
+        let results = [named("記述統計", vec![vec![Value::from("x"), Value::from(1.5)]]),
+                       named("記述統計", vec![vec![Value::from("y"), Value::Null]]),
+                       with_note];
+
+        let bytes = results_workbook(&results).unwrap();
+        let mut workbook = Xlsx::new(std::io::Cursor::new(bytes)).unwrap();
+
+        assert_eq!(workbook.sheet_names(),
+                   vec!["記述統計".to_string(),
+                        "記述統計 (2)".to_string(),
+                        "t検定".to_string()]);
+        let first = workbook.worksheet_range("記述統計").unwrap();
+        assert_eq!(first.get_value((0, 1)), Some(&Data::String("値".to_string())));
+        assert_eq!(first.get_value((1, 1)), Some(&Data::Float(1.5)));
+        let second = workbook.worksheet_range("記述統計 (2)").unwrap();
+        assert_eq!(second.get_value((1, 0)), Some(&Data::String("y".to_string())));
+        assert!(matches!(second.get_value((1, 1)), None | Some(Data::Empty)));
+        let third = workbook.worksheet_range("t検定").unwrap();
+        assert_eq!(third.get_value((3, 0)), Some(&Data::String("注記".to_string())));
+    }
+
+    #[test]
+    fn invalid_and_duplicate_names_are_resolved_deterministically() {
+        let long = "a".repeat(40);
+        let names = sheet_names(["a/b:c",
+                                 "A_B_C",
+                                 "  ",
+                                 "'quoted'",
+                                 long.as_str(),
+                                 long.as_str(),
+                                 "History"]);
+
+        assert_eq!(names,
+                   vec!["a_b_c".to_string(),
+                        "A_B_C (2)".to_string(),
+                        "結果3".to_string(),
+                        "quoted".to_string(),
+                        "a".repeat(31),
+                        format!("{} (2)", "a".repeat(27)),
+                        "History_".to_string()]);
+    }
+}
@@ -0,0 +1,19 @@
+pub(crate) mod acf;
+pub(crate) mod anomaly;
+pub(crate) mod association;
+pub(crate) mod balance;
+pub(crate) mod boxplot;
+pub(crate) mod collinearity;
+pub(crate) mod cumulative;
+pub(crate) mod diversity;
+pub(crate) mod ecdf;
+pub(crate) mod group_zscore;
+pub(crate) mod histogram;
+pub(crate) mod interaction;
+pub(crate) mod normality;
+pub(crate) mod quality;
+pub(crate) mod rolling;
+pub(crate) mod sample;
+pub(crate) mod simpson;
+pub(crate) mod spearman;
+pub(crate) mod trend;
@@ -0,0 +1,158 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::{
+    is_constant,
+    mean,
+};
+
+/// Two-sided 95% normal quantile used for the white-noise bounds, as in R's `plot(acf(x))`.
+const WHITE_NOISE_Z: f64 = 1.96;
+
+/// Sample autocorrelation of `column` at lags `0..=max_lag`, computed like R's `acf()`:
+/// `r_k = Σ (x_t - x̄)(x_{t+k} - x̄) / Σ (x_t - x̄)²`, so `r_0` is 1.
+///
+/// Rows are taken in order as equally spaced time points. Missing values before the first and
+/// after the last observation are dropped; a gap between observations is an error unless
+/// `interpolate` is set, in which case it is filled linearly. With `confidence`, every lag also
+/// gets the approximate 95% bounds `±1.96 / √n` under the white-noise hypothesis.
+pub(crate) fn acf(dataset: &NumericDataset,
+                  column: &str,
+                  max_lag: usize,
+                  confidence: bool,
+                  interpolate: bool)
+                  -> Result<ParsedDataTable, String> {
+    let values = dataset.get(column)
+                        .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+    let (series, interpolated) = prepare_series(values, interpolate)?;
+    let n = series.len();
+    if max_lag >= n {
+        return Err(format!("max_lag must be smaller than the series length ({})", n));
+    }
+    if is_constant(&series) {
+        return Err(format!("column '{}' is constant, so the autocorrelation is undefined",
+                           column));
+    }
+
+    let m = mean(&series).expect("series is not empty");
+    let deviations: Vec<f64> = series.iter().map(|x| x - m).collect();
+    let denominator: f64 = deviations.iter().map(|d| d * d).sum();
+    let bound = WHITE_NOISE_Z / (n as f64).sqrt();
+
+    let rows = (0..=max_lag).map(|lag| {
+                                let numerator: f64 = deviations.iter()
+                                                               .zip(&deviations[lag..])
+                                                               .map(|(a, b)| a * b)
+                                                               .sum();
+                                let mut row =
+                                    vec![Value::from(lag), number_cell(Some(numerator / denominator))];
+                                if confidence {
+                                    row.extend([number_cell(Some(-bound)), number_cell(Some(bound))]);
+                                }
+                                row
+                            })
+                            .collect();
+
+    let mut headers = vec!["ラグ".to_string(), "自己相関".to_string()];
+    let mut notes = vec![format!("系列の長さ n = {}", n)];
+    if confidence {
+        headers.extend(["95%下限".to_string(), "95%上限".to_string()]);
+        notes.push("信頼限界は白色雑音を仮定した ±1.96/√n です".to_string());
+    }
+    if interpolated > 0 {
+        notes.push(format!("途中の欠損値 {} 件を線形補間しました", interpolated));
+    }
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: Some(notes.join(" / ")),
+                         title: Some(format!("自己相関 ({})", column)),
+                         column_types: Vec::new() })
+}
+
+/// Drops missing values at both ends and fills interior gaps linearly when `interpolate` is set.
+/// Returns the series and the number of filled values.
+fn prepare_series(values: &[Option<f64>],
+                  interpolate: bool)
+                  -> Result<(Vec<f64>, usize), String> {
+    let first = values.iter()
+                      .position(Option::is_some)
+                      .ok_or_else(|| "the series has no observed values".to_string())?;
+    let last = values.iter()
+                     .rposition(Option::is_some)
+                     .expect("an observed value exists");
+    let window = &values[first..=last];
+
+    let gaps = window.iter().filter(|value| value.is_none()).count();
+    if gaps > 0 && !interpolate {
+        return Err(format!("the series has {} missing values between observations; enable interpolation to fill them",
+                           gaps));
+    }
+
+    let mut series = Vec::with_capacity(window.len());
+    let mut previous = 0;
+    for (i, value) in window.iter().enumerate() {
+        if let Some(value) = value {
+            if i > previous + 1 {
+                let start = series[previous];
+                let step = (value - start) / (i - previous) as f64;
+                series.extend((1..i - previous).map(|k| start + step * k as f64));
+            }
+            series.push(*value);
+            previous = i;
+        }
+    }
+    Ok((series, gaps))
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::acf;
+
+    #[test]
+    fn autoregressive_series_decays_from_one() {
+        // x_t = 0.8 x_{t-1} + e_t with uniform disturbances from a fixed-seed LCG.
+        let mut state: u64 = 42;
+        let mut x = 0.0;
+        let series: Vec<Option<f64>> = (0..200).map(|_| {
+                                                   state = state.wrapping_mul(6364136223846793005)
+                                                                .wrapping_add(1442695040888963407);
+                                                   let e = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+                                                   x = 0.8 * x + e;
+                                                   Some(x)
+                                               })
+                                               .collect();
+        let mut dataset = IndexMap::new();
+        dataset.insert("y".to_string(), series);
+
+        let table = acf(&dataset, "y", 5, true, false).unwrap();
+        let r: Vec<f64> = table.rows.iter().map(|row| row[1].as_f64().unwrap()).collect();
+
+        assert_eq!(table.rows[0][1], Value::from(1.0));
+        assert!(r[1] > 0.5, "{:?}", r);
+        assert!(r.windows(2).all(|pair| pair[1] < pair[0]), "{:?}", r);
+        assert_eq!(table.headers.len(), 4);
+        assert!((table.rows[3][3].as_f64().unwrap() - 1.96 / 200f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn interior_gap_needs_interpolation() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("y".to_string(),
+                       vec![None, Some(1.0), None, Some(3.0), Some(2.0), Some(5.0), None]);
+
+        let err = acf(&dataset, "y", 2, false, false).unwrap_err();
+        assert!(err.contains("1 missing values"));
+
+        let table = acf(&dataset, "y", 2, false, true).unwrap();
+        assert_eq!(table.headers.len(), 2);
+        assert!(table.note.unwrap().contains("n = 5"));
+    }
+}
@@ -0,0 +1,122 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::{
+    observed_values,
+    sorted_quantile,
+};
+
+/// Cutoff recommended by Iglewicz and Hoaglin for the modified z-score.
+pub(crate) const DEFAULT_ANOMALY_THRESHOLD: f64 = 3.5;
+
+/// Scales the MAD to the standard deviation of a normal distribution (0.6745 = Φ⁻¹(0.75)).
+const MAD_SCALE: f64 = 0.6745;
+
+/// Flags the rows of `column` whose robust (modified) z-score `0.6745 * (x - median) / MAD`
+/// exceeds `threshold` in absolute value. The median and MAD are not pulled by the anomalies
+/// themselves, so one spike cannot hide another as with the mean and SD.
+/// Missing cells have no score and are not flagged. When the MAD is zero (more than half of the
+/// values are identical) no score is defined and nothing is flagged.
+pub(crate) fn anomaly_detect(dataset: &NumericDataset,
+                             column: &str,
+                             threshold: f64)
+                             -> Result<ParsedDataTable, String> {
+    if !threshold.is_finite() || threshold <= 0.0 {
+        return Err("threshold must be a positive number".to_string());
+    }
+    let values = dataset.get(column)
+                        .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+
+    let mut sorted = observed_values(values);
+    if sorted.is_empty() {
+        return Err(format!("column '{}' has no observed values", column));
+    }
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted_quantile(&sorted, 0.5);
+    let mut deviations: Vec<f64> = sorted.iter().map(|value| (value - median).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    let mad = sorted_quantile(&deviations, 0.5);
+
+    let mut flagged = 0usize;
+    let rows = values.iter()
+                     .enumerate()
+                     .map(|(i, value)| {
+                         let score = value.filter(|_| mad > 0.0)
+                                          .map(|value| MAD_SCALE * (value - median) / mad);
+                         let anomaly = score.is_some_and(|score| score.abs() > threshold);
+                         if anomaly {
+                             flagged += 1;
+                         }
+                         vec![Value::from(i + 1),
+                              number_cell(*value),
+                              number_cell(score),
+                              Value::Bool(anomaly)]
+                     })
+                     .collect();
+
+    let summary = if mad > 0.0 {
+        format!("ロバスト z = 0.6745 × (値 - 中央値) / MAD、|z| > {} を異常値としました (中央値 = {}, MAD = {}, 異常値 {} 件)",
+                threshold, median, mad, flagged)
+    } else {
+        format!("値の半数以上が {} で MAD が 0 のため、ロバスト z スコアを計算できず異常値は判定していません",
+                median)
+    };
+    Ok(ParsedDataTable { headers: vec!["行".to_string(),
+                                       column.to_string(),
+                                       "ロバスト z".to_string(),
+                                       "異常値".to_string()],
+                         rows,
+                         note: Some(summary),
+                         title: Some(format!("異常値検出 ({})", column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        DEFAULT_ANOMALY_THRESHOLD,
+        anomaly_detect,
+    };
+
+    fn flags(values: Vec<Option<f64>>) -> Vec<Value> {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), values);
+        anomaly_detect(&dataset, "x", DEFAULT_ANOMALY_THRESHOLD).unwrap()
+                                                                .rows
+                                                                .into_iter()
+                                                                .map(|row| row[3].clone())
+                                                                .collect()
+    }
+
+    #[test]
+    fn spike_is_flagged_and_normal_points_are_not() {
+        let series = vec![Some(10.0),
+                          Some(11.0),
+                          Some(9.0),
+                          Some(10.5),
+                          Some(95.0),
+                          None,
+                          Some(9.5),
+                          Some(10.0)];
+
+        let flagged: Vec<bool> = flags(series).iter().map(|flag| flag.as_bool().unwrap()).collect();
+
+        assert_eq!(flagged,
+                   vec![false, false, false, false, true, false, false, false]);
+    }
+
+    #[test]
+    fn zero_mad_flags_nothing() {
+        let flagged = flags(vec![Some(5.0), Some(5.0), Some(5.0), Some(100.0)]);
+
+        assert!(flagged.iter().all(|flag| flag == &Value::Bool(false)));
+    }
+}
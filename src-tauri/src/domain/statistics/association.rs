@@ -0,0 +1,644 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::string_mixed::StringMixedDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+/// Pearson chi-square statistic and Cramér's V of an r × c contingency table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Association {
+    pub n: usize,
+    pub rows: usize,
+    pub cols: usize,
+    pub chi_square: f64,
+    pub cramers_v: f64,
+    /// Bergsma (2013) bias-corrected V; `None` when the corrected table dimensions leave no spread.
+    pub cramers_v_corrected: Option<f64>,
+}
+
+/// Computes chi-square and Cramér's V from a table of counts (rows × columns).
+/// Every row and column must have a positive total, and both dimensions must be at least 2.
+pub(crate) fn association(counts: &[Vec<usize>]) -> Result<Association, String> {
+    let rows = counts.len();
+    let cols = counts.first().map_or(0, Vec::len);
+    if rows < 2 || cols < 2 {
+        return Err(format!("a contingency table needs at least 2 × 2 levels (got {} × {})",
+                           rows, cols));
+    }
+    let row_totals: Vec<usize> = counts.iter().map(|row| row.iter().sum()).collect();
+    let col_totals: Vec<usize> = (0..cols).map(|j| counts.iter().map(|row| row[j]).sum()).collect();
+    if row_totals.contains(&0) || col_totals.contains(&0) {
+        return Err("every row and column of the contingency table needs at least one count".to_string());
+    }
+    let n: usize = row_totals.iter().sum();
+
+    let chi_square: f64 =
+        counts.iter()
+              .zip(&row_totals)
+              .flat_map(|(row, &row_total)| {
+                  row.iter().zip(&col_totals).map(move |(&observed, &col_total)| {
+                                                 let expected = (row_total * col_total) as f64 / n as f64;
+                                                 (observed as f64 - expected).powi(2) / expected
+                                             })
+              })
+              .sum();
+
+    let n_f = n as f64;
+    let (r, k) = (rows as f64, cols as f64);
+    let phi_square = chi_square / n_f;
+    let cramers_v = (phi_square / (r.min(k) - 1.0)).sqrt().clamp(0.0, 1.0);
+
+    let phi_square_corrected = (phi_square - (k - 1.0) * (r - 1.0) / (n_f - 1.0)).max(0.0);
+    let r_corrected = r - (r - 1.0).powi(2) / (n_f - 1.0);
+    let k_corrected = k - (k - 1.0).powi(2) / (n_f - 1.0);
+    let denominator = r_corrected.min(k_corrected) - 1.0;
+    let cramers_v_corrected =
+        (denominator > 0.0).then(|| (phi_square_corrected / denominator).sqrt().clamp(0.0, 1.0));
+
+    Ok(Association { n,
+                     rows,
+                     cols,
+                     chi_square,
+                     cramers_v,
+                     cramers_v_corrected })
+}
+
+/// Absolute adjusted residual above which a cell is reported as over- or under-represented.
+pub(crate) const LARGE_RESIDUAL: f64 = 2.0;
+
+/// Counts of two categorical columns, with levels in order of first appearance.
+struct CrossTab<'a> {
+    row_levels: Vec<&'a str>,
+    col_levels: Vec<&'a str>,
+    counts: Vec<Vec<usize>>,
+    /// Rows in the source columns, including the ones dropped for a missing value.
+    source_rows: usize,
+}
+
+/// Counts value pairs of `row` × `column`, dropping rows where either value is missing.
+fn cross_tabulate<'a>(dataset: &'a StringMixedDataset,
+                      row: &str,
+                      column: &str)
+                      -> Result<CrossTab<'a>, String> {
+    let row_values = dataset.get(row)
+                            .ok_or_else(|| format!("row variable '{}' is not in the dataset", row))?;
+    let col_values = dataset.get(column)
+                            .ok_or_else(|| format!("column variable '{}' is not in the dataset", column))?;
+
+    let mut row_levels: IndexMap<&str, usize> = IndexMap::new();
+    let mut col_levels: IndexMap<&str, usize> = IndexMap::new();
+    let mut pairs = Vec::new();
+    for (row_value, col_value) in row_values.iter().zip(col_values) {
+        let (Some(row_value), Some(col_value)) = (row_value, col_value) else {
+            continue;
+        };
+        let next = row_levels.len();
+        let i = *row_levels.entry(row_value.as_str()).or_insert(next);
+        let next = col_levels.len();
+        let j = *col_levels.entry(col_value.as_str()).or_insert(next);
+        pairs.push((i, j));
+    }
+    let mut counts = vec![vec![0usize; col_levels.len()]; row_levels.len()];
+    for (i, j) in pairs {
+        counts[i][j] += 1;
+    }
+
+    Ok(CrossTab { row_levels: row_levels.into_keys().collect(),
+                  col_levels: col_levels.into_keys().collect(),
+                  counts,
+                  source_rows: row_values.len().max(col_values.len()) })
+}
+
+/// Adjusted standardized residuals `(O - E) / √(E (1 - row share) (1 - column share))`, the same
+/// as R's `chisq.test()$stdres`. They are approximately standard normal under independence.
+/// Assumes every row and column total is positive, as `association` checks.
+pub(crate) fn adjusted_residuals(counts: &[Vec<usize>]) -> Vec<Vec<f64>> {
+    let cols = counts.first().map_or(0, Vec::len);
+    let row_totals: Vec<f64> = counts.iter()
+                                     .map(|row| row.iter().sum::<usize>() as f64)
+                                     .collect();
+    let col_totals: Vec<f64> = (0..cols).map(|j| counts.iter().map(|row| row[j]).sum::<usize>() as f64)
+                                        .collect();
+    let n: f64 = row_totals.iter().sum();
+    counts.iter()
+          .zip(&row_totals)
+          .map(|(row, &row_total)| {
+              row.iter()
+                 .zip(&col_totals)
+                 .map(|(&observed, &col_total)| {
+                     let expected = row_total * col_total / n;
+                     let variance = expected * (1.0 - row_total / n) * (1.0 - col_total / n);
+                     (observed as f64 - expected) / variance.sqrt()
+                 })
+                 .collect()
+          })
+          .collect()
+}
+
+/// Observed counts of a two-way table and, on request, the adjusted residual of each cell and
+/// Goodman-Kruskal's gamma.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContingencyTable {
+    pub counts: ParsedDataTable,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub residuals: Option<ParsedDataTable>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gamma: Option<ParsedDataTable>,
+}
+
+/// Optional parts of `contingency_table`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ContingencyOptions {
+    pub residuals: bool,
+    /// Goodman-Kruskal's gamma; needs both level orders.
+    pub gamma: bool,
+    /// Order of the row levels, lowest first. Must list every observed level once.
+    pub row_order: Option<Vec<String>>,
+    /// Order of the column levels, lowest first. Must list every observed level once.
+    pub column_order: Option<Vec<String>>,
+}
+
+/// Pairs of observations ordered the same way (concordant) or opposite ways (discordant) on both
+/// variables of a table whose rows and columns are in ascending order. Tied pairs are in neither.
+pub(crate) fn concordant_discordant(counts: &[Vec<usize>]) -> (u64, u64) {
+    let mut concordant = 0u64;
+    let mut discordant = 0u64;
+    for (i, row) in counts.iter().enumerate() {
+        for (j, &count) in row.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            for below in &counts[i + 1..] {
+                let higher: usize = below[j + 1..].iter().sum();
+                let lower: usize = below[..j].iter().sum();
+                concordant += (count * higher) as u64;
+                discordant += (count * lower) as u64;
+            }
+        }
+    }
+    (concordant, discordant)
+}
+
+/// Cross-tabulates two categorical columns with row and column totals and the chi-square test of
+/// independence in the note. Levels appear in order of first appearance unless an order is given.
+///
+/// With `residuals`, adds a table of adjusted residuals aligned to the count cells; cells beyond
+/// ±2 are listed in its note as over- or under-represented. With `gamma`, adds Goodman-Kruskal's
+/// gamma, (C - D) / (C + D) over the concordant and discordant pairs, for two ordinal columns;
+/// since an order cannot be inferred from category labels, both level orders are then required.
+pub(crate) fn contingency_table(dataset: &StringMixedDataset,
+                                row: &str,
+                                column: &str,
+                                options: &ContingencyOptions)
+                                -> Result<ContingencyTable, String> {
+    if options.gamma && (options.row_order.is_none() || options.column_order.is_none()) {
+        return Err("gamma needs an explicit level order for both the row and the column".to_string());
+    }
+    let mut crosstab = cross_tabulate(dataset, row, column)?;
+    if let Some(order) = &options.row_order {
+        let positions = order_positions(&crosstab.row_levels, order, row)?;
+        crosstab.row_levels = positions.iter().map(|&i| crosstab.row_levels[i]).collect();
+        crosstab.counts = positions.iter().map(|&i| crosstab.counts[i].clone()).collect();
+    }
+    if let Some(order) = &options.column_order {
+        let positions = order_positions(&crosstab.col_levels, order, column)?;
+        crosstab.col_levels = positions.iter().map(|&j| crosstab.col_levels[j]).collect();
+        crosstab.counts = crosstab.counts
+                                  .iter()
+                                  .map(|counts| positions.iter().map(|&j| counts[j]).collect())
+                                  .collect();
+    }
+    let result = association(&crosstab.counts)?;
+    let df = (result.rows - 1) * (result.cols - 1);
+    let dropped = crosstab.source_rows - result.n;
+
+    let mut headers = vec![format!("{} \\ {}", row, column)];
+    headers.extend(crosstab.col_levels.iter().map(|level| level.to_string()));
+
+    let mut count_headers = headers.clone();
+    count_headers.push("合計".to_string());
+    let mut count_rows: Vec<Vec<Value>> = crosstab.row_levels
+                                                  .iter()
+                                                  .zip(&crosstab.counts)
+                                                  .map(|(level, counts)| {
+                                                      let mut cells = vec![Value::String(level.to_string())];
+                                                      cells.extend(counts.iter()
+                                                                         .map(|&count| Value::from(count)));
+                                                      cells.push(Value::from(counts.iter().sum::<usize>()));
+                                                      cells
+                                                  })
+                                                  .collect();
+    let mut total_row = vec![Value::String("合計".to_string())];
+    total_row.extend((0..result.cols).map(|j| {
+                                         Value::from(crosstab.counts
+                                                             .iter()
+                                                             .map(|counts| counts[j])
+                                                             .sum::<usize>())
+                                     }));
+    total_row.push(Value::from(result.n));
+    count_rows.push(total_row);
+
+    let mut notes = vec![format!("χ² = {:.3}, 自由度 = {}, n = {}", result.chi_square, df, result.n)];
+    if dropped > 0 {
+        notes.push(format!("{} または {} が欠損している {} 行を除外しました",
+                           row, column, dropped));
+    }
+    let counts = ParsedDataTable { headers: count_headers,
+                                   rows: count_rows,
+                                   note: Some(notes.join(" / ")),
+                                   title: Some(format!("クロス集計表 ({} × {})", row, column)),
+                                   column_types: Vec::new() };
+
+    let gamma = options.gamma.then(|| gamma_table(&crosstab.counts, row, column));
+    let residuals = options.residuals
+                           .then(|| residual_table(&crosstab, headers, &format!("{} × {}", row, column)));
+    Ok(ContingencyTable { counts,
+                          residuals,
+                          gamma })
+}
+
+/// Positions in `levels` of the observed levels, in the order `order` lists them. Every observed
+/// level must be listed exactly once; listed levels that were not observed are skipped.
+fn order_positions(levels: &[&str],
+                   order: &[String],
+                   variable: &str)
+                   -> Result<Vec<usize>, String> {
+    for (index, level) in order.iter().enumerate() {
+        if order[..index].contains(level) {
+            return Err(format!("level '{}' is listed twice in the order of '{}'", level, variable));
+        }
+    }
+    if let Some(missing) = levels.iter()
+                                 .find(|level| !order.iter().any(|listed| listed == *level))
+    {
+        return Err(format!("level '{}' of '{}' is missing from its order", missing, variable));
+    }
+    Ok(order.iter()
+            .filter_map(|listed| levels.iter().position(|level| level == listed))
+            .collect())
+}
+
+fn gamma_table(counts: &[Vec<usize>],
+               row: &str,
+               column: &str)
+               -> ParsedDataTable {
+    let (concordant, discordant) = concordant_discordant(counts);
+    let untied = concordant + discordant;
+    let gamma = (untied > 0).then(|| (concordant as f64 - discordant as f64) / untied as f64);
+
+    let mut notes = vec!["γ = (一致ペア数 - 不一致ペア数) / (一致ペア数 + 不一致ペア数)。同順位のペアは含みません".to_string()];
+    if gamma.is_none() {
+        notes.push("同順位でないペアがないため γ を計算できません".to_string());
+    }
+    ParsedDataTable { headers: vec!["一致ペア数".to_string(),
+                                    "不一致ペア数".to_string(),
+                                    "γ".to_string()],
+                      rows: vec![vec![Value::from(concordant),
+                                      Value::from(discordant),
+                                      number_cell(gamma)]],
+                      note: Some(notes.join(" / ")),
+                      title: Some(format!("Goodman-Kruskal の γ ({} × {})", row, column)),
+                      column_types: Vec::new() }
+}
+
+fn residual_table(crosstab: &CrossTab<'_>,
+                  headers: Vec<String>,
+                  label: &str)
+                  -> ParsedDataTable {
+    let residuals = adjusted_residuals(&crosstab.counts);
+    let mut over = Vec::new();
+    let mut under = Vec::new();
+    let mut rows = Vec::with_capacity(residuals.len());
+    for (row_level, residuals) in crosstab.row_levels.iter().zip(&residuals) {
+        let mut cells = vec![Value::String(row_level.to_string())];
+        for (col_level, &residual) in crosstab.col_levels.iter().zip(residuals) {
+            if residual > LARGE_RESIDUAL {
+                over.push(format!("{} × {}", row_level, col_level));
+            } else if residual < -LARGE_RESIDUAL {
+                under.push(format!("{} × {}", row_level, col_level));
+            }
+            cells.push(number_cell(Some(residual)));
+        }
+        rows.push(cells);
+    }
+
+    let mut notes = vec![format!("調整済み標準化残差 (|残差| > {} のセルは独立の場合の期待度数から大きく外れています)",
+                                 LARGE_RESIDUAL)];
+    if !over.is_empty() {
+        notes.push(format!("期待より多い: {}", over.join(", ")));
+    }
+    if !under.is_empty() {
+        notes.push(format!("期待より少ない: {}", under.join(", ")));
+    }
+    ParsedDataTable { headers,
+                      rows,
+                      note: Some(notes.join(" / ")),
+                      title: Some(format!("調整済み残差 ({})", label)),
+                      column_types: Vec::new() }
+}
+
+/// Adjusted (standardized Pearson) residuals of a cross-tab that is already counted, shaped like
+/// the input so that it can be colored as a heatmap: the first column holds the row levels and
+/// every other column the counts of one column level. A trailing 合計 row and column, as
+/// `contingency_table` adds, are dropped.
+pub(crate) fn contingency_residuals(table: &ParsedDataTable) -> Result<ParsedDataTable, String> {
+    let has_total_column = table.headers.len() > 2 && table.headers.last().is_some_and(|h| h == "合計");
+    let width = table.headers.len() - usize::from(has_total_column);
+    let is_total_row = |row: &Vec<Value>| row.first().and_then(Value::as_str) == Some("合計");
+    let body: Vec<&Vec<Value>> = match table.rows.split_last() {
+        Some((last, rest)) if !rest.is_empty() && is_total_row(last) => rest.iter().collect(),
+        _ => table.rows.iter().collect(),
+    };
+
+    let row_levels: Vec<String> = body.iter()
+                                      .map(|row| match row.first() {
+                                          Some(Value::String(level)) => level.clone(),
+                                          Some(Value::Null) | None => String::new(),
+                                          Some(level) => level.to_string(),
+                                      })
+                                      .collect();
+    let counts = body.iter()
+                     .zip(&row_levels)
+                     .map(|(row, level)| {
+                         (1..width).map(|j| {
+                                       count_cell(row.get(j)).ok_or_else(|| {
+                                           format!("cell '{}' × '{}' is not a non-negative whole count",
+                                                   level, table.headers[j])
+                                       })
+                                   })
+                                   .collect::<Result<Vec<usize>, String>>()
+                     })
+                     .collect::<Result<Vec<Vec<usize>>, String>>()?;
+    let result = association(&counts)?;
+
+    let crosstab = CrossTab { row_levels: row_levels.iter().map(String::as_str).collect(),
+                              col_levels: table.headers[1..width].iter().map(String::as_str).collect(),
+                              counts,
+                              source_rows: result.n };
+    let label = table.headers[0].replace(" \\ ", " × ");
+    Ok(residual_table(&crosstab, table.headers[..width].to_vec(), &label))
+}
+
+/// A count cell as a number or numeric text; fractional, negative and missing values are `None`.
+fn count_cell(cell: Option<&Value>) -> Option<usize> {
+    let value = match cell? {
+        Value::Number(number) => number.as_f64()?,
+        Value::String(text) => text.trim().parse::<f64>().ok()?,
+        _ => return None,
+    };
+    (value.is_finite() && value >= 0.0 && value.fract() == 0.0).then_some(value as usize)
+}
+
+/// Cross-tabulates two categorical columns and reports chi-square with Cramér's V as an
+/// effect size that is comparable across tables of different sizes. Rows where either value
+/// is missing are dropped. With `bias_corrected`, the bias-corrected V is added as a column.
+pub(crate) fn cramers_v(dataset: &StringMixedDataset,
+                        row: &str,
+                        column: &str,
+                        bias_corrected: bool)
+                        -> Result<ParsedDataTable, String> {
+    let crosstab = cross_tabulate(dataset, row, column)?;
+    let result = association(&crosstab.counts)?;
+    let df = (result.rows - 1) * (result.cols - 1);
+    let dropped = crosstab.source_rows - result.n;
+
+    let mut headers = vec!["行変数".to_string(),
+                           "列変数".to_string(),
+                           "n".to_string(),
+                           "表の大きさ".to_string(),
+                           "χ²".to_string(),
+                           "自由度".to_string(),
+                           "クラメールの V".to_string()];
+    let mut values = vec![Value::String(row.to_string()),
+                          Value::String(column.to_string()),
+                          Value::from(result.n),
+                          Value::String(format!("{} × {}", result.rows, result.cols)),
+                          number_cell(Some(result.chi_square)),
+                          Value::from(df),
+                          number_cell(Some(result.cramers_v))];
+    if bias_corrected {
+        headers.push("補正済み V".to_string());
+        values.push(number_cell(result.cramers_v_corrected));
+    }
+
+    let mut notes = vec!["V = √(χ² / (n × (min(行数, 列数) - 1)))".to_string()];
+    if bias_corrected {
+        notes.push("補正済み V は Bergsma (2013) の方法によります".to_string());
+    }
+    if dropped > 0 {
+        notes.push(format!("{} または {} が欠損している {} 行を除外しました",
+                           row, column, dropped));
+    }
+
+    Ok(ParsedDataTable { headers,
+                         rows: vec![values],
+                         note: Some(notes.join(" / ")),
+                         title: Some(format!("クラメールの連関係数 ({} × {})", row, column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        ContingencyOptions,
+        adjusted_residuals,
+        association,
+        concordant_discordant,
+        contingency_residuals,
+        contingency_table,
+        cramers_v,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    #[test]
+    fn two_by_three_table_has_known_v() {
+        let result = association(&[vec![10, 20, 30], vec![20, 20, 10]]).unwrap();
+
+        assert_eq!(result.n, 110);
+        assert!((result.chi_square - 12.527_777_777_777_78).abs() < 1e-9);
+        assert!((result.cramers_v - 0.337_474_278_855_276_5).abs() < 1e-12);
+        // V² × n × (min(r, c) - 1) recovers chi-square
+        assert!((result.cramers_v.powi(2) * 110.0 - result.chi_square).abs() < 1e-9);
+        assert!((result.cramers_v_corrected.unwrap() - 0.310_523_585_447_774_9).abs() < 1e-12);
+    }
+
+    #[test]
+    fn perfect_association_is_one_and_independence_is_zero() {
+        let perfect = association(&[vec![5, 0], vec![0, 5]]).unwrap();
+        assert!((perfect.cramers_v - 1.0).abs() < 1e-12);
+
+        let independent = association(&[vec![4, 8], vec![2, 4]]).unwrap();
+        assert!(independent.cramers_v.abs() < 1e-12);
+    }
+
+    #[test]
+    fn dataset_columns_are_cross_tabulated_without_missing_pairs() {
+        let mut dataset = IndexMap::new();
+        let column = |values: &[Option<&str>]| -> Vec<Option<String>> {
+            values.iter().map(|value| value.map(str::to_string)).collect()
+        };
+        dataset.insert("sex".to_string(),
+                       column(&[Some("m"), Some("m"), Some("f"), Some("f"), None]));
+        dataset.insert("answer".to_string(),
+                       column(&[Some("yes"), Some("yes"), Some("no"), Some("no"), Some("no")]));
+
+        let table = cramers_v(&dataset, "sex", "answer", true).unwrap();
+
+        assert_eq!(table.rows[0][2], Value::from(4));
+        assert_eq!(table.rows[0][3], Value::from("2 × 2"));
+        assert_eq!(table.rows[0][6], Value::from(1.0));
+        assert_eq!(table.headers.len(), 8);
+        assert!(table.note.unwrap().contains("1 行を除外"));
+    }
+
+    #[test]
+    fn single_level_is_rejected() {
+        assert!(association(&[vec![3, 4]]).is_err());
+    }
+
+    #[test]
+    fn over_represented_cell_has_a_large_positive_residual() {
+        // 30 of the 40 "a" rows answered "x", against an expected 40 × 40 / 100 = 16.
+        let counts = [vec![30, 5, 5], vec![10, 25, 25]];
+        let residuals = adjusted_residuals(&counts);
+
+        assert!(residuals[0][0] > 2.0);
+        // Same as R: chisq.test(matrix(c(30, 10, 5, 25, 5, 25), 2))$stdres[1, 1]
+        assert!((residuals[0][0] - 5.833_333_333_333_333).abs() < 1e-9);
+        // Residuals of a 2-row table mirror each other within a column.
+        assert!((residuals[0][0] + residuals[1][0]).abs() < 1e-12);
+
+        let mut dataset = IndexMap::new();
+        let mut group = Vec::new();
+        let mut answer = Vec::new();
+        for (level, row) in ["a", "b"].iter().zip(&counts) {
+            for (value, &count) in ["x", "y", "z"].iter().zip(row) {
+                group.extend(std::iter::repeat_n(Some(level.to_string()), count));
+                answer.extend(std::iter::repeat_n(Some(value.to_string()), count));
+            }
+        }
+        dataset.insert("group".to_string(), group);
+        dataset.insert("answer".to_string(), answer);
+
+        let with_residuals = ContingencyOptions { residuals: true,
+                                                  ..ContingencyOptions::default() };
+        let table = contingency_table(&dataset, "group", "answer", &with_residuals).unwrap();
+        assert_eq!(table.counts.headers,
+                   vec!["group \\ answer", "x", "y", "z", "合計"]);
+        assert_eq!(table.counts.rows[0][1], Value::from(30));
+        assert_eq!(table.counts.rows[2][4], Value::from(100));
+        let residuals = table.residuals.unwrap();
+        assert_eq!(residuals.rows[0][1].as_f64().unwrap(),
+                   adjusted_residuals(&counts)[0][0]);
+        assert!(residuals.note.unwrap().contains("期待より多い: a × x"));
+
+        assert!(contingency_table(&dataset, "group", "answer", &ContingencyOptions::default()).unwrap()
+                                                                                              .residuals
+                                                                                              .is_none());
+    }
+
+    #[test]
+    fn monotone_ordinal_table_has_a_positive_gamma() {
+        // Rows: education low → high; columns: satisfaction low → high; mass on the diagonal.
+        let counts = [vec![20, 8, 2], vec![6, 18, 6], vec![2, 7, 21]];
+        let (concordant, discordant) = concordant_discordant(&counts);
+        // e.g. the 20 (low, low) cases are concordant with the 18 + 6 + 7 + 21 cases above both.
+        assert_eq!((concordant, discordant), (1802, 220));
+
+        let mut dataset = IndexMap::new();
+        let mut education = Vec::new();
+        let mut satisfaction = Vec::new();
+        // Levels first appear in an order that is not the ordinal one.
+        for (level, row) in ["高", "中", "低"].iter().zip(counts.iter().rev()) {
+            for (value, &count) in ["満足", "普通", "不満"].iter().zip(row.iter().rev()) {
+                education.extend(std::iter::repeat_n(Some(level.to_string()), count));
+                satisfaction.extend(std::iter::repeat_n(Some(value.to_string()), count));
+            }
+        }
+        dataset.insert("education".to_string(), education);
+        dataset.insert("satisfaction".to_string(), satisfaction);
+        let order = |levels: &[&str]| Some(levels.iter().map(|level| level.to_string()).collect());
+        let options = ContingencyOptions { gamma: true,
+                                           row_order: order(&["低", "中", "高"]),
+                                           column_order: order(&["不満", "普通", "満足"]),
+                                           ..ContingencyOptions::default() };
+
+        let table = contingency_table(&dataset, "education", "satisfaction", &options).unwrap();
+
+        assert_eq!(table.counts.headers,
+                   vec!["education \\ satisfaction", "不満", "普通", "満足", "合計"]);
+        assert_eq!(table.counts.rows[0][1], Value::from(20));
+        let gamma = table.gamma.unwrap();
+        assert_eq!(gamma.rows[0][0], Value::from(1802));
+        let value = gamma.rows[0][2].as_f64().unwrap();
+        assert!((value - 1582.0 / 2022.0).abs() < 1e-12 && value > 0.0);
+
+        let without_order = ContingencyOptions { gamma: true,
+                                                 ..ContingencyOptions::default() };
+        assert!(contingency_table(&dataset, "education", "satisfaction", &without_order).is_err());
+        let incomplete = ContingencyOptions { row_order: order(&["低", "高"]),
+                                              ..ContingencyOptions::default() };
+        let err = contingency_table(&dataset, "education", "satisfaction", &incomplete).unwrap_err();
+        assert!(err.contains("'中'"), "{}", err);
+    }
+
+    #[test]
+    fn dominant_diagonal_has_positive_residuals_on_it_and_negative_ones_off_it() {
+        let table = ParsedDataTable { headers: vec!["before \\ after".to_string(),
+                                                    "low".to_string(),
+                                                    "mid".to_string(),
+                                                    "high".to_string(),
+                                                    "合計".to_string()],
+                                      rows: vec![vec![Value::from("low"),
+                                                      Value::from(20),
+                                                      Value::from(3),
+                                                      Value::from(2),
+                                                      Value::from(25)],
+                                                 vec![Value::from("mid"),
+                                                      Value::from("4"),
+                                                      Value::from(18),
+                                                      Value::from(3),
+                                                      Value::from(25)],
+                                                 vec![Value::from("high"),
+                                                      Value::from(1),
+                                                      Value::from(4),
+                                                      Value::from(20),
+                                                      Value::from(25)],
+                                                 vec![Value::from("合計"),
+                                                      Value::from(25),
+                                                      Value::from(25),
+                                                      Value::from(25),
+                                                      Value::from(75)]],
+                                      note: None,
+                                      title: None,
+                                      column_types: Vec::new() };
+
+        let residuals = contingency_residuals(&table).unwrap();
+
+        assert_eq!(residuals.headers, vec!["before \\ after", "low", "mid", "high"]);
+        assert_eq!(residuals.rows.len(), 3);
+        assert_eq!(residuals.title.as_deref(), Some("調整済み残差 (before × after)"));
+        for (i, row) in residuals.rows.iter().enumerate() {
+            for (j, cell) in row[1..].iter().enumerate() {
+                let residual = cell.as_f64().unwrap();
+                if i == j {
+                    assert!(residual > 5.0, "({}, {}) = {}", i, j, residual);
+                } else {
+                    assert!(residual < -1.0, "({}, {}) = {}", i, j, residual);
+                }
+            }
+        }
+
+        let mut fractional = table.clone();
+        fractional.rows[0][1] = Value::from(2.5);
+        assert!(contingency_residuals(&fractional).is_err());
+    }
+}
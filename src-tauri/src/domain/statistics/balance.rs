@@ -0,0 +1,195 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::numeric::{
+    NumericCellContext,
+    parse_numeric_string,
+};
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+
+/// Below this min/max group-size ratio the design is flagged as severely unbalanced.
+pub(crate) const SEVERE_IMBALANCE_RATIO: f64 = 0.5;
+
+/// Per-level group sizes of a factor plus the min/max size ratio (1 = perfectly balanced).
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupBalance {
+    pub table: ParsedDataTable,
+    /// `None` when no level has a usable response.
+    pub balance_ratio: Option<f64>,
+}
+
+/// Counts the numeric `response` values in each level of `factor`, in order of first appearance.
+/// Responses are read like a string-mixed numeric column, so text that is not a number counts as
+/// missing. Levels whose responses are all missing are kept with a count of 0; rows with a missing
+/// factor value are left out because they cannot enter a grouped analysis.
+pub(crate) fn group_balance(table: &ParsedDataTable,
+                            factor: &str,
+                            response: &str)
+                            -> Result<GroupBalance, String> {
+    let factor_index = table.column_index(factor)?;
+    let response_index = table.column_index(response)?;
+
+    let mut counts: IndexMap<String, usize> = IndexMap::new();
+    let mut missing_factor = 0usize;
+    let mut non_numeric = 0usize;
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let level = match row.get(factor_index) {
+            None | Some(Value::Null) => {
+                missing_factor += 1;
+                continue;
+            },
+            Some(Value::String(value)) => value.clone(),
+            Some(value) => value.to_string(),
+        };
+        let observed = match row.get(response_index) {
+            None | Some(Value::Null) => false,
+            Some(Value::Number(_)) => true,
+            Some(Value::String(text)) => {
+                let context = NumericCellContext::new(row_index, response_index, response);
+                match parse_numeric_string(text, context) {
+                    Ok(value) => value.is_some(),
+                    Err(_) => {
+                        non_numeric += 1;
+                        false
+                    },
+                }
+            },
+            Some(_) => {
+                non_numeric += 1;
+                false
+            },
+        };
+        *counts.entry(level).or_default() += usize::from(observed);
+    }
+    if counts.is_empty() {
+        return Err(format!("factor '{}' has no non-missing levels", factor));
+    }
+
+    let total: usize = counts.values().sum();
+    let min = counts.values().copied().min().expect("counts is not empty");
+    let max = counts.values().copied().max().expect("counts is not empty");
+    let balance_ratio = (max > 0).then(|| min as f64 / max as f64);
+
+    let rows = counts.iter()
+                     .map(|(level, count)| {
+                         let share = (total > 0).then(|| *count as f64 / total as f64);
+                         vec![Value::String(level.clone()),
+                              Value::from(*count),
+                              number_cell(share)]
+                     })
+                     .collect();
+
+    let ratio_note = balance_ratio.map(|ratio| {
+                                      if ratio < SEVERE_IMBALANCE_RATIO {
+                                          format!("バランス比 (最小 / 最大) = {:.3}: 群の大きさが大きく偏っています。分散分析の解釈に注意してください",
+                                                  ratio)
+                                      } else {
+                                          format!("バランス比 (最小 / 最大) = {:.3}", ratio)
+                                      }
+                                  });
+    let missing_note = (missing_factor > 0).then(|| {
+                                               format!("{} が欠損している {} 行を除外しました",
+                                                       factor, missing_factor)
+                                           });
+    let non_numeric_note = (non_numeric > 0).then(|| {
+                                                format!("{} の数値でない {} 件を欠損として数えました",
+                                                        response, non_numeric)
+                                            });
+
+    Ok(GroupBalance { table: ParsedDataTable { headers: vec!["水準".to_string(),
+                                                             "n".to_string(),
+                                                             "割合".to_string()],
+                                               rows,
+                                               note: join_notes([ratio_note,
+                                                                 missing_note,
+                                                                 non_numeric_note]),
+                                               title: Some(format!("群の大きさ ({} × {})",
+                                                                   factor, response)),
+                                               column_types: Vec::new() },
+                      balance_ratio })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::group_balance;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(rows: &[(Value, Value)]) -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["group".to_string(), "score".to_string()],
+                          rows: rows.iter()
+                                    .map(|(group, score)| vec![group.clone(), score.clone()])
+                                    .collect(),
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn unbalanced_design_reports_counts_and_ratio() {
+        let mut rows = vec![(Value::from("a"), Value::from(1.0)); 6];
+        rows.push((Value::from("a"), Value::Null));
+        rows.extend(vec![(Value::from("b"), Value::from(2.0)); 2]);
+        rows.push((Value::from("c"), Value::Null));
+        rows.push((Value::Null, Value::from(3.0)));
+
+        let balance = group_balance(&table(&rows), "group", "score").unwrap();
+
+        let counts: Vec<(Value, Value)> = balance.table
+                                                 .rows
+                                                 .iter()
+                                                 .map(|row| (row[0].clone(), row[1].clone()))
+                                                 .collect();
+        assert_eq!(counts,
+                   vec![(Value::from("a"), Value::from(6)),
+                        (Value::from("b"), Value::from(2)),
+                        (Value::from("c"), Value::from(0))]);
+        assert_eq!(balance.balance_ratio, Some(0.0));
+        let note = balance.table.note.unwrap();
+        assert!(note.contains("大きく偏っています"), "{}", note);
+        assert!(note.contains("1 行を除外"), "{}", note);
+    }
+
+    #[test]
+    fn ratio_is_min_over_max_group_size() {
+        let mut rows = vec![(Value::from(1), Value::from(1.0)); 4];
+        rows.extend(vec![(Value::from(2), Value::from(2.0)); 3]);
+
+        let balance = group_balance(&table(&rows), "group", "score").unwrap();
+
+        assert_eq!(balance.balance_ratio, Some(0.75));
+        assert_eq!(balance.table.rows[0][0], Value::from("1"));
+        assert!(!balance.table.note.unwrap().contains("偏っています"));
+    }
+
+    #[test]
+    fn non_numeric_responses_count_as_missing() {
+        let rows = vec![(Value::from("a"), Value::from(1.0)),
+                        (Value::from("a"), Value::from(" 2.5 ")),
+                        (Value::from("a"), Value::from("n/a")),
+                        (Value::from("a"), Value::from(" ")),
+                        (Value::from("b"), Value::from(true)),
+                        (Value::from("b"), Value::from("3"))];
+
+        let balance = group_balance(&table(&rows), "group", "score").unwrap();
+
+        let counts: Vec<Value> = balance.table.rows.iter().map(|row| row[1].clone()).collect();
+        assert_eq!(counts, vec![Value::from(2), Value::from(1)]);
+        assert_eq!(balance.balance_ratio, Some(0.5));
+        let note = balance.table.note.unwrap();
+        assert!(note.contains("score の数値でない 2 件"), "{}", note);
+    }
+
+    #[test]
+    fn unknown_column_is_rejected() {
+        let err = group_balance(&table(&[]), "group", "weight").unwrap_err();
+        assert!(err.contains("column 'weight' is not in the dataset"), "{}", err);
+    }
+}
@@ -0,0 +1,224 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::string_mixed::{
+    StringMixedDataset,
+    parse_numeric_cells,
+};
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::sorted_quantile;
+
+/// Whisker reach in IQRs (Tukey).
+const WHISKER_IQR: f64 = 1.5;
+
+/// Five-number summary and whiskers per column (and group level), with the outliers listed
+/// separately, one row per outlying cell.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BoxplotStats {
+    pub table: ParsedDataTable,
+    pub outliers: ParsedDataTable,
+}
+
+/// Box plot statistics of numeric `columns`, per level of `group` when given.
+///
+/// Quartiles use linear interpolation (R's `quantile(type = 7)`, as in ggplot2's box plots).
+/// Following Tukey, each whisker runs to the most extreme value within 1.5 × IQR of its quartile,
+/// i.e. inside `[Q1 - 1.5 IQR, Q3 + 1.5 IQR]`; values outside that range are outliers.
+/// Missing values are excluded, as are rows whose group is missing. Levels keep the order in which
+/// they first appear.
+pub(crate) fn boxplot_stats(dataset: &StringMixedDataset,
+                            columns: &[String],
+                            group: Option<&str>)
+                            -> Result<BoxplotStats, String> {
+    if columns.is_empty() {
+        return Err("at least one column is required".to_string());
+    }
+    let groups = group.map(|group| {
+                          dataset.get(group)
+                                 .ok_or_else(|| format!("group variable '{}' is not in the dataset", group))
+                      })
+                      .transpose()?;
+    let mut levels: Vec<Option<&str>> = Vec::new();
+    match groups {
+        Some(groups) => {
+            for level in groups.iter().flatten() {
+                if !levels.contains(&Some(level.as_str())) {
+                    levels.push(Some(level.as_str()));
+                }
+            }
+        },
+        None => levels.push(None),
+    }
+
+    let mut rows = Vec::new();
+    let mut outlier_rows = Vec::new();
+    for column in columns {
+        let values = numeric_column(dataset, column)?;
+        for level in &levels {
+            let observed: Vec<(usize, f64)> =
+                values.iter()
+                      .enumerate()
+                      .filter(|(i, _)| groups.is_none_or(|groups| groups[*i].as_deref() == *level))
+                      .filter_map(|(i, value)| value.map(|value| (i, value)))
+                      .collect();
+            let mut sorted: Vec<f64> = observed.iter().map(|(_, value)| *value).collect();
+            sorted.sort_by(f64::total_cmp);
+
+            let mut row = vec![Value::from(column.as_str())];
+            let mut outlier_prefix = vec![Value::from(column.as_str())];
+            if let Some(level) = level {
+                row.push(Value::from(*level));
+                outlier_prefix.push(Value::from(*level));
+            }
+            row.push(Value::from(sorted.len()));
+            if sorted.is_empty() {
+                row.extend(std::iter::repeat_n(Value::Null, 7));
+                row.push(Value::from(0));
+                rows.push(row);
+                continue;
+            }
+
+            let q1 = sorted_quantile(&sorted, 0.25);
+            let q3 = sorted_quantile(&sorted, 0.75);
+            let reach = WHISKER_IQR * (q3 - q1);
+            let (lower_fence, upper_fence) = (q1 - reach, q3 + reach);
+            let inside = |value: &&f64| (lower_fence..=upper_fence).contains(*value);
+            let lower_whisker = sorted.iter().find(inside).copied();
+            let upper_whisker = sorted.iter().rev().find(inside).copied();
+
+            let outliers: Vec<&(usize, f64)> = observed.iter().filter(|(_, value)| !inside(&value)).collect();
+            row.extend([number_cell(sorted.first().copied()),
+                        number_cell(Some(q1)),
+                        number_cell(Some(sorted_quantile(&sorted, 0.5))),
+                        number_cell(Some(q3)),
+                        number_cell(sorted.last().copied()),
+                        number_cell(lower_whisker),
+                        number_cell(upper_whisker),
+                        Value::from(outliers.len())]);
+            rows.push(row);
+            for (i, value) in outliers {
+                let mut outlier_row = outlier_prefix.clone();
+                outlier_row.extend([Value::from(i + 1), number_cell(Some(*value))]);
+                outlier_rows.push(outlier_row);
+            }
+        }
+    }
+
+    let mut headers = vec!["列".to_string()];
+    if let Some(group) = group {
+        headers.push(group.to_string());
+    }
+    let mut outlier_headers = headers.clone();
+    headers.extend(["n",
+                    "最小値",
+                    "第1四分位数",
+                    "中央値",
+                    "第3四分位数",
+                    "最大値",
+                    "下ひげ",
+                    "上ひげ",
+                    "外れ値数"].map(str::to_string));
+    outlier_headers.extend(["行".to_string(), "値".to_string()]);
+
+    let mut notes = vec!["四分位数は線形補間 (R の quantile(type = 7))、ひげは Q1 - 1.5×IQR 以上・Q3 + 1.5×IQR 以下で最も外側の値までです。その範囲外の値を外れ値としました".to_string()];
+    if let Some(groups) = groups {
+        let missing_group = groups.iter().filter(|level| level.is_none()).count();
+        if missing_group > 0 {
+            notes.push(format!("群が欠損している {} 行を除外しました", missing_group));
+        }
+    }
+    let outlier_count = outlier_rows.len();
+    Ok(BoxplotStats { table: ParsedDataTable { headers,
+                                               rows,
+                                               note: Some(notes.join(" / ")),
+                                               title: Some("箱ひげ図の統計量".to_string()),
+                                               column_types: Vec::new() },
+                      outliers: ParsedDataTable { headers: outlier_headers,
+                                                  rows: outlier_rows,
+                                                  note: Some(format!("外れ値 {} 件", outlier_count)),
+                                                  title: Some("外れ値".to_string()),
+                                                  column_types: Vec::new() } })
+}
+
+fn numeric_column(dataset: &StringMixedDataset,
+                  column: &str)
+                  -> Result<Vec<Option<f64>>, String> {
+    let (col_index, _, raw_values) =
+        dataset.get_full(column)
+               .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+    parse_numeric_cells(raw_values, col_index, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use crate::domain::input::string_mixed::StringMixedDataset;
+
+    use super::boxplot_stats;
+
+    fn dataset(values: &[Option<&str>],
+               groups: &[Option<&str>])
+               -> StringMixedDataset {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(),
+                       values.iter().map(|v| v.map(str::to_string)).collect());
+        dataset.insert("g".to_string(),
+                       groups.iter().map(|v| v.map(str::to_string)).collect());
+        dataset
+    }
+
+    #[test]
+    fn whiskers_stop_at_the_last_value_inside_the_fences() {
+        let mut values: Vec<Option<&str>> = ["3", "1", "100", "2", "5", "4", "6", "7", "8", "9"].map(Some)
+                                                                                                .to_vec();
+        values.push(None);
+        let data = dataset(&values, &[None; 11]);
+
+        let stats = boxplot_stats(&data, &["x".to_string()], None).unwrap();
+
+        // Q1 = 3.25, Q3 = 7.75, IQR = 4.5, fences = [-3.5, 14.5]
+        let row = &stats.table.rows[0];
+        assert_eq!(row[1], Value::from(10));
+        assert_eq!(row[3], Value::from(3.25));
+        assert_eq!(row[5], Value::from(7.75));
+        assert_eq!(row[6], Value::from(100.0));
+        assert_eq!(row[7], Value::from(1.0));
+        assert_eq!(row[8], Value::from(9.0));
+        assert_eq!(row[9], Value::from(1));
+        assert_eq!(stats.outliers.rows,
+                   vec![vec![Value::from("x"), Value::from(3), Value::from(100.0)]]);
+        stats.table.validate().unwrap();
+    }
+
+    #[test]
+    fn groups_are_summarized_separately() {
+        let data = dataset(&[Some("1"),
+                             Some("2"),
+                             Some("10"),
+                             Some("20"),
+                             Some("30"),
+                             Some("5")],
+                           &[Some("a"), Some("a"), Some("b"), Some("b"), Some("b"), None]);
+
+        let stats = boxplot_stats(&data, &["x".to_string()], Some("g")).unwrap();
+
+        assert_eq!(stats.table.headers[1], "g");
+        let medians: Vec<(&Value, &Value)> = stats.table.rows.iter().map(|row| (&row[1], &row[5])).collect();
+        assert_eq!(medians,
+                   vec![(&Value::from("a"), &Value::from(1.5)),
+                        (&Value::from("b"), &Value::from(20.0))]);
+        assert!(stats.table
+                     .note
+                     .as_deref()
+                     .unwrap()
+                     .contains("群が欠損している 1 行"));
+        assert!(boxplot_stats(&data, &["y".to_string()], None).is_err());
+    }
+}
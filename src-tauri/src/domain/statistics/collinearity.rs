@@ -0,0 +1,318 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+use crate::domain::statistics::sample::{
+    is_constant,
+    pearson,
+};
+
+/// VIF above which a column is flagged as highly collinear with the others.
+pub(crate) const HIGH_VIF: f64 = 10.0;
+
+/// Pivots smaller than this are treated as zero when inverting the correlation matrix.
+const SINGULAR_PIVOT: f64 = 1e-12;
+
+/// Highly correlated column pairs and, on request, each column's variance inflation factor.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CollinearityScreen {
+    pub pairs: ParsedDataTable,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vif: Option<ParsedDataTable>,
+}
+
+/// Lists the column pairs whose absolute Pearson correlation (pairwise-complete observations)
+/// exceeds `threshold`, strongest first. With `include_vif`, also reports each column's VIF, read
+/// off the diagonal of the inverse correlation matrix of the complete rows.
+pub(crate) fn collinearity_screen(dataset: &NumericDataset,
+                                  threshold: f64,
+                                  include_vif: bool)
+                                  -> Result<CollinearityScreen, String> {
+    if !(0.0..1.0).contains(&threshold) {
+        return Err(format!("threshold must be in [0, 1): {}", threshold));
+    }
+    if dataset.len() < 2 {
+        return Err(format!("at least two columns are required (got {})", dataset.len()));
+    }
+    let columns: Vec<(&String, &Vec<Option<f64>>)> = dataset.iter().collect();
+
+    let mut pairs = Vec::new();
+    for (i, (x_name, x)) in columns.iter().enumerate() {
+        for (y_name, y) in &columns[i + 1..] {
+            let complete: Vec<(f64, f64)> = x.iter()
+                                             .zip(y.iter())
+                                             .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+                                             .collect();
+            if complete.len() < 3 {
+                continue;
+            }
+            if let Some(r) = pearson(&complete).filter(|r| r.abs() > threshold) {
+                pairs.push((*x_name, *y_name, r, complete.len()));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.abs().total_cmp(&a.2.abs()));
+
+    let pair_count = pairs.len();
+    let rows = pairs.into_iter()
+                    .map(|(x_name, y_name, r, n)| {
+                        vec![Value::String(x_name.clone()),
+                             Value::String(y_name.clone()),
+                             number_cell(Some(r)),
+                             Value::from(n)]
+                    })
+                    .collect();
+    let pairs = ParsedDataTable { headers: vec!["変数1".to_string(),
+                                                "変数2".to_string(),
+                                                "相関係数".to_string(),
+                                                "n".to_string()],
+                                  rows,
+                                  note: Some(format!("|r| > {} の組み合わせ {} 件 (欠損値はペアごとに除外)",
+                                                     threshold, pair_count)),
+                                  title: Some("相関の高い変数の組み合わせ".to_string()),
+                                  column_types: Vec::new() };
+
+    let vif = include_vif.then(|| vif_table(&columns));
+    Ok(CollinearityScreen { pairs, vif })
+}
+
+fn vif_table(columns: &[(&String, &Vec<Option<f64>>)]) -> ParsedDataTable {
+    let (complete_rows, complete) = complete_cases(columns);
+
+    let defined = complete_rows.len() > columns.len() && !complete.iter().any(|values| is_constant(values));
+    let vifs = if defined {
+        invert(&correlation_matrix(&complete)).map(|inverse| {
+                                                  (0..columns.len()).map(|j| inverse[j][j])
+                                                                    .collect::<Vec<_>>()
+                                              })
+    } else {
+        None
+    };
+
+    let rows = columns.iter()
+                      .enumerate()
+                      .map(|(j, (name, _))| {
+                          let vif = vifs.as_ref().map(|vifs| vifs[j]);
+                          vec![Value::String((*name).clone()),
+                               number_cell(vif),
+                               vif.map_or(Value::Null, |vif| Value::Bool(vif > HIGH_VIF))]
+                      })
+                      .collect();
+
+    let undefined_note = if !defined {
+        Some("完全なケースが変数の数 + 1 未満、または値が一定の変数があるため VIF を計算できません".to_string())
+    } else if vifs.is_none() {
+        Some("完全に共線な変数があるため VIF を計算できません".to_string())
+    } else {
+        None
+    };
+    ParsedDataTable { headers: vec!["変数".to_string(),
+                                    "VIF".to_string(),
+                                    format!("VIF > {}", HIGH_VIF)],
+                      rows,
+                      note: join_notes([Some(format!("完全なケース n = {} で算出しています",
+                                                     complete_rows.len())),
+                                        undefined_note]),
+                      title: Some("分散拡大係数 (VIF)".to_string()),
+                      column_types: Vec::new() }
+}
+
+/// Variance inflation factors of `columns` on their own, with the same definition and handling of
+/// undefined cases as the VIF table of `collinearity_screen`.
+pub(crate) fn vif_for_columns(dataset: &NumericDataset,
+                              columns: &[String])
+                              -> Result<ParsedDataTable, String> {
+    if columns.len() < 2 {
+        return Err(format!("at least two columns are required (got {})", columns.len()));
+    }
+    let mut selected: Vec<(&String, &Vec<Option<f64>>)> = Vec::with_capacity(columns.len());
+    for column in columns {
+        let entry = dataset.get_key_value(column)
+                           .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+        if selected.iter().any(|(name, _)| *name == column) {
+            return Err(format!("column '{}' is listed more than once", column));
+        }
+        selected.push(entry);
+    }
+    Ok(vif_table(&selected))
+}
+
+/// Row indices where every column is observed, and each column's values on those rows.
+fn complete_cases(columns: &[(&String, &Vec<Option<f64>>)]) -> (Vec<usize>, Vec<Vec<f64>>) {
+    let row_count = columns.iter().map(|(_, values)| values.len()).min().unwrap_or(0);
+    let complete_rows: Vec<usize> =
+        (0..row_count).filter(|&row| columns.iter().all(|(_, values)| values[row].is_some()))
+                      .collect();
+    let complete = columns.iter()
+                          .map(|(_, values)| {
+                              complete_rows.iter()
+                                           .map(|&row| values[row].expect("complete row"))
+                                           .collect()
+                          })
+                          .collect();
+    (complete_rows, complete)
+}
+
+fn correlation_matrix(columns: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    (0..columns.len()).map(|i| {
+                          (0..columns.len()).map(|j| {
+                                                if i == j {
+                                                    1.0
+                                                } else {
+                                                    let pairs: Vec<(f64, f64)> =
+                                                        columns[i].iter()
+                                                                  .copied()
+                                                                  .zip(columns[j].iter().copied())
+                                                                  .collect();
+                                                    pearson(&pairs).unwrap_or(0.0)
+                                                }
+                                            })
+                                            .collect()
+                      })
+                      .collect()
+}
+
+/// Gauss-Jordan inversion with partial pivoting; `None` when the matrix is (numerically) singular.
+fn invert(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut inverse: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+                                           .collect();
+    for col in 0..n {
+        let pivot_row =
+            (col..n).max_by(|&a_row, &b_row| a[a_row][col].abs().total_cmp(&a[b_row][col].abs()))?;
+        if a[pivot_row][col].abs() < SINGULAR_PIVOT {
+            return None;
+        }
+        a.swap(col, pivot_row);
+        inverse.swap(col, pivot_row);
+
+        let pivot = a[col][col];
+        for j in 0..n {
+            a[col][j] /= pivot;
+            inverse[col][j] /= pivot;
+        }
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = a[row][col];
+            if factor == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                a[row][j] -= factor * a[col][j];
+                inverse[row][j] -= factor * inverse[col][j];
+            }
+        }
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        collinearity_screen,
+        vif_for_columns,
+    };
+    use crate::domain::statistics::sample::pearson;
+
+    fn dataset() -> IndexMap<String, Vec<Option<f64>>> {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let noise = [0.01, -0.02, 0.015, 0.0, -0.01, 0.02, -0.015, 0.005];
+        let z = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), x.iter().map(|v| Some(*v)).collect());
+        dataset.insert("x_copy".to_string(),
+                       x.iter().zip(noise).map(|(v, e)| Some(v + e)).collect());
+        dataset.insert("z".to_string(), z.iter().map(|v| Some(*v)).collect());
+        dataset
+    }
+
+    #[test]
+    fn nearly_identical_columns_are_reported_as_a_pair() {
+        let screen = collinearity_screen(&dataset(), 0.9, false).unwrap();
+
+        assert_eq!(screen.pairs.rows.len(), 1);
+        assert_eq!(screen.pairs.rows[0][0], Value::from("x"));
+        assert_eq!(screen.pairs.rows[0][1], Value::from("x_copy"));
+        assert!(screen.pairs.rows[0][2].as_f64().unwrap() > 0.999);
+        assert_eq!(screen.pairs.rows[0][3], Value::from(8));
+        assert!(screen.vif.is_none());
+    }
+
+    #[test]
+    fn vif_flags_the_redundant_columns_only() {
+        let screen = collinearity_screen(&dataset(), 0.9, true).unwrap();
+        let vif = screen.vif.unwrap();
+
+        let flags: Vec<&Value> = vif.rows.iter().map(|row| &row[2]).collect();
+        assert_eq!(flags,
+                   vec![&Value::Bool(true), &Value::Bool(true), &Value::Bool(false)]);
+        // With two columns, VIF = 1 / (1 - r^2) for both.
+        let mut two = dataset();
+        two.shift_remove("x_copy");
+        let pairs: Vec<(f64, f64)> = two["x"].iter()
+                                             .zip(&two["z"])
+                                             .map(|(x, z)| (x.unwrap(), z.unwrap()))
+                                             .collect();
+        let r = pearson(&pairs).unwrap();
+        let vif = collinearity_screen(&two, 0.9, true).unwrap().vif.unwrap();
+        let x_vif = vif.rows[0][1].as_f64().unwrap();
+        assert!((x_vif - 1.0 / (1.0 - r * r)).abs() < 1e-12);
+        assert_eq!(vif.rows[0][1], vif.rows[1][1]);
+    }
+
+    #[test]
+    fn threshold_outside_the_unit_interval_is_rejected() {
+        assert!(collinearity_screen(&dataset(), 1.0, false).is_err());
+        assert!(collinearity_screen(&dataset(), -0.1, false).is_err());
+    }
+
+    fn names(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|column| column.to_string()).collect()
+    }
+
+    #[test]
+    fn standalone_vif_matches_the_screen() {
+        let table = vif_for_columns(&dataset(), &names(&["x", "x_copy", "z"])).unwrap();
+        let screened = collinearity_screen(&dataset(), 0.9, true).unwrap().vif.unwrap();
+
+        assert_eq!(table.rows, screened.rows);
+        assert_eq!(table.note, screened.note);
+    }
+
+    #[test]
+    fn undefined_vifs_are_null_with_a_note() {
+        let mut data = dataset();
+        let doubled: Vec<Option<f64>> = data["x"].iter().map(|v| v.map(|v| 2.0 * v + 1.0)).collect();
+        data.insert("x_doubled".to_string(), doubled);
+        data.insert("flat".to_string(), vec![Some(1.0); 8]);
+
+        let collinear = vif_for_columns(&data, &names(&["x", "x_doubled", "z"])).unwrap();
+        assert!(collinear.rows.iter().all(|row| row[1].is_null()));
+        assert!(collinear.note.unwrap().contains("完全に共線な変数"));
+
+        let constant = vif_for_columns(&data, &names(&["x", "flat"])).unwrap();
+        assert!(constant.rows.iter().all(|row| row[1].is_null()));
+        assert!(constant.note.unwrap().contains("値が一定の変数"));
+    }
+
+    #[test]
+    fn standalone_vif_rejects_unknown_and_repeated_columns() {
+        let data = dataset();
+
+        assert!(vif_for_columns(&data, &names(&["x", "missing"])).is_err());
+        assert!(vif_for_columns(&data, &names(&["x", "x"])).is_err());
+        assert!(vif_for_columns(&data, &names(&["x"])).is_err());
+    }
+}
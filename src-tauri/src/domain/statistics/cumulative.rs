@@ -0,0 +1,132 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CumulativeStat {
+    Sum,
+    Mean,
+    Max,
+    Min,
+}
+
+impl CumulativeStat {
+    fn label(self) -> &'static str {
+        match self {
+            CumulativeStat::Sum => "累積和",
+            CumulativeStat::Mean => "累積平均",
+            CumulativeStat::Max => "累積最大値",
+            CumulativeStat::Min => "累積最小値",
+        }
+    }
+}
+
+/// Running sum, mean, maximum or minimum of `column`, one output row per input row.
+/// A missing cell is skipped and the row repeats the last running value, or, with
+/// `reset_on_missing`, the row is null and the accumulation starts again after it.
+/// Rows before the first value are null in both cases.
+pub(crate) fn cumulative_stats(dataset: &NumericDataset,
+                               column: &str,
+                               stat: CumulativeStat,
+                               reset_on_missing: bool)
+                               -> Result<ParsedDataTable, String> {
+    let values = dataset.get(column)
+                        .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+
+    let mut count = 0usize;
+    let mut running: Option<f64> = None;
+    let mut missing = 0usize;
+    let rows = values.iter()
+                     .enumerate()
+                     .map(|(i, value)| {
+                         let output = match value {
+                             Some(value) => {
+                                 count += 1;
+                                 running = Some(match (stat, running) {
+                                                    (_, None) => *value,
+                                                    (CumulativeStat::Sum, Some(acc)) => acc + value,
+                                                    // Incremental mean: m_k = m_{k-1} + (x_k - m_{k-1}) / k
+                                                    (CumulativeStat::Mean, Some(acc)) => {
+                                                        acc + (value - acc) / count as f64
+                                                    },
+                                                    (CumulativeStat::Max, Some(acc)) => acc.max(*value),
+                                                    (CumulativeStat::Min, Some(acc)) => acc.min(*value),
+                                                });
+                                 running
+                             },
+                             None => {
+                                 missing += 1;
+                                 if reset_on_missing {
+                                     count = 0;
+                                     running = None;
+                                 }
+                                 running
+                             },
+                         };
+                         vec![Value::from(i + 1), number_cell(*value), number_cell(output)]
+                     })
+                     .collect();
+
+    let note = (missing > 0).then(|| {
+                                if reset_on_missing {
+                                    format!("欠損値 {} 個の位置で集計をリセットしました", missing)
+                                } else {
+                                    format!("欠損値 {} 個を飛ばし、直前の値を引き継ぎました", missing)
+                                }
+                            });
+    Ok(ParsedDataTable { headers: vec!["行".to_string(), column.to_string(), stat.label().to_string()],
+                         rows,
+                         note,
+                         title: Some(format!("{} ({})", stat.label(), column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        CumulativeStat,
+        cumulative_stats,
+    };
+
+    fn running(values: &[Option<f64>],
+               stat: CumulativeStat,
+               reset_on_missing: bool)
+               -> Vec<Value> {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), values.to_vec());
+        cumulative_stats(&dataset, "x", stat, reset_on_missing).unwrap()
+                                                               .rows
+                                                               .into_iter()
+                                                               .map(|row| row[2].clone())
+                                                               .collect()
+    }
+
+    #[test]
+    fn cumulative_sum_of_one_two_three() {
+        assert_eq!(running(&[Some(1.0), Some(2.0), Some(3.0)], CumulativeStat::Sum, false),
+                   vec![Value::from(1.0), Value::from(3.0), Value::from(6.0)]);
+        assert_eq!(running(&[Some(1.0), Some(2.0), Some(3.0)], CumulativeStat::Mean, false),
+                   vec![Value::from(1.0), Value::from(1.5), Value::from(2.0)]);
+    }
+
+    #[test]
+    fn missing_cell_is_carried_over_or_resets_the_total() {
+        let values = [Some(1.0), None, Some(3.0)];
+
+        assert_eq!(running(&values, CumulativeStat::Sum, false),
+                   vec![Value::from(1.0), Value::from(1.0), Value::from(4.0)]);
+        assert_eq!(running(&values, CumulativeStat::Sum, true),
+                   vec![Value::from(1.0), Value::Null, Value::from(3.0)]);
+        assert_eq!(running(&[None, Some(2.0)], CumulativeStat::Max, false),
+                   vec![Value::Null, Value::from(2.0)]);
+    }
+}
@@ -0,0 +1,149 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::domain::input::string_mixed::StringMixedDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+const MISSING_LEVEL: &str = "(欠損)";
+
+/// Counts each level in first-seen order. Missing cells are counted as `MISSING_LEVEL`
+/// when `include_missing` is set, and skipped otherwise.
+pub(crate) fn level_counts(values: &[Option<String>],
+                           include_missing: bool)
+                           -> IndexMap<String, usize> {
+    let mut counts = IndexMap::new();
+    for value in values {
+        let level = match value {
+            Some(value) => value.as_str(),
+            None if include_missing => MISSING_LEVEL,
+            None => continue,
+        };
+        *counts.entry(level.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Gini impurity `1 - Σ p²` of a level distribution. Returns `None` when there are no observations.
+pub(crate) fn gini_impurity(counts: &IndexMap<String, usize>) -> Option<f64> {
+    let total = total(counts)?;
+    Some(1.0
+         - counts.values()
+                 .map(|&count| (count as f64 / total).powi(2))
+                 .sum::<f64>())
+}
+
+/// Shannon entropy `-Σ p log2 p` in bits. Returns `None` when there are no observations.
+pub(crate) fn shannon_entropy(counts: &IndexMap<String, usize>) -> Option<f64> {
+    let total = total(counts)?;
+    Some(-counts.values()
+                .filter(|&&count| count > 0)
+                .map(|&count| {
+                    let p = count as f64 / total;
+                    p * p.log2()
+                })
+                .sum::<f64>())
+}
+
+/// Builds a per-column diversity profile (levels, mode, Gini impurity, entropy) without R.
+/// Ties for the mode are resolved by the level that appears first in the column.
+pub(crate) fn categorical_diversity(dataset: &StringMixedDataset,
+                                    include_missing: bool)
+                                    -> ParsedDataTable {
+    let rows = dataset.iter()
+                      .map(|(column, values)| {
+                          let counts = level_counts(values, include_missing);
+                          let n: usize = counts.values().sum();
+                          let mode = counts.iter()
+                                           .rev()
+                                           .max_by_key(|(_, count)| **count)
+                                           .map(|(level, _)| Value::String(level.clone()))
+                                           .unwrap_or(Value::Null);
+                          vec![Value::String(column.clone()),
+                               Value::from(n),
+                               Value::from(counts.len()),
+                               mode,
+                               number_cell(gini_impurity(&counts)),
+                               number_cell(shannon_entropy(&counts))]
+                      })
+                      .collect();
+
+    let note = if include_missing {
+        format!("欠損値は水準「{}」として集計しています。エントロピーの単位はビット (log2) です",
+                MISSING_LEVEL)
+    } else {
+        "欠損値を除外して集計しています。エントロピーの単位はビット (log2) です".to_string()
+    };
+
+    ParsedDataTable { headers: vec!["変数".to_string(),
+                                    "n".to_string(),
+                                    "水準数".to_string(),
+                                    "最頻値".to_string(),
+                                    "ジニ不純度".to_string(),
+                                    "エントロピー".to_string()],
+                      rows,
+                      note: Some(note),
+                      title: Some("カテゴリ変数の多様性".to_string()),
+                      column_types: Vec::new() }
+}
+
+fn total(counts: &IndexMap<String, usize>) -> Option<f64> {
+    let total: usize = counts.values().sum();
+    if total == 0 { None } else { Some(total as f64) }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        categorical_diversity,
+        gini_impurity,
+        level_counts,
+        shannon_entropy,
+    };
+
+    fn column(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|value| value.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn entropy_and_gini_match_known_proportions() {
+        // p = (1/2, 1/4, 1/4): H = 1.5 bits, Gini = 1 - (1/4 + 1/16 + 1/16) = 0.625
+        let values = column(&[Some("a"), Some("a"), Some("b"), Some("c")]);
+        let counts = level_counts(&values, false);
+
+        assert!((shannon_entropy(&counts).unwrap() - 1.5).abs() < 1e-12);
+        assert!((gini_impurity(&counts).unwrap() - 0.625).abs() < 1e-12);
+    }
+
+    #[test]
+    fn missing_values_follow_include_flag() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("group".to_string(), column(&[Some("x"), None, None, Some("y")]));
+
+        let excluded = categorical_diversity(&dataset, false);
+        assert_eq!(excluded.rows[0][1], Value::from(2));
+        assert_eq!(excluded.rows[0][2], Value::from(2));
+        assert_eq!(excluded.rows[0][3], Value::String("x".to_string()));
+
+        let included = categorical_diversity(&dataset, true);
+        assert_eq!(included.rows[0][1], Value::from(4));
+        assert_eq!(included.rows[0][2], Value::from(3));
+        assert_eq!(included.rows[0][3], Value::String("(欠損)".to_string()));
+    }
+
+    #[test]
+    fn all_missing_column_reports_nulls() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("empty".to_string(), column(&[None, None]));
+
+        let table = categorical_diversity(&dataset, false);
+        assert_eq!(table.rows[0][3], Value::Null);
+        assert_eq!(table.rows[0][4], Value::Null);
+        assert_eq!(table.rows[0][5], Value::Null);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::observed_values;
+
+/// Empirical CDF of one numeric column: one row per distinct value, in ascending order, with the
+/// share of observations less than or equal to it. Tied values collapse into a single row carrying
+/// the probability at their last occurrence, so the final row is exactly 1. Missing values are
+/// excluded.
+pub(crate) fn ecdf(dataset: &NumericDataset,
+                   column: &str)
+                   -> Result<ParsedDataTable, String> {
+    let values = dataset.get(column)
+                        .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+    let mut observed = observed_values(values);
+    if observed.is_empty() {
+        return Err(format!("column '{}' has no observed values", column));
+    }
+    observed.sort_by(f64::total_cmp);
+
+    let n = observed.len();
+    let mut rows = Vec::new();
+    for (index, &value) in observed.iter().enumerate() {
+        if observed.get(index + 1) == Some(&value) {
+            continue;
+        }
+        rows.push(vec![number_cell(Some(value)),
+                       number_cell(Some((index + 1) as f64 / n as f64))]);
+    }
+
+    let mut note = format!("有効なデータ数 n = {}", n);
+    let missing = values.len() - n;
+    if missing > 0 {
+        note.push_str(&format!(" / 欠損値 {} 件を除外しました", missing));
+    }
+
+    Ok(ParsedDataTable { headers: vec!["値".to_string(), "累積確率".to_string()],
+                         rows,
+                         note: Some(note),
+                         title: Some(format!("経験累積分布 ({})", column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::ecdf;
+
+    fn dataset(values: &[Option<f64>]) -> IndexMap<String, Vec<Option<f64>>> {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), values.to_vec());
+        dataset
+    }
+
+    #[test]
+    fn ties_collapse_and_the_maximum_reaches_exactly_one() {
+        let data = dataset(&[Some(0.3),
+                             Some(0.1),
+                             None,
+                             Some(0.3),
+                             Some(0.7),
+                             Some(0.1),
+                             Some(0.2)]);
+
+        let table = ecdf(&data, "x").unwrap();
+
+        let values: Vec<f64> = table.rows.iter().map(|row| row[0].as_f64().unwrap()).collect();
+        let probabilities: Vec<f64> = table.rows.iter().map(|row| row[1].as_f64().unwrap()).collect();
+        assert_eq!(values, vec![0.1, 0.2, 0.3, 0.7]);
+        assert_eq!(probabilities[0], 2.0 / 6.0);
+        assert_eq!(probabilities[2], 5.0 / 6.0);
+        assert_eq!(*probabilities.last().unwrap(), 1.0);
+        assert!(probabilities.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(table.note.unwrap().contains("n = 6"));
+    }
+
+    #[test]
+    fn an_all_missing_column_is_rejected() {
+        let data = dataset(&[None, None]);
+
+        assert!(ecdf(&data, "x").is_err());
+        assert!(ecdf(&data, "y").is_err());
+        assert_eq!(ecdf(&dataset(&[Some(5.0)]), "x").unwrap().rows,
+                   vec![vec![Value::from(5.0), Value::from(1.0)]]);
+    }
+}
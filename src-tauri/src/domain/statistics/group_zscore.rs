@@ -0,0 +1,127 @@
+use serde_json::Value;
+
+use crate::domain::input::string_mixed::{
+    StringMixedDataset,
+    parse_numeric_cells,
+};
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::{
+    mean,
+    sample_sd,
+};
+
+/// Standardizes `column` with the mean and SD of the rows whose `group` equals `reference_level`
+/// (e.g. a control group). Rows keep their original order; missing values stay missing.
+pub(crate) fn group_zscore(dataset: &StringMixedDataset,
+                           column: &str,
+                           group: &str,
+                           reference_level: &str)
+                           -> Result<ParsedDataTable, String> {
+    let (col_index, _, raw_values) =
+        dataset.get_full(column)
+               .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+    let groups = dataset.get(group)
+                        .ok_or_else(|| format!("group variable '{}' is not in the dataset", group))?;
+
+    let values = parse_numeric_cells(raw_values, col_index, column)?;
+
+    let reference: Vec<f64> = values.iter()
+                                    .zip(groups)
+                                    .filter(|(_, level)| level.as_deref() == Some(reference_level))
+                                    .filter_map(|(value, _)| *value)
+                                    .collect();
+    if reference.is_empty() {
+        return Err(format!("reference level '{}' has no observed values in group '{}'",
+                           reference_level, group));
+    }
+    let reference_mean = mean(&reference).expect("reference is not empty");
+    let reference_sd = sample_sd(&reference).filter(|sd| *sd > 0.0).ok_or_else(|| {
+                           format!("reference level '{}' needs at least 2 distinct values (SD > 0)",
+                                   reference_level)
+                       })?;
+
+    let rows = values.iter()
+                     .zip(groups)
+                     .map(|(value, level)| {
+                         vec![level.clone().map(Value::String).unwrap_or(Value::Null),
+                              number_cell(*value),
+                              number_cell(value.map(|v| (v - reference_mean) / reference_sd))]
+                     })
+                     .collect();
+
+    Ok(ParsedDataTable { headers: vec![group.to_string(), column.to_string(), format!("{} (z)", column)],
+                         rows,
+                         note: Some(format!("基準群 {} = {} (n = {}) の平均と標準偏差で標準化しました",
+                                            group,
+                                            reference_level,
+                                            reference.len())),
+                         title: Some(format!("基準群による z 得点 ({})", column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use crate::domain::input::string_mixed::StringMixedDataset;
+
+    use super::group_zscore;
+
+    fn dataset(values: &[Option<&str>],
+               groups: &[Option<&str>])
+               -> StringMixedDataset {
+        let mut dataset = IndexMap::new();
+        dataset.insert("score".to_string(),
+                       values.iter().map(|v| v.map(str::to_string)).collect());
+        dataset.insert("group".to_string(),
+                       groups.iter().map(|v| v.map(str::to_string)).collect());
+        dataset
+    }
+
+    #[test]
+    fn reference_group_standardizes_to_zero_mean() {
+        let data = dataset(&[Some("10"), Some("20"), Some("12"), Some("30"), Some("14"), None],
+                           &[Some("ctrl"),
+                             Some("trt"),
+                             Some("ctrl"),
+                             Some("trt"),
+                             Some("ctrl"),
+                             Some("trt")]);
+
+        let table = group_zscore(&data, "score", "group", "ctrl").unwrap();
+
+        assert_eq!(table.rows.len(), 6);
+        assert_eq!(table.rows[1][1], Value::from(20.0));
+        let reference_z: Vec<f64> = table.rows
+                                         .iter()
+                                         .filter(|row| row[0] == "ctrl")
+                                         .map(|row| row[2].as_f64().unwrap())
+                                         .collect();
+        let mean_z = reference_z.iter().sum::<f64>() / reference_z.len() as f64;
+        assert!(mean_z.abs() < 1e-12);
+        assert!((reference_z[0] + 1.0).abs() < 1e-12);
+        assert_eq!(table.rows[5][2], Value::Null);
+    }
+
+    #[test]
+    fn missing_reference_level_is_an_error() {
+        let data = dataset(&[Some("1"), Some("2")], &[Some("a"), Some("b")]);
+
+        let err = group_zscore(&data, "score", "group", "ctrl").unwrap_err();
+        assert!(err.contains("ctrl"));
+    }
+
+    #[test]
+    fn constant_reference_group_is_an_error() {
+        let data = dataset(&[Some("5"), Some("5"), Some("9")],
+                           &[Some("ctrl"), Some("ctrl"), Some("trt")]);
+
+        let err = group_zscore(&data, "score", "group", "ctrl").unwrap_err();
+        assert!(err.contains("SD > 0"));
+    }
+}
@@ -0,0 +1,234 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::observed_values;
+
+/// Most bins a histogram may have, so that a huge count or a tiny bin width is rejected instead
+/// of allocating a bin vector that does not fit in memory.
+pub(crate) const MAX_BINS: usize = 10_000;
+
+/// How the bin edges are chosen: a fixed number of bins or a fixed bin width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BinSpec {
+    Count(usize),
+    Width(f64),
+}
+
+impl BinSpec {
+    /// Number of bins and bin width covering `[min, max]`. With a fixed width the last bin may be
+    /// narrower, since its upper edge is clamped to `max`.
+    pub(crate) fn layout(self,
+                         min: f64,
+                         max: f64)
+                         -> Result<(usize, f64), String> {
+        match self {
+            BinSpec::Count(0) => Err("bins must be at least 1".to_string()),
+            BinSpec::Count(count) if count > MAX_BINS => {
+                Err(format!("bins must be at most {}: {}", MAX_BINS, count))
+            },
+            BinSpec::Count(count) => Ok((count, (max - min) / count as f64)),
+            BinSpec::Width(width) if width.is_finite() && width > 0.0 => {
+                let count = ((max - min) / width).ceil().max(1.0);
+                if count > MAX_BINS as f64 {
+                    return Err(format!("binwidth {} gives more than {} bins over [{}, {}]",
+                                       width, MAX_BINS, min, max));
+                }
+                Ok((count as usize, width))
+            },
+            BinSpec::Width(width) => Err(format!("binwidth must be positive: {}", width)),
+        }
+    }
+}
+
+/// Histogram request for one numeric column.
+/// `range` defaults to the observed minimum and maximum; values outside it are excluded.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HistogramSpec {
+    pub bins: BinSpec,
+    pub range: Option<(f64, f64)>,
+    pub density: bool,
+}
+
+/// Bins are left-closed `[lower, upper)` except the last one, which is closed `[lower, upper]`
+/// so that the range maximum is counted (the same convention as `numpy.histogram`).
+/// The last upper edge is the range maximum. Missing values are excluded.
+pub(crate) fn histogram(dataset: &NumericDataset,
+                        column: &str,
+                        spec: HistogramSpec)
+                        -> Result<ParsedDataTable, String> {
+    let values = dataset.get(column)
+                        .ok_or_else(|| format!("column '{}' is not in the dataset", column))?;
+    let observed = observed_values(values);
+
+    let (min, max) = match spec.range {
+        Some((min, max)) => {
+            if !(min.is_finite() && max.is_finite() && min < max) {
+                return Err(format!("range min must be less than max: [{}, {}]", min, max));
+            }
+            (min, max)
+        },
+        None => {
+            if observed.is_empty() {
+                return Err(format!("column '{}' has no observed values", column));
+            }
+            let min = observed.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = observed.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if min == max {
+                return Err(format!("column '{}' is constant; specify a range to build bins", column));
+            }
+            (min, max)
+        },
+    };
+
+    let (bin_count, width) = spec.bins.layout(min, max)?;
+
+    let mut counts = vec![0usize; bin_count];
+    let mut excluded = 0;
+    for &value in &observed {
+        if value < min || value > max {
+            excluded += 1;
+            continue;
+        }
+        let index = (((value - min) / width).floor() as usize).min(bin_count - 1);
+        counts[index] += 1;
+    }
+    let counted: usize = counts.iter().sum();
+
+    let rows = counts.iter()
+                     .enumerate()
+                     .map(|(index, &count)| {
+                         let lower = min + width * index as f64;
+                         let upper = if index + 1 == bin_count {
+                             max
+                         } else {
+                             lower + width
+                         };
+                         let mut row = vec![number_cell(Some(lower)),
+                                            number_cell(Some(upper)),
+                                            Value::from(count)];
+                         if spec.density {
+                             let density =
+                                 (counted > 0).then(|| count as f64 / (counted as f64 * (upper - lower)));
+                             row.push(number_cell(density));
+                         }
+                         row
+                     })
+                     .collect();
+
+    let mut headers = vec!["下限".to_string(), "上限".to_string(), "度数".to_string()];
+    if spec.density {
+        headers.push("密度".to_string());
+    }
+    let mut note = "各階級は下限を含み上限を含みません (最後の階級のみ上限を含みます)".to_string();
+    if excluded > 0 {
+        note.push_str(&format!(" / 範囲外の {} 件を除外しました", excluded));
+    }
+
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: Some(note),
+                         title: Some(format!("ヒストグラム ({})", column)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        BinSpec,
+        HistogramSpec,
+        MAX_BINS,
+        histogram,
+    };
+
+    fn dataset(values: &[Option<f64>]) -> IndexMap<String, Vec<Option<f64>>> {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), values.to_vec());
+        dataset
+    }
+
+    fn counts(table: &crate::domain::input::table::ParsedDataTable) -> Vec<Value> {
+        table.rows.iter().map(|row| row[2].clone()).collect()
+    }
+
+    #[test]
+    fn fixed_bin_count_uses_left_closed_bins_with_closed_last_bin() {
+        let data = dataset(&[Some(0.0), Some(1.0), Some(2.0), Some(3.0), Some(4.0), None]);
+        let spec = HistogramSpec { bins: BinSpec::Count(2),
+                                   range: None,
+                                   density: true };
+
+        let table = histogram(&data, "x", spec).unwrap();
+
+        assert_eq!(counts(&table), vec![Value::from(2), Value::from(3)]);
+        assert_eq!(table.rows[0][1], Value::from(2.0));
+        // density = count / (n * width) = 2 / (5 * 2)
+        assert_eq!(table.rows[0][3], Value::from(0.2));
+    }
+
+    #[test]
+    fn binwidth_and_range_exclude_values_outside() {
+        let data = dataset(&[Some(-1.0), Some(0.5), Some(1.5), Some(9.0)]);
+        let spec = HistogramSpec { bins: BinSpec::Width(1.0),
+                                   range: Some((0.0, 2.0)),
+                                   density: false };
+
+        let table = histogram(&data, "x", spec).unwrap();
+
+        assert_eq!(counts(&table), vec![Value::from(1), Value::from(1)]);
+        assert!(table.note.unwrap().contains("2 件"));
+    }
+
+    #[test]
+    fn binwidth_bins_stop_at_the_range_maximum() {
+        let data = dataset(&[Some(0.5), Some(2.4), Some(2.5), Some(2.6)]);
+        let spec = HistogramSpec { bins: BinSpec::Width(1.0),
+                                   range: Some((0.0, 2.5)),
+                                   density: true };
+
+        let table = histogram(&data, "x", spec).unwrap();
+
+        assert_eq!(counts(&table),
+                   vec![Value::from(1), Value::from(0), Value::from(2)]);
+        assert_eq!(table.rows[2][1], Value::from(2.5));
+        // The last bin is [2, 2.5]: density = 2 / (3 * 0.5)
+        assert!((table.rows[2][3].as_f64().unwrap() - 4.0 / 3.0).abs() < 1e-12);
+        assert!(table.note.unwrap().contains("1 件"));
+    }
+
+    #[test]
+    fn bin_counts_above_the_cap_are_rejected() {
+        let data = dataset(&[Some(0.0), Some(1.0)]);
+        let tiny_width = HistogramSpec { bins: BinSpec::Width(1e-12),
+                                         range: None,
+                                         density: false };
+        let too_many = HistogramSpec { bins: BinSpec::Count(MAX_BINS + 1),
+                                       range: None,
+                                       density: false };
+
+        let err = histogram(&data, "x", tiny_width).unwrap_err();
+        assert!(err.contains("more than 10000 bins"), "{}", err);
+        assert!(histogram(&data, "x", too_many).is_err());
+    }
+
+    #[test]
+    fn invalid_bins_and_range_are_rejected() {
+        let data = dataset(&[Some(1.0), Some(2.0)]);
+        let zero_bins = HistogramSpec { bins: BinSpec::Count(0),
+                                        range: None,
+                                        density: false };
+        let reversed = HistogramSpec { bins: BinSpec::Count(3),
+                                       range: Some((2.0, 1.0)),
+                                       density: false };
+
+        assert!(histogram(&data, "x", zero_bins).is_err());
+        assert!(histogram(&data, "x", reversed).is_err());
+    }
+}
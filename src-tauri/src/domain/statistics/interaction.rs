@@ -0,0 +1,160 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::domain::input::string_mixed::{
+    StringMixedDataset,
+    parse_numeric_cells,
+};
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+
+use super::sample::{
+    mean,
+    sample_sd,
+};
+
+/// Mean and standard error of `response` in every combination of the levels of `factor_a` and
+/// `factor_b`: the points of an interaction plot. The table is long, one row per combination,
+/// with levels in order of first appearance and `factor_a` varying slowest.
+///
+/// Rows with a missing factor value are left out, and missing responses are skipped. A
+/// combination without any response is still listed, with n = 0 and a null mean; the SE needs at
+/// least two responses.
+pub(crate) fn interaction_means(dataset: &StringMixedDataset,
+                                response: &str,
+                                factor_a: &str,
+                                factor_b: &str)
+                                -> Result<ParsedDataTable, String> {
+    if factor_a == factor_b {
+        return Err("the two factors must be different columns".to_string());
+    }
+    if response == factor_a || response == factor_b {
+        return Err("the response must not be one of the factors".to_string());
+    }
+    let (col_index, _, raw_values) =
+        dataset.get_full(response)
+               .ok_or_else(|| format!("response '{}' is not in the dataset", response))?;
+    let a_values = dataset.get(factor_a)
+                          .ok_or_else(|| format!("factor '{}' is not in the dataset", factor_a))?;
+    let b_values = dataset.get(factor_b)
+                          .ok_or_else(|| format!("factor '{}' is not in the dataset", factor_b))?;
+
+    let values = parse_numeric_cells(raw_values, col_index, response)?;
+
+    let mut a_levels: IndexMap<&str, ()> = IndexMap::new();
+    let mut b_levels: IndexMap<&str, ()> = IndexMap::new();
+    let mut cells: IndexMap<(&str, &str), Vec<f64>> = IndexMap::new();
+    let mut missing_factor = 0usize;
+    for (row_index, value) in values.into_iter().enumerate() {
+        let (Some(Some(a)), Some(Some(b))) = (a_values.get(row_index), b_values.get(row_index)) else {
+            missing_factor += 1;
+            continue;
+        };
+        a_levels.insert(a.as_str(), ());
+        b_levels.insert(b.as_str(), ());
+        cells.entry((a.as_str(), b.as_str())).or_default().extend(value);
+    }
+    if cells.values().all(Vec::is_empty) {
+        return Err(format!("response '{}' has no observed values in any factor combination",
+                           response));
+    }
+
+    let mut empty_cells = 0usize;
+    let mut rows = Vec::with_capacity(a_levels.len() * b_levels.len());
+    for a in a_levels.keys() {
+        for b in b_levels.keys() {
+            let values = cells.get(&(*a, *b)).map_or(&[][..], Vec::as_slice);
+            if values.is_empty() {
+                empty_cells += 1;
+            }
+            let se = sample_sd(values).map(|sd| sd / (values.len() as f64).sqrt());
+            rows.push(vec![Value::from(*a),
+                           Value::from(*b),
+                           Value::from(values.len()),
+                           number_cell(mean(values)),
+                           number_cell(se)]);
+        }
+    }
+
+    let empty_note =
+        (empty_cells > 0).then(|| format!("観測値のない組み合わせ {} 個は平均を空欄にしました", empty_cells));
+    let missing_note =
+        (missing_factor > 0).then(|| format!("因子に欠損がある {} 行を除外しました", missing_factor));
+    Ok(ParsedDataTable { headers: vec![factor_a.to_string(),
+                                       factor_b.to_string(),
+                                       "n".to_string(),
+                                       "平均".to_string(),
+                                       "標準誤差".to_string()],
+                         rows,
+                         note: join_notes([Some("標準誤差 = 標準偏差 / √n".to_string()),
+                                           empty_note,
+                                           missing_note]),
+                         title: Some(format!("{}のセル平均 ({} × {})", response, factor_a, factor_b)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::interaction_means;
+    use crate::domain::input::string_mixed::StringMixedDataset;
+
+    fn dataset(rows: &[(&str, &str, Option<&str>)]) -> StringMixedDataset {
+        let mut dataset = IndexMap::new();
+        dataset.insert("drug".to_string(),
+                       rows.iter().map(|(a, _, _)| Some(a.to_string())).collect());
+        dataset.insert("dose".to_string(),
+                       rows.iter().map(|(_, b, _)| Some(b.to_string())).collect());
+        dataset.insert("score".to_string(),
+                       rows.iter().map(|(_, _, y)| y.map(str::to_string)).collect());
+        dataset
+    }
+
+    #[test]
+    fn two_by_two_design_gives_four_cell_means() {
+        let data = dataset(&[("A", "low", Some("10")),
+                             ("A", "high", Some("20")),
+                             ("B", "low", Some("12")),
+                             ("B", "high", Some("30")),
+                             ("A", "low", Some("14")),
+                             ("A", "high", Some("22")),
+                             ("B", "low", Some("16")),
+                             ("B", "high", None)]);
+
+        let table = interaction_means(&data, "score", "drug", "dose").unwrap();
+
+        let cells: Vec<(&Value, &Value, &Value, &Value)> =
+            table.rows
+                 .iter()
+                 .map(|row| (&row[0], &row[1], &row[2], &row[3]))
+                 .collect();
+        assert_eq!(cells,
+                   vec![(&Value::from("A"), &Value::from("low"), &Value::from(2), &Value::from(12.0)),
+                        (&Value::from("A"), &Value::from("high"), &Value::from(2), &Value::from(21.0)),
+                        (&Value::from("B"), &Value::from("low"), &Value::from(2), &Value::from(14.0)),
+                        (&Value::from("B"), &Value::from("high"), &Value::from(1), &Value::from(30.0))]);
+        // SD of 10 and 14 is 2√2, so SE = 2.
+        assert!((table.rows[0][4].as_f64().unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(table.rows[3][4], Value::Null);
+    }
+
+    #[test]
+    fn empty_combination_is_kept_with_a_null_mean() {
+        let data = dataset(&[("A", "low", Some("1")),
+                             ("A", "high", Some("2")),
+                             ("B", "low", Some("3"))]);
+
+        let table = interaction_means(&data, "score", "drug", "dose").unwrap();
+
+        assert_eq!(table.rows.len(), 4);
+        assert_eq!(table.rows[3][..4],
+                   [Value::from("B"), Value::from("high"), Value::from(0), Value::Null]);
+        assert!(table.note.unwrap().contains("1 個"));
+        assert!(interaction_means(&data, "score", "drug", "drug").is_err());
+    }
+}
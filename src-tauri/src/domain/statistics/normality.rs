@@ -0,0 +1,123 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::{
+    is_constant,
+    mean,
+    observed_values,
+};
+
+const NORMALITY_THRESHOLD: f64 = 1.0;
+
+/// Sample skewness with the small-sample adjustment (type 2, as in `e1071::skewness(type = 2)`).
+/// Returns `None` for fewer than 3 observations or a constant column.
+pub(crate) fn skewness(values: &[f64]) -> Option<f64> {
+    if values.len() < 3 || is_constant(values) {
+        return None;
+    }
+    let n = values.len() as f64;
+    let m2 = central_moment(values, 2)?;
+    let m3 = central_moment(values, 3)?;
+    let g1 = m3 / m2.powf(1.5);
+    Some(g1 * (n * (n - 1.0)).sqrt() / (n - 2.0))
+}
+
+/// Sample excess kurtosis with the small-sample adjustment (type 2, as in `e1071::kurtosis(type = 2)`).
+/// Returns `None` for fewer than 4 observations or a constant column.
+pub(crate) fn excess_kurtosis(values: &[f64]) -> Option<f64> {
+    if values.len() < 4 || is_constant(values) {
+        return None;
+    }
+    let n = values.len() as f64;
+    let m2 = central_moment(values, 2)?;
+    let m4 = central_moment(values, 4)?;
+    let g2 = m4 / (m2 * m2) - 3.0;
+    Some(((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+}
+
+/// Builds a per-column normality preview without R.
+/// A column is flagged as approximately normal when both |skewness| and |excess kurtosis| are below 1.
+/// Missing values are excluded; columns whose moments are undefined report null.
+pub(crate) fn quick_normality(dataset: &NumericDataset) -> ParsedDataTable {
+    let rows = dataset.iter()
+                      .map(|(column, values)| {
+                          let observed = observed_values(values);
+                          let skew = skewness(&observed);
+                          let kurt = excess_kurtosis(&observed);
+                          let flag = match (skew, kurt) {
+                              (Some(skew), Some(kurt)) => Value::Bool(skew.abs() < NORMALITY_THRESHOLD
+                                                                      && kurt.abs() < NORMALITY_THRESHOLD),
+                              _ => Value::Null,
+                          };
+                          vec![Value::String(column.clone()),
+                               Value::from(observed.len()),
+                               number_cell(skew),
+                               number_cell(kurt),
+                               flag]
+                      })
+                      .collect();
+
+    ParsedDataTable { headers: vec!["変数".to_string(),
+                                    "n".to_string(),
+                                    "歪度".to_string(),
+                                    "尖度".to_string(),
+                                    "近似的に正規".to_string()],
+                      rows,
+                      note: Some("歪度・尖度の絶対値がともに1未満の列を近似的に正規とみなす簡易判定です".to_string()),
+                      title: Some("簡易正規性チェック".to_string()),
+                      column_types: Vec::new() }
+}
+
+fn central_moment(values: &[f64],
+                  power: i32)
+                  -> Option<f64> {
+    let mean = mean(values)?;
+    let sum: f64 = values.iter().map(|value| (value - mean).powi(power)).sum();
+    Some(sum / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        excess_kurtosis,
+        quick_normality,
+        skewness,
+    };
+
+    #[test]
+    fn skewness_and_kurtosis_match_hand_computed_values() {
+        // mean = 4, m2 = 10, m3 = 36, m4 = 278.8
+        let values = [1.0, 2.0, 3.0, 4.0, 10.0];
+
+        let skew = skewness(&values).expect("skewness is defined");
+        let kurt = excess_kurtosis(&values).expect("kurtosis is defined");
+
+        assert!((skew - 1.2 * 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((kurt - 3.152).abs() < 1e-12);
+    }
+
+    #[test]
+    fn quick_normality_excludes_missing_and_reports_null_for_constant_columns() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("skewed".to_string(),
+                       vec![Some(1.0), Some(2.0), None, Some(3.0), Some(4.0), Some(10.0)]);
+        dataset.insert("constant".to_string(), vec![Some(0.1); 5]);
+
+        let table = quick_normality(&dataset);
+        table.validate().expect("table is valid");
+
+        assert_eq!(table.rows[0][1], Value::from(5));
+        assert_eq!(table.rows[0][4], Value::Bool(false));
+        assert_eq!(table.rows[1][2], Value::Null);
+        assert_eq!(table.rows[1][3], Value::Null);
+        assert_eq!(table.rows[1][4], Value::Null);
+    }
+}
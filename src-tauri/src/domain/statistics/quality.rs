@@ -0,0 +1,460 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::numeric::{
+    NumericCellContext,
+    parse_numeric_string,
+};
+use crate::domain::input::table::{
+    ColumnType,
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+use crate::domain::statistics::sample::{
+    mean,
+    sample_sd,
+};
+
+/// Per-column quality scores and the sheet score (mean of the column scores).
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataQuality {
+    pub table: ParsedDataTable,
+    pub overall_score: f64,
+}
+
+/// Per-row completeness, and the table without its sparse rows when they were dropped.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RowCompleteness {
+    pub table: ParsedDataTable,
+    /// 0-based indices (into `rows`) of the rows below `min_fraction`, in ascending order.
+    pub sparse_row_indices: Vec<usize>,
+    /// The input table without the sparse rows; only with `drop_sparse`.
+    pub filtered: Option<ParsedDataTable>,
+}
+
+/// Type a non-missing cell reads as. The declaration order (also the order of `ALL`) breaks ties
+/// for the majority type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellType {
+    Number,
+    Boolean,
+    Text,
+}
+
+impl CellType {
+    const ALL: [CellType; 3] = [CellType::Number, CellType::Boolean, CellType::Text];
+
+    /// Coarsens the column-type classifier of `ColumnType::of_cell`, so that quality scores and the
+    /// inferred column types agree on what a cell is. Dates count as text here.
+    fn of(cell: &Value) -> Self {
+        match ColumnType::of_cell(cell) {
+            Some(ColumnType::Integer | ColumnType::Numeric) => CellType::Number,
+            Some(ColumnType::Boolean) => CellType::Boolean,
+            Some(ColumnType::Date | ColumnType::Text | ColumnType::Mixed) | None => CellType::Text,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CellType::Number => "数値",
+            CellType::Boolean => "論理値",
+            CellType::Text => "文字列",
+        }
+    }
+}
+
+/// Scores every column of a parsed table between 0 and 1:
+/// - completeness = 1 - (missing cells / rows); null and blank cells are missing
+/// - consistency = cells of the majority type (number, boolean or text) / non-missing cells
+/// - uniqueness = distinct values / non-missing cells, for `key_columns` only
+///
+/// A column's score is the mean of the components that apply to it; consistency and uniqueness
+/// do not apply to a column without values. The sheet score is the mean of the column scores.
+pub(crate) fn data_quality(table: &ParsedDataTable,
+                           key_columns: &[String])
+                           -> Result<DataQuality, String> {
+    if table.headers.is_empty() {
+        return Err("the table has no columns".to_string());
+    }
+    for key in key_columns {
+        table.column_index(key)?;
+    }
+
+    let row_count = table.rows.len();
+    let mut scores = Vec::with_capacity(table.headers.len());
+    let mut rows = Vec::with_capacity(table.headers.len());
+    for (col_index, header) in table.headers.iter().enumerate() {
+        let values: Vec<&Value> = table.rows
+                                       .iter()
+                                       .filter_map(|row| row.get(col_index))
+                                       .filter(|cell| !is_missing(cell))
+                                       .collect();
+        let missing_rate = (row_count > 0).then(|| 1.0 - values.len() as f64 / row_count as f64);
+        let completeness = missing_rate.map_or(0.0, |rate| 1.0 - rate);
+
+        let majority = majority_type(&values);
+        let consistency = majority.map(|(_, share)| share);
+
+        let uniqueness = (key_columns.contains(header) && !values.is_empty()).then(|| {
+                             let distinct: HashSet<String> =
+                                 values.iter().map(|cell| cell_key(cell)).collect();
+                             distinct.len() as f64 / values.len() as f64
+                         });
+
+        let components: Vec<f64> = [Some(completeness), consistency, uniqueness].into_iter()
+                                                                                .flatten()
+                                                                                .collect();
+        let score = components.iter().sum::<f64>() / components.len() as f64;
+        scores.push(score);
+
+        rows.push(vec![Value::String(header.clone()),
+                       majority.map_or(Value::Null, |(cell_type, _)| Value::from(cell_type.label())),
+                       number_cell(missing_rate),
+                       number_cell(Some(completeness)),
+                       number_cell(consistency),
+                       number_cell(uniqueness),
+                       number_cell(Some(score))]);
+    }
+    let overall_score = scores.iter().sum::<f64>() / scores.len() as f64;
+
+    let table = ParsedDataTable { headers: vec!["列".to_string(),
+                                                "推定型".to_string(),
+                                                "欠損率".to_string(),
+                                                "完全性".to_string(),
+                                                "一貫性".to_string(),
+                                                "一意性".to_string(),
+                                                "スコア".to_string()],
+                                  rows,
+                                  note: Some(format!("完全性 = 1 - 欠損率、一貫性 = 最も多い型のセルの割合、一意性 = 異なる値の数 / 値の数 (キー列のみ)。スコアは該当する指標の平均、シート全体のスコア = {:.3}",
+                                                     overall_score)),
+                                  title: Some("データ品質".to_string()),
+                                  column_types: Vec::new() };
+    Ok(DataQuality { table, overall_score })
+}
+
+/// Scores every row by the share of its cells that hold a value, over all columns: null and
+/// blank cells are missing, and so are the cells a short row lacks. Rows whose share is below
+/// `min_fraction` are flagged as sparse, and with `drop_sparse` the table is also returned
+/// without them.
+pub(crate) fn row_completeness(table: &ParsedDataTable,
+                               min_fraction: f64,
+                               drop_sparse: bool)
+                               -> Result<RowCompleteness, String> {
+    if table.headers.is_empty() {
+        return Err("the table has no columns".to_string());
+    }
+    if !(0.0..=1.0).contains(&min_fraction) {
+        return Err("min_fraction must be between 0 and 1".to_string());
+    }
+
+    let column_count = table.headers.len();
+    let mut rows = Vec::with_capacity(table.rows.len());
+    let mut sparse_row_indices = Vec::new();
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let present = row.iter()
+                         .take(column_count)
+                         .filter(|cell| !is_missing(cell))
+                         .count();
+        let fraction = present as f64 / column_count as f64;
+        let sparse = fraction < min_fraction;
+        if sparse {
+            sparse_row_indices.push(row_index);
+        }
+        rows.push(vec![Value::from(row_index + 1),
+                       Value::from(present),
+                       number_cell(Some(fraction)),
+                       Value::Bool(sparse)]);
+    }
+
+    let filtered =
+        drop_sparse.then(|| {
+                       let mut filtered = table.clone();
+                       filtered.rows =
+                           table.rows
+                                .iter()
+                                .enumerate()
+                                .filter(|(row_index, _)| sparse_row_indices.binary_search(row_index).is_err())
+                                .map(|(_, row)| row.clone())
+                                .collect();
+                       let dropped_note = format!("完全率が {} 未満の {} 行を除外しました",
+                                                  min_fraction,
+                                                  sparse_row_indices.len());
+                       filtered.note = join_notes([table.note.clone(), Some(dropped_note)]);
+                       filtered.refresh_column_types();
+                       filtered
+                   });
+
+    let table = ParsedDataTable { headers: vec!["行".to_string(),
+                                                "値のあるセル".to_string(),
+                                                "完全率".to_string(),
+                                                "疎な行".to_string()],
+                                  rows,
+                                  note: Some(format!("完全率 = 値のあるセル / 全 {} 列。{} 未満の {} 行を疎な行としました",
+                                                     column_count,
+                                                     min_fraction,
+                                                     sparse_row_indices.len())),
+                                  title: Some("行ごとの完全性".to_string()),
+                                  column_types: Vec::new() };
+    Ok(RowCompleteness { table,
+                         sparse_row_indices,
+                         filtered })
+}
+
+/// Lists the columns that carry (almost) no information:
+/// - numeric columns (every value a number) whose coefficient of variation, SD / |mean|, is below
+///   `threshold`; when the mean is 0 the variance is compared instead
+/// - other columns with a single distinct value
+/// - columns without any value
+///
+/// Only flagged columns are returned, in sheet order, each with the metric it was flagged on.
+pub(crate) fn low_variance_columns(table: &ParsedDataTable,
+                                   threshold: f64)
+                                   -> Result<ParsedDataTable, String> {
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(format!("threshold must be a non-negative number: {}", threshold));
+    }
+
+    let mut rows = Vec::new();
+    for (col_index, header) in table.headers.iter().enumerate() {
+        let values: Vec<&Value> = table.rows
+                                       .iter()
+                                       .filter_map(|row| row.get(col_index))
+                                       .filter(|cell| !is_missing(cell))
+                                       .collect();
+        if values.is_empty() {
+            rows.push(vec![Value::String(header.clone()),
+                           Value::Null,
+                           Value::from("値の数"),
+                           Value::from(0)]);
+            continue;
+        }
+
+        let numbers: Option<Vec<f64>> = values.iter().map(|cell| numeric_value(cell, header)).collect();
+        let flagged = match numbers {
+            Some(numbers) => {
+                let sd = sample_sd(&numbers).unwrap_or(0.0);
+                let mean = mean(&numbers).expect("values is not empty");
+                let (metric, value) = if mean == 0.0 {
+                    ("分散", sd * sd)
+                } else {
+                    ("変動係数", sd / mean.abs())
+                };
+                (value < threshold || sd == 0.0).then(|| (CellType::Number, metric, number_cell(Some(value))))
+            },
+            None => {
+                let distinct: HashSet<String> = values.iter().map(|cell| cell_key(cell)).collect();
+                let majority = majority_type(&values).map(|(cell_type, _)| cell_type);
+                (distinct.len() == 1).then(|| {
+                                         (majority.unwrap_or(CellType::Text),
+                                          "水準数",
+                                          Value::from(distinct.len()))
+                                     })
+            },
+        };
+        if let Some((cell_type, metric, value)) = flagged {
+            rows.push(vec![Value::String(header.clone()),
+                           Value::from(cell_type.label()),
+                           Value::from(metric),
+                           value]);
+        }
+    }
+
+    let flagged_count = rows.len();
+    Ok(ParsedDataTable { headers: vec!["列".to_string(),
+                                       "推定型".to_string(),
+                                       "指標".to_string(),
+                                       "値".to_string()],
+                         rows,
+                         note: Some(format!("変動係数 (平均が 0 の列は分散) が {} 未満の数値列、値が 1 種類だけの列、値のない列を表示しています ({} / {} 列)",
+                                            threshold,
+                                            flagged_count,
+                                            table.headers.len())),
+                         title: Some("ばらつきのない列".to_string()),
+                         column_types: Vec::new() })
+}
+
+/// Majority type of the non-missing cells and the share of cells that have it.
+fn majority_type(values: &[&Value]) -> Option<(CellType, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut counts = [0usize; CellType::ALL.len()];
+    for cell in values {
+        let cell_type = CellType::of(cell);
+        counts[CellType::ALL.iter()
+                            .position(|t| *t == cell_type)
+                            .expect("listed in ALL")] += 1;
+    }
+    // Strictly greater keeps the earlier type on ties.
+    let mut best = 0;
+    for i in 1..counts.len() {
+        if counts[i] > counts[best] {
+            best = i;
+        }
+    }
+    Some((CellType::ALL[best], counts[best] as f64 / values.len() as f64))
+}
+
+fn numeric_value(cell: &Value,
+                 header: &str)
+                 -> Option<f64> {
+    match cell {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => parse_numeric_string(text, NumericCellContext::new(0, 0, header)).ok()
+                                                                                                .flatten(),
+        _ => None,
+    }
+}
+
+fn is_missing(cell: &Value) -> bool {
+    match cell {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Text used to compare values for uniqueness, so that `1` and `"1"` count as the same value.
+fn cell_key(cell: &Value) -> String {
+    match cell {
+        Value::String(text) => text.trim().to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        data_quality,
+        low_variance_columns,
+        row_completeness,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn fixture() -> ParsedDataTable {
+        let clean = ["1", "2", "3", "4", "5", "6"];
+        let messy = [Value::from("10"),
+                     Value::Null,
+                     Value::from("n/a?"),
+                     Value::from(7.5),
+                     Value::from(""),
+                     Value::from("10")];
+        ParsedDataTable { headers: vec!["clean".to_string(), "messy".to_string()],
+                          rows: clean.iter()
+                                     .zip(messy)
+                                     .map(|(clean, messy)| vec![Value::from(*clean), messy])
+                                     .collect(),
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn clean_column_scores_higher_than_messy_column() {
+        let quality = data_quality(&fixture(), &[]).unwrap();
+        let score = |row: usize| quality.table.rows[row][6].as_f64().unwrap();
+
+        assert_eq!(score(0), 1.0);
+        // completeness 4 / 6, consistency 3 / 4 (three numbers, one text)
+        assert!((score(1) - (4.0 / 6.0 + 0.75) / 2.0).abs() < 1e-12);
+        assert!(score(0) > score(1));
+        assert_eq!(quality.table.rows[1][1], Value::from("数値"));
+        assert!(quality.table.rows[1][5].is_null());
+        assert!((quality.overall_score - (score(0) + score(1)) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cells_are_typed_like_the_inferred_column_types() {
+        let cells = [Value::from("TRUE"),
+                     Value::from(" false "),
+                     Value::from(true),
+                     Value::from("2024-01-15")];
+        let table = ParsedDataTable { headers: vec!["flag".to_string()],
+                                      rows: cells.into_iter().map(|cell| vec![cell]).collect(),
+                                      note: None,
+                                      title: None,
+                                      column_types: Vec::new() };
+
+        let quality = data_quality(&table, &[]).unwrap();
+
+        // The date is the only cell that is not a boolean.
+        assert_eq!(quality.table.rows[0][1], Value::from("論理値"));
+        assert_eq!(quality.table.rows[0][4].as_f64(), Some(0.75));
+    }
+
+    #[test]
+    fn uniqueness_counts_for_key_columns_only() {
+        let quality = data_quality(&fixture(), &["messy".to_string()]).unwrap();
+
+        // "10" appears twice among the four values.
+        assert_eq!(quality.table.rows[1][5].as_f64(), Some(0.75));
+        assert!(quality.table.rows[0][5].is_null());
+        assert!(data_quality(&fixture(), &["id".to_string()]).is_err());
+    }
+
+    #[test]
+    fn only_the_constant_columns_are_flagged_as_low_variance() {
+        let table = ParsedDataTable { headers: vec!["constant".to_string(),
+                                                    "normal".to_string(),
+                                                    "site".to_string(),
+                                                    "group".to_string()],
+                                      rows: (1..=6).map(|i| {
+                                                       vec![Value::from(5),
+                                                            Value::from(format!("{}", i * 3)),
+                                                            Value::from("Tokyo"),
+                                                            Value::from(if i % 2 == 0 { "a" } else { "b" })]
+                                                   })
+                                                   .collect(),
+                                      note: None,
+                                      title: None,
+                                      column_types: Vec::new() };
+
+        let flagged = low_variance_columns(&table, 0.01).unwrap();
+
+        assert_eq!(flagged.rows,
+                   vec![vec![Value::from("constant"),
+                             Value::from("数値"),
+                             Value::from("変動係数"),
+                             Value::from(0.0)],
+                        vec![Value::from("site"),
+                             Value::from("文字列"),
+                             Value::from("水準数"),
+                             Value::from(1)]]);
+        assert!(low_variance_columns(&table, -1.0).is_err());
+    }
+
+    #[test]
+    fn nearly_empty_row_is_flagged_and_optionally_dropped() {
+        let table = ParsedDataTable { headers: vec!["id".to_string(), "a".to_string(), "b".to_string()],
+                                      rows: vec![vec![Value::from(1), Value::from(2), Value::from(3)],
+                                                 vec![Value::from(2), Value::Null, Value::from(" ")],
+                                                 vec![Value::from(3), Value::from(5)],
+                                                 vec![Value::from(4), Value::from(6), Value::from(7)]],
+                                      note: None,
+                                      title: None,
+                                      column_types: Vec::new() };
+
+        let kept = row_completeness(&table, 0.5, false).unwrap();
+        assert_eq!(kept.sparse_row_indices, vec![1]);
+        assert_eq!(kept.table.rows[1],
+                   vec![Value::from(2),
+                        Value::from(1),
+                        Value::from(1.0 / 3.0),
+                        Value::Bool(true)]);
+        // A short row's missing cells count against it.
+        assert_eq!(kept.table.rows[2][2].as_f64(), Some(2.0 / 3.0));
+        assert!(kept.filtered.is_none());
+
+        let dropped = row_completeness(&table, 0.5, true).unwrap().filtered.unwrap();
+        let ids: Vec<&Value> = dropped.rows.iter().map(|row| &row[0]).collect();
+        assert_eq!(ids, vec![&Value::from(1), &Value::from(3), &Value::from(4)]);
+        assert!(row_completeness(&table, 1.5, false).is_err());
+    }
+}
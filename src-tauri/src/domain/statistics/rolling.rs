@@ -0,0 +1,129 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+use crate::domain::statistics::sample::pearson;
+
+/// Smallest window (and smallest number of complete pairs) for which a correlation is reported.
+pub(crate) const MIN_ROLLING_WINDOW: usize = 3;
+
+/// Computes the Pearson correlation between `x` and `y` over a trailing window of `window` rows.
+/// One output row is produced per input row so the result lines up with the original row order;
+/// rows before the first full window, and windows with fewer than three complete pairs, are null.
+pub(crate) fn rolling_correlation(dataset: &NumericDataset,
+                                  x: &str,
+                                  y: &str,
+                                  window: usize)
+                                  -> Result<ParsedDataTable, String> {
+    if window < MIN_ROLLING_WINDOW {
+        return Err(format!("window must be at least {} (got {})", MIN_ROLLING_WINDOW, window));
+    }
+    let x_values = dataset.get(x)
+                          .ok_or_else(|| format!("x variable '{}' is not in the dataset", x))?;
+    let y_values = dataset.get(y)
+                          .ok_or_else(|| format!("y variable '{}' is not in the dataset", y))?;
+    if x_values.len() != y_values.len() {
+        return Err("x and y must have the same length".to_string());
+    }
+
+    let mut undefined = 0usize;
+    let rows = (0..x_values.len()).map(|i| {
+                                      if i + 1 < window {
+                                          return vec![Value::from(i + 1), Value::Null, Value::from(0)];
+                                      }
+                                      let start = i + 1 - window;
+                                      let pairs: Vec<(f64, f64)> =
+                                          x_values[start..=i].iter()
+                                                             .zip(&y_values[start..=i])
+                                                             .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+                                                             .collect();
+                                      let r = if pairs.len() < MIN_ROLLING_WINDOW {
+                                          None
+                                      } else {
+                                          pearson(&pairs)
+                                      };
+                                      if r.is_none() {
+                                          undefined += 1;
+                                      }
+                                      vec![Value::from(i + 1), number_cell(r), Value::from(pairs.len())]
+                                  })
+                                  .collect();
+
+    let mut notes = Vec::new();
+    if x_values.len() >= window {
+        notes.push(format!("先頭の {} 行は窓が満たないため空欄です", window - 1));
+    } else {
+        notes.push(format!("行数が窓幅 {} に満たないため相関係数を計算できません", window));
+    }
+    if undefined > 0 {
+        notes.push(format!("完全なペアが {} 組未満、または値が一定の窓 {} 件は空欄です",
+                           MIN_ROLLING_WINDOW, undefined));
+    }
+
+    Ok(ParsedDataTable { headers: vec!["行".to_string(), "相関係数".to_string(), "n".to_string()],
+                         rows,
+                         note: Some(notes.join(" / ")),
+                         title: Some(format!("移動相関 ({} × {}, 窓幅 {})", x, y, window)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::rolling_correlation;
+
+    #[test]
+    fn correlation_sign_flip_is_reflected_in_rolling_output() {
+        let xs: Vec<Option<f64>> = (0..10).map(|i| Some(i as f64)).collect();
+        let ys: Vec<Option<f64>> = (0..10).map(|i| {
+                                              let i = i as f64;
+                                              Some(if i < 5.0 { i } else { 10.0 - i })
+                                          })
+                                          .collect();
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), xs);
+        dataset.insert("y".to_string(), ys);
+
+        let table = rolling_correlation(&dataset, "x", "y", 3).unwrap();
+
+        assert_eq!(table.rows.len(), 10);
+        assert_eq!(table.rows[0][1], Value::Null);
+        assert_eq!(table.rows[1][1], Value::Null);
+        assert_eq!(table.rows[2][1], Value::from(1.0));
+        assert_eq!(table.rows[4][1], Value::from(1.0));
+        assert_eq!(table.rows[7][1], Value::from(-1.0));
+        assert_eq!(table.rows[9][1], Value::from(-1.0));
+    }
+
+    #[test]
+    fn windows_without_enough_complete_pairs_are_null() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(),
+                       vec![Some(1.0), Some(2.0), None, Some(4.0), Some(5.0)]);
+        dataset.insert("y".to_string(),
+                       vec![Some(2.0), Some(4.0), Some(6.0), Some(8.0), Some(10.0)]);
+
+        let table = rolling_correlation(&dataset, "x", "y", 3).unwrap();
+
+        assert_eq!(table.rows[2][1], Value::Null);
+        assert_eq!(table.rows[2][2], Value::from(2));
+        assert_eq!(table.rows[3][1], Value::Null);
+        assert_eq!(table.rows[4][1], Value::Null);
+        assert!(table.note.unwrap().contains("3 件"));
+    }
+
+    #[test]
+    fn window_below_three_is_rejected() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), vec![Some(1.0), Some(2.0)]);
+        dataset.insert("y".to_string(), vec![Some(1.0), Some(2.0)]);
+
+        let err = rolling_correlation(&dataset, "x", "y", 2).unwrap_err();
+        assert!(err.contains("at least 3"));
+    }
+}
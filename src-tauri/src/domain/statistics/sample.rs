@@ -0,0 +1,54 @@
+/// Collects the observed (non-missing) values of a dataset column in row order.
+pub(crate) fn observed_values(column: &[Option<f64>]) -> Vec<f64> {
+    column.iter().filter_map(|value| *value).collect()
+}
+
+pub(crate) fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Returns true when every value is identical (including the empty case).
+pub(crate) fn is_constant(values: &[f64]) -> bool {
+    values.windows(2).all(|pair| pair[0] == pair[1])
+}
+
+/// Sample standard deviation (n - 1 denominator). Requires at least two values.
+pub(crate) fn sample_sd(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let m = mean(values)?;
+    let ss: f64 = values.iter().map(|v| (v - m).powi(2)).sum();
+    Some((ss / (values.len() - 1) as f64).sqrt())
+}
+
+/// Quantile of already-sorted values with linear interpolation, matching R's default
+/// `quantile(type = 7)`. Requires at least one value and `p` in [0, 1].
+pub(crate) fn sorted_quantile(sorted: &[f64],
+                              p: f64)
+                              -> f64 {
+    let h = (sorted.len() - 1) as f64 * p;
+    let lower = h.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    sorted[lower] + (h - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+/// Pearson correlation of paired observations.
+/// Returns `None` when either side is constant, since the correlation is undefined.
+pub(crate) fn pearson(pairs: &[(f64, f64)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    let x_mean = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let y_mean = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (sxx, syy, sxy) = pairs.iter().fold((0.0, 0.0, 0.0), |(sxx, syy, sxy), (x, y)| {
+                                          let dx = x - x_mean;
+                                          let dy = y - y_mean;
+                                          (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+                                      });
+    if sxx == 0.0 || syy == 0.0 {
+        return None;
+    }
+    Some((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
@@ -0,0 +1,171 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    column_values,
+    join_notes,
+    number_cell,
+};
+
+use super::sample::pearson;
+
+/// Fewest complete pairs for which a group's correlation is reported.
+const MIN_PAIRS: usize = 3;
+
+/// Pooled and within-group correlations of two columns, with a Simpson's paradox indicator.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SimpsonsCheck {
+    /// The pooled row first, then one row per level of the group column.
+    pub table: ParsedDataTable,
+    /// `None` when the pooled correlation is undefined.
+    pub pooled: Option<f64>,
+    /// True when every group with a correlation has the same sign and the pooled one has the other.
+    pub paradox: bool,
+}
+
+/// Pearson correlation of `x` and `y` over all rows and within each level of `group`, flagging a
+/// Simpson's paradox when the pooled sign is the opposite of a sign shared by at least two groups.
+///
+/// Only rows where x, y and the group are all present are used, so the pooled and within-group
+/// coefficients describe the same observations. Levels keep the order in which they first
+/// appear; a level with fewer than three pairs, or a constant x or y, gets no coefficient and
+/// does not count towards the shared sign.
+pub(crate) fn simpsons_check(table: &ParsedDataTable,
+                             x: &str,
+                             y: &str,
+                             group: &str)
+                             -> Result<SimpsonsCheck, String> {
+    let x_values = column_values(table, table.column_index(x)?, x)?;
+    let y_values = column_values(table, table.column_index(y)?, y)?;
+    let group_index = table.column_index(group)?;
+
+    let mut pooled_pairs = Vec::new();
+    let mut by_level: IndexMap<String, Vec<(f64, f64)>> = IndexMap::new();
+    for ((row, x_value), y_value) in table.rows.iter().zip(&x_values).zip(&y_values) {
+        let level = match row.get(group_index) {
+            None | Some(Value::Null) => continue,
+            Some(Value::String(value)) if value.trim().is_empty() => continue,
+            Some(Value::String(value)) => value.clone(),
+            Some(value) => value.to_string(),
+        };
+        let (Some(x_value), Some(y_value)) = (x_value, y_value) else {
+            continue;
+        };
+        pooled_pairs.push((*x_value, *y_value));
+        by_level.entry(level).or_default().push((*x_value, *y_value));
+    }
+    if pooled_pairs.len() < MIN_PAIRS {
+        return Err(format!("at least {} rows with {}, {} and {} present are required",
+                           MIN_PAIRS, x, y, group));
+    }
+
+    let pooled = pearson(&pooled_pairs);
+    let within: Vec<(&String, usize, Option<f64>)> =
+        by_level.iter()
+                .map(|(level, pairs)| {
+                    let r = (pairs.len() >= MIN_PAIRS).then(|| pearson(pairs)).flatten();
+                    (level, pairs.len(), r)
+                })
+                .collect();
+
+    let signs: Vec<f64> = within.iter()
+                                .filter_map(|(_, _, r)| r.filter(|r| *r != 0.0).map(f64::signum))
+                                .collect();
+    let shared_sign = (signs.len() >= 2 && signs.iter().all(|sign| *sign == signs[0])).then(|| signs[0]);
+    let paradox = match (pooled, shared_sign) {
+        (Some(pooled), Some(sign)) => pooled != 0.0 && pooled.signum() != sign,
+        _ => false,
+    };
+
+    let mut rows = vec![vec![Value::from("全体"),
+                             Value::from(pooled_pairs.len()),
+                             number_cell(pooled)]];
+    rows.extend(within.iter().map(|(level, n, r)| {
+                                 vec![Value::from(level.as_str()), Value::from(*n), number_cell(*r)]
+                             }));
+
+    let paradox_note = paradox.then(|| {
+                                  format!("全体の相関と群内の相関の符号が逆です (シンプソンのパラドックスの可能性)。{} で層別して解釈してください",
+                                          group)
+                              });
+    let sparse = within.iter().filter(|(_, _, r)| r.is_none()).count();
+    let sparse_note = (sparse > 0).then(|| {
+                          format!("{} 群は観測が3件未満か値が一定のため、相関係数を算出していません",
+                                  sparse)
+                      });
+    let excluded = table.rows.len() - pooled_pairs.len();
+    let excluded_note = (excluded > 0).then(|| format!("欠損のある {} 行を除外しました", excluded));
+
+    Ok(SimpsonsCheck { table: ParsedDataTable { headers: vec![group.to_string(),
+                                                              "n".to_string(),
+                                                              "相関係数".to_string()],
+                                                rows,
+                                                note: join_notes([paradox_note,
+                                                                  sparse_note,
+                                                                  excluded_note]),
+                                                title: Some(format!("全体と群内の相関 ({} × {}, {} 別)",
+                                                                    x, y, group)),
+                                                column_types: Vec::new() },
+                       pooled,
+                       paradox })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::simpsons_check;
+    use crate::domain::input::table::ParsedDataTable;
+
+    /// Three groups whose means rise in x and fall in y, each with a positive slope inside.
+    fn paradox_table() -> ParsedDataTable {
+        let mut rows = Vec::new();
+        for (offset, level) in [(0.0, "a"), (5.0, "b"), (10.0, "c")] {
+            for step in 0..5 {
+                let x = offset + step as f64;
+                let y = 20.0 - 2.0 * offset + step as f64 * 0.5 + if step % 2 == 0 { 0.3 } else { -0.3 };
+                rows.push(vec![Value::from(x), Value::from(y), Value::from(level)]);
+            }
+        }
+        rows.push(vec![Value::Null, Value::from(1.0), Value::from("a")]);
+        ParsedDataTable { headers: vec!["x".to_string(), "y".to_string(), "g".to_string()],
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn flags_opposite_signs_in_a_constructed_paradox() {
+        let check = simpsons_check(&paradox_table(), "x", "y", "g").unwrap();
+
+        assert!(check.pooled.unwrap() < -0.8, "{:?}", check.pooled);
+        assert!(check.paradox);
+        assert_eq!(check.table.rows.len(), 4);
+        assert_eq!(check.table.rows[0][1], Value::from(15));
+        assert!(check.table.rows[1..].iter()
+                                     .all(|row| row[2].as_f64().unwrap() > 0.9));
+        let note = check.table.note.unwrap();
+        assert!(note.contains("シンプソン"), "{}", note);
+        assert!(note.contains("欠損のある 1 行"), "{}", note);
+    }
+
+    #[test]
+    fn consistent_signs_are_not_flagged() {
+        let mut table = paradox_table();
+        for row in &mut table.rows {
+            if let Some(x) = row[0].as_f64() {
+                row[1] = Value::from(x * 2.0 + if x as i64 % 2 == 0 { 0.5 } else { 0.0 });
+            }
+        }
+
+        let check = simpsons_check(&table, "x", "y", "g").unwrap();
+
+        assert!(check.pooled.unwrap() > 0.9);
+        assert!(!check.paradox);
+        assert!(simpsons_check(&table, "x", "z", "g").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+use crate::domain::statistics::sample::pearson;
+
+/// Smallest number of complete pairs for which a rank correlation is reported.
+pub(crate) const MIN_SPEARMAN_PAIRS: usize = 3;
+
+/// 1-based ranks with ties given their average rank, matching R's `rank(ties.method = "average")`.
+pub(crate) fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Positions start..end share ranks start + 1 ..= end.
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Spearman correlation of the complete pairs of two columns: the Pearson correlation of their
+/// ranks, where both sides are ranked among the complete pairs only (R's `cor(method = "spearman",
+/// use = "pairwise.complete.obs")`). `None` below three pairs or when either side is constant.
+fn spearman(x: &[Option<f64>],
+            y: &[Option<f64>])
+            -> (Option<f64>, usize) {
+    let (xs, ys): (Vec<f64>, Vec<f64>) = x.iter().zip(y).filter_map(|(x, y)| Some(((*x)?, (*y)?))).unzip();
+    if xs.len() < MIN_SPEARMAN_PAIRS {
+        return (None, xs.len());
+    }
+    let pairs: Vec<(f64, f64)> = average_ranks(&xs).into_iter().zip(average_ranks(&ys)).collect();
+    (pearson(&pairs), xs.len())
+}
+
+/// Square Spearman rank-correlation matrix over every column of the dataset, using
+/// pairwise-complete observations. Cells with fewer than three complete pairs, or a constant
+/// column within the pairs, are null.
+pub(crate) fn quick_spearman(dataset: &NumericDataset) -> Result<ParsedDataTable, String> {
+    if dataset.len() < 2 {
+        return Err(format!("at least two columns are required (got {})", dataset.len()));
+    }
+    let columns: Vec<(&String, &Vec<Option<f64>>)> = dataset.iter().collect();
+
+    let mut undefined = 0usize;
+    let mut min_pairs = usize::MAX;
+    let rows = columns.iter()
+                      .enumerate()
+                      .map(|(i, (name, x))| {
+                          let mut row = vec![Value::String((*name).clone())];
+                          for (j, (_, y)) in columns.iter().enumerate() {
+                              let (rho, n) = spearman(x, y);
+                              if i != j {
+                                  min_pairs = min_pairs.min(n);
+                                  if rho.is_none() && i < j {
+                                      undefined += 1;
+                                  }
+                              }
+                              row.push(number_cell(rho));
+                          }
+                          row
+                      })
+                      .collect();
+
+    let mut headers = vec!["変数".to_string()];
+    headers.extend(columns.iter().map(|(name, _)| (*name).clone()));
+
+    let mut notes = vec!["順位は同順位に平均順位を与えて算出しています (欠損値はペアごとに除外)".to_string()];
+    if undefined > 0 {
+        notes.push(format!("完全なペアが {} 組未満、または値が一定の組み合わせ {} 件は空欄です",
+                           MIN_SPEARMAN_PAIRS, undefined));
+    }
+    if min_pairs < usize::MAX {
+        notes.push(format!("ペアごとの最小 n = {}", min_pairs));
+    }
+
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: Some(notes.join(" / ")),
+                         title: Some("スピアマンの順位相関行列".to_string()),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        average_ranks,
+        quick_spearman,
+    };
+
+    #[test]
+    fn ties_get_their_average_rank() {
+        assert_eq!(average_ranks(&[10.0, 20.0, 20.0, 5.0, 20.0]),
+                   vec![2.0, 4.0, 4.0, 1.0, 4.0]);
+    }
+
+    #[test]
+    fn matrix_matches_known_spearman_values_with_ties() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(),
+                       vec![Some(1.0), Some(2.0), Some(2.0), Some(3.0), Some(4.0)]);
+        dataset.insert("y".to_string(),
+                       vec![Some(2.0), Some(1.0), Some(3.0), Some(3.0), Some(5.0)]);
+        dataset.insert("z".to_string(), vec![Some(1.0), None, None, Some(2.0), None]);
+
+        let table = quick_spearman(&dataset).unwrap();
+
+        assert_eq!(table.headers, vec!["変数", "x", "y", "z"]);
+        // R: cor(c(1, 2, 2, 3, 4), c(2, 1, 3, 3, 5), method = "spearman") = 7.25 / 9.5
+        let rho = table.rows[0][2].as_f64().unwrap();
+        assert!((rho - 7.25 / 9.5).abs() < 1e-12);
+        assert_eq!(table.rows[1][1], table.rows[0][2]);
+        assert_eq!(table.rows[0][1], Value::from(1.0));
+        // Only two complete pairs with z.
+        assert_eq!(table.rows[0][3], Value::Null);
+        assert_eq!(table.rows[2][3], Value::Null);
+        assert!(table.note.unwrap().contains("2 件"));
+    }
+
+    #[test]
+    fn a_single_column_is_rejected() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), vec![Some(1.0)]);
+        assert!(quick_spearman(&dataset).is_err());
+    }
+}
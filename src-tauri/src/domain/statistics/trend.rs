@@ -0,0 +1,272 @@
+use serde_json::Value;
+
+use crate::domain::input::numeric::NumericDataset;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    number_cell,
+};
+
+use super::sample::{
+    is_constant,
+    mean,
+    sorted_quantile,
+};
+
+/// Above this many complete pairs, the Theil-Sen fit uses an evenly spaced subsample of the rows,
+/// which keeps the number of pairwise slopes (n² / 2) near 4.5 million.
+pub(crate) const MAX_THEIL_SEN_POINTS: usize = 3000;
+
+/// Two-sided 97.5% standard normal quantile, for the 95% confidence interval of the slope.
+const Z_975: f64 = 1.959_963_984_540_054;
+
+/// Ordinary least squares fit of `y = intercept + slope * x`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// `None` when y is constant (total sum of squares is zero).
+    pub r_squared: Option<f64>,
+}
+
+/// Fits a simple linear regression on paired observations.
+/// Requires at least two pairs and a non-constant x (otherwise the slope is undefined).
+pub(crate) fn linear_fit(xs: &[f64],
+                         ys: &[f64])
+                         -> Result<LinearFit, String> {
+    if xs.len() != ys.len() {
+        return Err("x and y must have the same length".to_string());
+    }
+    if xs.len() < 2 {
+        return Err("at least 2 complete (x, y) pairs are required".to_string());
+    }
+    if is_constant(xs) {
+        return Err("x is constant, so the slope is undefined".to_string());
+    }
+
+    let x_mean = mean(xs).expect("xs is not empty");
+    let y_mean = mean(ys).expect("ys is not empty");
+    let sxx: f64 = xs.iter().map(|x| (x - x_mean).powi(2)).sum();
+    let sxy: f64 = xs.iter().zip(ys).map(|(x, y)| (x - x_mean) * (y - y_mean)).sum();
+    let syy: f64 = ys.iter().map(|y| (y - y_mean).powi(2)).sum();
+
+    let slope = sxy / sxx;
+    let intercept = y_mean - slope * x_mean;
+    let r_squared = if syy == 0.0 {
+        None
+    } else {
+        Some((sxy * sxy) / (sxx * syy))
+    };
+    Ok(LinearFit { slope,
+                   intercept,
+                   r_squared })
+}
+
+/// Builds a one-row trend line summary for a scatterplot overlay without R.
+/// Pairs where either x or y is missing are dropped before fitting.
+pub(crate) fn trend_line(dataset: &NumericDataset,
+                         x: &str,
+                         y: &str)
+                         -> Result<ParsedDataTable, String> {
+    let x_values = dataset.get(x)
+                          .ok_or_else(|| format!("x variable '{}' is not in the dataset", x))?;
+    let y_values = dataset.get(y)
+                          .ok_or_else(|| format!("y variable '{}' is not in the dataset", y))?;
+
+    let (xs, ys): (Vec<f64>, Vec<f64>) = x_values.iter()
+                                                 .zip(y_values)
+                                                 .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+                                                 .unzip();
+    let fit = linear_fit(&xs, &ys)?;
+    let x_min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+    let x_max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let dropped = x_values.len().max(y_values.len()) - xs.len();
+
+    let row = vec![Value::from(xs.len()),
+                   number_cell(Some(fit.slope)),
+                   number_cell(Some(fit.intercept)),
+                   number_cell(fit.r_squared),
+                   number_cell(Some(x_min)),
+                   number_cell(Some(fit.intercept + fit.slope * x_min)),
+                   number_cell(Some(x_max)),
+                   number_cell(Some(fit.intercept + fit.slope * x_max))];
+
+    let note = if dropped > 0 {
+        Some(format!("x または y が欠損している {} 行を除外しました", dropped))
+    } else {
+        None
+    };
+
+    Ok(ParsedDataTable { headers: vec!["n".to_string(),
+                                       "傾き".to_string(),
+                                       "切片".to_string(),
+                                       "R²".to_string(),
+                                       "x 最小値".to_string(),
+                                       "予測値 (x 最小値)".to_string(),
+                                       "x 最大値".to_string(),
+                                       "予測値 (x 最大値)".to_string()],
+                         rows: vec![row],
+                         note,
+                         title: Some(format!("回帰直線 ({} ~ {})", y, x)),
+                         column_types: Vec::new() })
+}
+
+/// Theil-Sen line: the slope is the median of the slopes between every two points with different
+/// x, and the intercept is the median of `y - slope * x`. Unlike the least-squares slope it hardly
+/// moves when a few points are outliers.
+///
+/// The 95% confidence interval of the slope is Sen's (1968) rank interval, without a correction
+/// for tied x or y. Pairs where either x or y is missing are dropped; beyond
+/// [`MAX_THEIL_SEN_POINTS`] pairs an evenly spaced subsample of the rows is used.
+pub(crate) fn theil_sen(dataset: &NumericDataset,
+                        x: &str,
+                        y: &str)
+                        -> Result<ParsedDataTable, String> {
+    let x_values = dataset.get(x)
+                          .ok_or_else(|| format!("x variable '{}' is not in the dataset", x))?;
+    let y_values = dataset.get(y)
+                          .ok_or_else(|| format!("y variable '{}' is not in the dataset", y))?;
+
+    let complete: Vec<(f64, f64)> = x_values.iter()
+                                            .zip(y_values)
+                                            .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+                                            .collect();
+    let dropped = x_values.len().max(y_values.len()) - complete.len();
+    let points: Vec<(f64, f64)> = if complete.len() > MAX_THEIL_SEN_POINTS {
+        (0..MAX_THEIL_SEN_POINTS).map(|i| complete[i * complete.len() / MAX_THEIL_SEN_POINTS])
+                                 .collect()
+    } else {
+        complete.clone()
+    };
+    if points.len() < 2 {
+        return Err("at least 2 complete (x, y) pairs are required".to_string());
+    }
+
+    let mut slopes = Vec::with_capacity(points.len() * (points.len() - 1) / 2);
+    for (i, (x1, y1)) in points.iter().enumerate() {
+        for (x2, y2) in &points[i + 1..] {
+            if x1 != x2 {
+                slopes.push((y2 - y1) / (x2 - x1));
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return Err("x is constant, so the slope is undefined".to_string());
+    }
+    slopes.sort_by(f64::total_cmp);
+    let slope = sorted_quantile(&slopes, 0.5);
+
+    let mut offsets: Vec<f64> = points.iter().map(|(x, y)| y - slope * x).collect();
+    offsets.sort_by(f64::total_cmp);
+    let intercept = sorted_quantile(&offsets, 0.5);
+
+    // Sen (1968): the interval runs from the M1-th to the (M2 + 1)-th ordered slope (1-based).
+    let n = points.len() as f64;
+    let half_width = Z_975 * (n * (n - 1.0) * (2.0 * n + 5.0) / 18.0).sqrt();
+    let count = slopes.len() as f64;
+    let lower_rank = ((count - half_width) / 2.0).round();
+    let upper_rank = ((count + half_width) / 2.0).round();
+    let (lower, upper) = if lower_rank >= 1.0 && upper_rank < count {
+        (Some(slopes[lower_rank as usize - 1]), Some(slopes[upper_rank as usize]))
+    } else {
+        (None, None)
+    };
+
+    let mut notes = vec!["95% 信頼区間は Sen (1968) の方法によります (同順位の補正なし)".to_string()];
+    if dropped > 0 {
+        notes.push(format!("x または y が欠損している {} 行を除外しました", dropped));
+    }
+    if points.len() < complete.len() {
+        notes.push(format!("{} 組のうち等間隔に選んだ {} 組で算出しました",
+                           complete.len(),
+                           points.len()));
+    }
+    if lower.is_none() {
+        notes.push("データが少ないため信頼区間を計算できません".to_string());
+    }
+
+    Ok(ParsedDataTable { headers: vec!["n".to_string(),
+                                       "傾き".to_string(),
+                                       "下限 (95% CI)".to_string(),
+                                       "上限 (95% CI)".to_string(),
+                                       "切片".to_string()],
+                         rows: vec![vec![Value::from(points.len()),
+                                         number_cell(Some(slope)),
+                                         number_cell(lower),
+                                         number_cell(upper),
+                                         number_cell(Some(intercept))]],
+                         note: Some(notes.join(" / ")),
+                         title: Some(format!("Theil-Sen 回帰 ({} ~ {})", y, x)),
+                         column_types: Vec::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::Value;
+
+    use super::{
+        linear_fit,
+        theil_sen,
+        trend_line,
+    };
+
+    #[test]
+    fn perfectly_linear_data_has_known_slope_and_unit_r_squared() {
+        let fit = linear_fit(&[1.0, 2.0, 3.0, 4.0], &[5.0, 7.0, 9.0, 11.0]).unwrap();
+
+        assert!((fit.slope - 2.0).abs() < 1e-12);
+        assert!((fit.intercept - 3.0).abs() < 1e-12);
+        assert!((fit.r_squared.unwrap() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn constant_x_is_an_error() {
+        let err = linear_fit(&[2.0, 2.0, 2.0], &[1.0, 2.0, 3.0]).unwrap_err();
+        assert!(err.contains("constant"));
+    }
+
+    #[test]
+    fn incomplete_pairs_are_dropped() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), vec![Some(0.0), Some(1.0), None, Some(2.0)]);
+        dataset.insert("y".to_string(),
+                       vec![Some(1.0), Some(3.0), Some(100.0), Some(5.0)]);
+
+        let table = trend_line(&dataset, "x", "y").unwrap();
+
+        assert_eq!(table.rows[0][0], Value::from(3));
+        assert_eq!(table.rows[0][5], Value::from(1.0));
+        assert_eq!(table.rows[0][7], Value::from(5.0));
+        assert!(table.note.is_some());
+    }
+
+    #[test]
+    fn an_outlier_barely_moves_the_theil_sen_slope() {
+        let xs: Vec<f64> = (1..=10).map(f64::from).collect();
+        let mut ys: Vec<f64> = xs.iter().map(|x| 2.0 * x + 1.0).collect();
+        ys[9] = 100.0;
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), xs.iter().map(|x| Some(*x)).collect());
+        dataset.insert("y".to_string(), ys.iter().map(|y| Some(*y)).collect());
+
+        let table = theil_sen(&dataset, "x", "y").unwrap();
+        let cell = |index: usize| table.rows[0][index].as_f64().unwrap();
+
+        let ols = linear_fit(&xs, &ys).unwrap();
+        assert!(ols.slope > 6.0, "{}", ols.slope);
+        assert_eq!(cell(1), 2.0);
+        assert_eq!(cell(4), 1.0);
+        assert!(cell(2) <= 2.0 && cell(3) >= 2.0);
+        assert_eq!(table.rows[0][0], Value::from(10));
+    }
+
+    #[test]
+    fn theil_sen_needs_two_distinct_x_values() {
+        let mut dataset = IndexMap::new();
+        dataset.insert("x".to_string(), vec![Some(1.0), Some(1.0), None]);
+        dataset.insert("y".to_string(), vec![Some(1.0), Some(2.0), Some(3.0)]);
+
+        let err = theil_sen(&dataset, "x", "y").unwrap_err();
+        assert!(err.contains("constant"), "{}", err);
+    }
+}
@@ -0,0 +1,11 @@
+pub(crate) mod aggregate;
+pub(crate) mod cast;
+pub(crate) mod categories;
+pub(crate) mod codebook;
+pub(crate) mod compute;
+pub(crate) mod concat;
+pub(crate) mod duplicates;
+pub(crate) mod header_match;
+pub(crate) mod partition;
+pub(crate) mod reshape;
+pub(crate) mod winsorize;
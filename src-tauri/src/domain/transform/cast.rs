@@ -0,0 +1,207 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use crate::domain::input::numeric::{
+    MAX_EXACT_F64_INTEGER,
+    NumericCellContext,
+    parse_numeric_string,
+};
+use crate::domain::input::table::ParsedDataTable;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CastType {
+    Number,
+    String,
+    Boolean,
+}
+
+impl CastType {
+    fn as_str(self) -> &'static str {
+        match self {
+            CastType::Number => "number",
+            CastType::String => "string",
+            CastType::Boolean => "boolean",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CastColumnResult {
+    pub table: ParsedDataTable,
+    /// Non-empty cells that could not be converted and were set to null.
+    pub failed_count: usize,
+}
+
+/// Converts one column of an already-parsed table to `to_type`.
+/// Null and blank cells stay null. A cell that cannot be converted becomes null and is counted,
+/// or fails the whole cast when `strict` is set.
+pub(crate) fn cast_column(table: &ParsedDataTable,
+                          column: &str,
+                          to_type: CastType,
+                          strict: bool)
+                          -> Result<CastColumnResult, String> {
+    let col_index = table.column_index(column)?;
+
+    let mut cast = table.clone();
+    let mut failed_count = 0;
+    for (row_index, row) in cast.rows.iter_mut().enumerate() {
+        let Some(cell) = row.get_mut(col_index) else {
+            continue;
+        };
+        match cast_cell(cell, to_type, column) {
+            Some(value) => *cell = value,
+            None if strict => {
+                return Err(format!("rows[{}] value {} in column '{}' cannot be converted to {}",
+                                   row_index,
+                                   cell,
+                                   column,
+                                   to_type.as_str()));
+            },
+            None => {
+                *cell = Value::Null;
+                failed_count += 1;
+            },
+        }
+    }
+    cast.refresh_column_types();
+
+    Ok(CastColumnResult { table: cast,
+                          failed_count })
+}
+
+/// `None` means the cell has a value that cannot be converted.
+fn cast_cell(cell: &Value,
+             to_type: CastType,
+             header: &str)
+             -> Option<Value> {
+    if is_blank(cell) {
+        return Some(Value::Null);
+    }
+    match to_type {
+        CastType::Number => to_number(cell, header),
+        CastType::String => Some(Value::String(to_text(cell))),
+        CastType::Boolean => to_boolean(cell),
+    }
+}
+
+fn is_blank(cell: &Value) -> bool {
+    match cell {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+fn to_number(cell: &Value,
+             header: &str)
+             -> Option<Value> {
+    match cell {
+        Value::Number(_) => Some(cell.clone()),
+        Value::Bool(value) => Some(Value::from(u8::from(*value))),
+        Value::String(text) => {
+            let number = parse_numeric_string(text, NumericCellContext::new(0, 0, header)).ok()??;
+            Some(number_value(number))
+        },
+        _ => None,
+    }
+}
+
+/// Whole numbers within the exact f64 range are emitted as integers, as the readers do.
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() <= MAX_EXACT_F64_INTEGER as f64 {
+        Value::from(number as i64)
+    } else {
+        Value::from(number)
+    }
+}
+
+fn to_text(cell: &Value) -> String {
+    match cell {
+        Value::String(text) => text.clone(),
+        Value::Bool(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn to_boolean(cell: &Value) -> Option<Value> {
+    match cell {
+        Value::Bool(_) => Some(cell.clone()),
+        Value::Number(number) => match number.as_f64() {
+            Some(1.0) => Some(Value::Bool(true)),
+            Some(0.0) => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Value::String(text) => match text.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Some(Value::Bool(true)),
+            "false" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        CastType,
+        cast_column,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(rows: Vec<Vec<Value>>) -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["id".to_string(), "score".to_string()],
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn string_to_number_nulls_and_counts_unconvertible_cells() {
+        let source = table(vec![vec![Value::from(1), Value::from(" 12 ")],
+                                vec![Value::from(2), Value::from("3.5")],
+                                vec![Value::from(3), Value::from("n/a")],
+                                vec![Value::from(4), Value::from("")],
+                                vec![Value::from(5), Value::from("abc")]]);
+
+        let result = cast_column(&source, "score", CastType::Number, false).unwrap();
+
+        let scores: Vec<&Value> = result.table.rows.iter().map(|row| &row[1]).collect();
+        assert_eq!(scores,
+                   vec![&Value::from(12),
+                        &Value::from(3.5),
+                        &Value::Null,
+                        &Value::Null,
+                        &Value::Null]);
+        assert_eq!(result.failed_count, 2);
+        assert!(result.table.validate().is_ok());
+
+        assert!(cast_column(&source, "score", CastType::Number, true).is_err());
+    }
+
+    #[test]
+    fn number_to_string_keeps_the_rendered_value() {
+        let source = table(vec![vec![Value::from(1), Value::from(1.25)],
+                                vec![Value::from(2), Value::Null],
+                                vec![Value::from(3), Value::from(true)]]);
+
+        let result = cast_column(&source, "id", CastType::String, true).unwrap();
+
+        let ids: Vec<&Value> = result.table.rows.iter().map(|row| &row[0]).collect();
+        assert_eq!(ids, vec![&Value::from("1"), &Value::from("2"), &Value::from("3")]);
+        assert_eq!(result.table.rows[0][1], Value::from(1.25));
+        assert_eq!(result.failed_count, 0);
+    }
+
+    #[test]
+    fn unknown_column_is_rejected() {
+        assert!(cast_column(&table(vec![]), "missing", CastType::Boolean, false).is_err());
+    }
+}
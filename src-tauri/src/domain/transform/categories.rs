@@ -0,0 +1,180 @@
+use indexmap::IndexMap;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CategoryStrategy {
+    Lowercase,
+    Uppercase,
+    Titlecase,
+    Trim,
+}
+
+impl CategoryStrategy {
+    /// Canonical spelling of a level. Every strategy trims surrounding whitespace.
+    fn apply(self,
+             text: &str)
+             -> String {
+        let trimmed = text.trim();
+        match self {
+            CategoryStrategy::Lowercase => trimmed.to_lowercase(),
+            CategoryStrategy::Uppercase => trimmed.to_uppercase(),
+            CategoryStrategy::Titlecase => {
+                let mut titled = String::with_capacity(trimmed.len());
+                let mut word_start = true;
+                for c in trimmed.chars() {
+                    if word_start {
+                        titled.extend(c.to_uppercase());
+                    } else {
+                        titled.extend(c.to_lowercase());
+                    }
+                    word_start = c.is_whitespace();
+                }
+                titled
+            },
+            CategoryStrategy::Trim => trimmed.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NormalizedCategories {
+    pub table: ParsedDataTable,
+    /// Original spelling, merged level and cell count for every spelling that was rewritten or
+    /// shares its level with another spelling.
+    pub mapping: ParsedDataTable,
+}
+
+/// Rewrites the text cells of `column` to their canonical spelling under `strategy`, so that
+/// variants such as "Male", "male" and " MALE " become one level. Numbers, booleans and nulls are
+/// left as they are.
+pub(crate) fn normalize_categories(table: &ParsedDataTable,
+                                   column: &str,
+                                   strategy: CategoryStrategy)
+                                   -> Result<NormalizedCategories, String> {
+    let col_index = table.column_index(column)?;
+
+    let mut normalized = table.clone();
+    // original spelling -> (level, cells)
+    let mut variants: IndexMap<String, (String, usize)> = IndexMap::new();
+    for row in &mut normalized.rows {
+        let Some(Value::String(text)) = row.get_mut(col_index) else {
+            continue;
+        };
+        let level = strategy.apply(text);
+        let entry = variants.entry(text.clone()).or_insert_with(|| (level.clone(), 0));
+        entry.1 += 1;
+        *text = level;
+    }
+
+    let mut spellings_per_level: IndexMap<&str, usize> = IndexMap::new();
+    for (level, _) in variants.values() {
+        *spellings_per_level.entry(level.as_str()).or_default() += 1;
+    }
+    let mapping_rows: Vec<Vec<Value>> =
+        variants.iter()
+                .filter(|(original, (level, _))| {
+                    *original != level || spellings_per_level[level.as_str()] > 1
+                })
+                .map(|(original, (level, count))| {
+                    vec![Value::from(original.as_str()),
+                         Value::from(level.as_str()),
+                         Value::from(*count)]
+                })
+                .collect();
+
+    let merged_levels = spellings_per_level.values()
+                                           .filter(|spellings| **spellings > 1)
+                                           .count();
+    let merge_note = (merged_levels > 0).then(|| {
+                         format!("{}: 表記ゆれのある {} 水準を統一しました (異なる表記 {} 種類 → {} 水準)",
+                                 column,
+                                 merged_levels,
+                                 variants.len(),
+                                 spellings_per_level.len())
+                     });
+    normalized.note = join_notes([table.note.clone(), merge_note]);
+    normalized.refresh_column_types();
+
+    Ok(NormalizedCategories { table: normalized,
+                              mapping: ParsedDataTable { headers: vec!["元の値".to_string(),
+                                                                       "統一後の値".to_string(),
+                                                                       "件数".to_string()],
+                                                         rows: mapping_rows,
+                                                         note: None,
+                                                         title: Some(format!("水準の統一 ({})",
+                                                                             column)),
+                                                         column_types: Vec::new() } })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        CategoryStrategy,
+        normalize_categories,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(cells: &[Value]) -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["sex".to_string()],
+                          rows: cells.iter().map(|cell| vec![cell.clone()]).collect(),
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn casing_variants_collapse_to_one_level_with_the_mapping_reported() {
+        let cells = [Value::from("Male"),
+                     Value::from("male"),
+                     Value::from(" MALE "),
+                     Value::from("male"),
+                     Value::from("female"),
+                     Value::Null];
+
+        let result = normalize_categories(&table(&cells), "sex", CategoryStrategy::Lowercase).unwrap();
+
+        let levels: Vec<&Value> = result.table.rows.iter().map(|row| &row[0]).collect();
+        assert_eq!(levels,
+                   vec![&Value::from("male"),
+                        &Value::from("male"),
+                        &Value::from("male"),
+                        &Value::from("male"),
+                        &Value::from("female"),
+                        &Value::Null]);
+        assert_eq!(result.mapping.rows,
+                   vec![vec![Value::from("Male"), Value::from("male"), Value::from(1)],
+                        vec![Value::from("male"), Value::from("male"), Value::from(2)],
+                        vec![Value::from(" MALE "), Value::from("male"), Value::from(1)]]);
+        assert!(result.table.note.unwrap().contains("異なる表記 4 種類 → 2 水準"));
+    }
+
+    #[test]
+    fn titlecase_and_trim_keep_other_cells() {
+        let cells = [Value::from("new  york "),
+                     Value::from("NEW  YORK"),
+                     Value::from(3)];
+
+        let titled = normalize_categories(&table(&cells), "sex", CategoryStrategy::Titlecase).unwrap();
+        assert_eq!(titled.table.rows[0][0], Value::from("New  York"));
+        assert_eq!(titled.table.rows[1][0], Value::from("New  York"));
+        assert_eq!(titled.table.rows[2][0], Value::from(3));
+
+        let trimmed = normalize_categories(&table(&cells), "sex", CategoryStrategy::Trim).unwrap();
+        assert_eq!(trimmed.table.rows[0][0], Value::from("new  york"));
+        assert_eq!(trimmed.mapping.rows.len(), 1);
+        assert!(trimmed.table.note.is_none());
+    }
+}
@@ -0,0 +1,186 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::metadata::cell_text;
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodedTable {
+    pub table: ParsedDataTable,
+    /// Column, value and cell count of every coded value the codebook has no label for.
+    pub unmapped: ParsedDataTable,
+}
+
+/// Replaces coded values with their labels, following `codebook`.
+///
+/// The codebook's first three columns are read as column name, code and label, whatever their
+/// headers. Codes match cells by value, so the number `1` and the text `"1"` (or `" 1 "`) are the
+/// same code. Cells without a label, including every cell of a column the codebook does not
+/// mention, are left as they are; those in codebook columns are reported in `unmapped`. Null and
+/// blank cells are never relabeled.
+pub(crate) fn apply_codebook(table: &ParsedDataTable,
+                             codebook: &ParsedDataTable)
+                             -> Result<CodedTable, String> {
+    if codebook.headers.len() < 3 {
+        return Err("codebook needs three columns: column, code and label".to_string());
+    }
+
+    // column -> code -> label
+    let mut labels: IndexMap<String, IndexMap<String, String>> = IndexMap::new();
+    for (row_index, row) in codebook.rows.iter().enumerate() {
+        let (Some(column), Some(code), Some(label)) =
+            (cell_at(row, 0), row.get(1).and_then(code_key), cell_at(row, 2))
+        else {
+            if row.iter().take(3).all(|cell| cell_text(Some(cell)).is_none()) {
+                continue;
+            }
+            return Err(format!("codebook row {} needs a column, a code and a label",
+                               row_index + 1));
+        };
+        let codes = labels.entry(column.clone()).or_default();
+        if let Some(existing) = codes.get(&code).filter(|existing| **existing != label) {
+            return Err(format!("code '{}' of column '{}' has two labels: '{}' and '{}'",
+                               code, column, existing, label));
+        }
+        codes.insert(code, label);
+    }
+
+    let mut coded = table.clone();
+    let mut unmapped: IndexMap<(String, String), usize> = IndexMap::new();
+    let mut relabeled = 0usize;
+    let mut missing_columns = Vec::new();
+    for (column, codes) in &labels {
+        let Ok(col_index) = table.column_index(column) else {
+            missing_columns.push(column.as_str());
+            continue;
+        };
+        for row in &mut coded.rows {
+            let Some(cell) = row.get_mut(col_index) else {
+                continue;
+            };
+            let Some(code) = code_key(cell) else {
+                continue;
+            };
+            match codes.get(&code) {
+                Some(label) => {
+                    *cell = Value::String(label.clone());
+                    relabeled += 1;
+                },
+                None => *unmapped.entry((column.clone(), code)).or_default() += 1,
+            }
+        }
+    }
+
+    let relabel_note = Some(format!("コードブックにより {} 個の値をラベルに置き換えました", relabeled));
+    let unmapped_note = (!unmapped.is_empty()).then(|| {
+                                                  format!("ラベルのないコード {} 種類はそのまま残しました",
+                                                          unmapped.len())
+                                              });
+    let missing_note = (!missing_columns.is_empty()).then(|| {
+                                                        format!("データにない列はスキップしました: {}",
+                                                                missing_columns.join(", "))
+                                                    });
+    coded.note = join_notes([table.note.clone(), relabel_note, unmapped_note, missing_note]);
+    coded.refresh_column_types();
+
+    Ok(CodedTable { table: coded,
+                    unmapped: ParsedDataTable { headers: vec!["列".to_string(),
+                                                              "値".to_string(),
+                                                              "件数".to_string()],
+                                                rows: unmapped.into_iter()
+                                                              .map(|((column, code), count)| {
+                                                                  vec![Value::from(column),
+                                                                       Value::from(code),
+                                                                       Value::from(count)]
+                                                              })
+                                                              .collect(),
+                                                note: None,
+                                                title: Some("ラベルのないコード".to_string()),
+                                                column_types: Vec::new() } })
+}
+
+fn cell_at(row: &[Value],
+           index: usize)
+           -> Option<String> {
+    cell_text(row.get(index))
+}
+
+/// Comparison key of a code: numbers and numeric text share one spelling, so `1`, `1.0` and
+/// `"1"` are the same code.
+fn code_key(cell: &Value) -> Option<String> {
+    let text = cell_text(Some(cell))?;
+    match text.parse::<f64>() {
+        Ok(number) if number.is_finite() && number.fract() == 0.0 && number.abs() < 1e15 => {
+            Some(format!("{}", number as i64))
+        },
+        Ok(number) if number.is_finite() => Some(number.to_string()),
+        _ => Some(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::apply_codebook;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(headers: &[&str],
+             rows: Vec<Vec<Value>>)
+             -> ParsedDataTable {
+        ParsedDataTable { headers: headers.iter().map(|header| header.to_string()).collect(),
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    fn codebook() -> ParsedDataTable {
+        table(&["変数", "コード", "ラベル"],
+              vec![vec![Value::from("sex"), Value::from(1), Value::from("男性")],
+                   vec![Value::from("sex"), Value::from("2"), Value::from("女性")],
+                   vec![Value::from("region"), Value::from(1), Value::from("北部")]])
+    }
+
+    #[test]
+    fn coded_values_are_replaced_and_unmapped_codes_reported() {
+        let data = table(&["id", "sex", "score"],
+                         vec![vec![Value::from(1), Value::from(1), Value::from(1)],
+                              vec![Value::from(2), Value::from(" 2 "), Value::from(2)],
+                              vec![Value::from(3), Value::from(9), Value::from(3)],
+                              vec![Value::from(4), Value::from(2.0), Value::Null],
+                              vec![Value::from(5), Value::Null, Value::from(5)]]);
+
+        let result = apply_codebook(&data, &codebook()).unwrap();
+
+        let sex: Vec<&Value> = result.table.rows.iter().map(|row| &row[1]).collect();
+        assert_eq!(sex,
+                   vec![&Value::from("男性"),
+                        &Value::from("女性"),
+                        &Value::from(9),
+                        &Value::from("女性"),
+                        &Value::Null]);
+        // Columns outside the codebook keep their codes.
+        assert_eq!(result.table.rows[0][2], Value::from(1));
+        assert_eq!(result.unmapped.rows,
+                   vec![vec![Value::from("sex"), Value::from("9"), Value::from(1)]]);
+        let note = result.table.note.unwrap();
+        assert!(note.contains("3 個の値"), "{}", note);
+        assert!(note.contains("スキップしました: region"), "{}", note);
+    }
+
+    #[test]
+    fn conflicting_labels_are_rejected() {
+        let mut conflicting = codebook();
+        conflicting.rows
+                   .push(vec![Value::from("sex"), Value::from("1"), Value::from("male")]);
+
+        let err = apply_codebook(&table(&["sex"], vec![]), &conflicting).unwrap_err();
+        assert!(err.contains("two labels"), "{}", err);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::domain::input::table::{
+    ParsedDataTable,
+    column_values,
+    join_notes,
+    number_cell,
+};
+
+/// Longest expression accepted, in tokens. Parsing and evaluation recurse along the expression
+/// tree, whose depth grows with the token count (`a + a + ...` chains to the left), so the cap
+/// keeps both within the stack.
+const MAX_EXPRESSION_TOKENS: usize = 1000;
+/// Deepest nesting of parentheses and unary minus accepted.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// Appends a column `name` computed row by row from `expr`.
+///
+/// The grammar is arithmetic over numeric columns:
+/// `expr := term (("+" | "-") term)*`, `term := factor (("*" | "/") factor)*`,
+/// `factor := "-" factor | number | column | "(" expr ")"`.
+/// A column is written as a bare name (letters, digits and `_`, not starting with a digit) or,
+/// for any other name, between backticks (e.g. `` `score (%)` ``).
+/// A row is null when a referenced cell is missing, when it divides by zero, or when the result is
+/// not finite. Division by zero is counted in the note.
+/// Expressions longer than `MAX_EXPRESSION_TOKENS` tokens or nested deeper than
+/// `MAX_NESTING_DEPTH` are rejected.
+pub(crate) fn add_computed_column(table: &ParsedDataTable,
+                                  name: &str,
+                                  expr: &str)
+                                  -> Result<ParsedDataTable, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("column name is empty".to_string());
+    }
+    if table.headers.iter().any(|header| header == name) {
+        return Err(format!("column '{}' already exists", name));
+    }
+
+    let tokens = tokenize(expr)?;
+    if tokens.len() > MAX_EXPRESSION_TOKENS {
+        return Err(format!("expression is too long ({} tokens; at most {})",
+                           tokens.len(),
+                           MAX_EXPRESSION_TOKENS));
+    }
+    let mut parser = Parser { tokens: &tokens,
+                              position: 0,
+                              depth: 0,
+                              table };
+    let parsed = parser.expression()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {} in expression", token.describe()));
+    }
+
+    let mut columns: Vec<(usize, Vec<Option<f64>>)> = Vec::new();
+    for col_index in parsed.columns() {
+        if columns.iter().all(|(index, _)| *index != col_index) {
+            let values = column_values(table, col_index, &table.headers[col_index])?;
+            columns.push((col_index, values));
+        }
+    }
+
+    let mut computed = table.clone();
+    computed.headers.push(name.to_string());
+    let mut divided_by_zero = 0usize;
+    for (row_index, row) in computed.rows.iter_mut().enumerate() {
+        let value = parsed.evaluate(&|col_index| {
+                              columns.iter()
+                                     .find(|(index, _)| *index == col_index)
+                                     .and_then(|(_, values)| values[row_index])
+                          });
+        let value = match value {
+            Ok(value) => value.filter(|value| value.is_finite()),
+            Err(DivisionByZero) => {
+                divided_by_zero += 1;
+                None
+            },
+        };
+        row.push(number_cell(value));
+    }
+
+    let zero_note = (divided_by_zero > 0).then(|| {
+                                             format!("{}: ゼロ除算となった {} 行を欠損としました",
+                                                     name, divided_by_zero)
+                                         });
+    computed.note = join_notes([table.note.clone(), zero_note]);
+    computed.refresh_column_types();
+    Ok(computed)
+}
+
+#[derive(Debug)]
+enum Token {
+    Number(f64),
+    Column(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Number(number) => format!("number {}", number),
+            Token::Column(column) => format!("column '{}'", column),
+            Token::Operator(operator) => format!("'{}'", operator),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if matches!(c, '+' | '-' | '*' | '/') {
+            chars.next();
+            tokens.push(Token::Operator(c));
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::Open);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::Close);
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            let text = &expr[start..end];
+            let number =
+                text.parse::<f64>()
+                    .map_err(|_| format!("invalid number '{}' at position {}", text, start + 1))?;
+            tokens.push(Token::Number(number));
+        } else if c == '`' {
+            chars.next();
+            let mut column = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '`')) => break,
+                    Some((_, c)) => column.push(c),
+                    None => return Err(format!("unclosed '`' at position {}", start + 1)),
+                }
+            }
+            tokens.push(Token::Column(column));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut column = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                column.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Column(column));
+        } else {
+            return Err(format!("character '{}' at position {} is not allowed",
+                               c,
+                               start + 1));
+        }
+    }
+    Ok(tokens)
+}
+
+enum Expr {
+    Number(f64),
+    Column(usize),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+struct DivisionByZero;
+
+impl Expr {
+    fn columns(&self) -> Vec<usize> {
+        match self {
+            Expr::Number(_) => Vec::new(),
+            Expr::Column(col_index) => vec![*col_index],
+            Expr::Negate(operand) => operand.columns(),
+            Expr::Binary(_, left, right) => {
+                let mut columns = left.columns();
+                columns.extend(right.columns());
+                columns
+            },
+        }
+    }
+
+    /// `Ok(None)` when a referenced cell is missing.
+    fn evaluate(&self,
+                cell: &dyn Fn(usize) -> Option<f64>)
+                -> Result<Option<f64>, DivisionByZero> {
+        Ok(match self {
+            Expr::Number(number) => Some(*number),
+            Expr::Column(col_index) => cell(*col_index),
+            Expr::Negate(operand) => operand.evaluate(cell)?.map(|value| -value),
+            Expr::Binary(operator, left, right) => {
+                let (Some(left), Some(right)) = (left.evaluate(cell)?, right.evaluate(cell)?) else {
+                    return Ok(None);
+                };
+                Some(match operator {
+                         '+' => left + right,
+                         '-' => left - right,
+                         '*' => left * right,
+                         _ if right == 0.0 => return Err(DivisionByZero),
+                         _ => left / right,
+                     })
+            },
+        })
+    }
+}
+
+/// Recursive-descent parser over the tokens; column names are resolved against the table headers.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    /// Open parentheses and unary minus around the factor being parsed.
+    depth: usize,
+    table: &'a ParsedDataTable,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<&Token, String> {
+        let token = self.tokens
+                        .get(self.position)
+                        .ok_or_else(|| "expression ended unexpectedly".to_string())?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek() {
+            let operator = *operator;
+            self.position += 1;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.factor()?;
+        while let Some(Token::Operator(operator @ ('*' | '/'))) = self.peek() {
+            let operator = *operator;
+            self.position += 1;
+            left = Expr::Binary(operator, Box::new(left), Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        let table = self.table;
+        match self.next()? {
+            Token::Operator('-') => Ok(Expr::Negate(Box::new(self.nested(Self::factor)?))),
+            Token::Number(number) => Ok(Expr::Number(*number)),
+            Token::Column(column) => table.column_index(column).map(Expr::Column),
+            Token::Open => {
+                let inner = self.nested(Self::expression)?;
+                match self.next() {
+                    Ok(Token::Close) => Ok(inner),
+                    Ok(token) => Err(format!("expected ')' but found {}", token.describe())),
+                    Err(_) => Err("missing ')' at the end of the expression".to_string()),
+                }
+            },
+            token => Err(format!("unexpected {} in expression", token.describe())),
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, failing past `MAX_NESTING_DEPTH`.
+    fn nested(&mut self,
+              parse: fn(&mut Self) -> Result<Expr, String>)
+              -> Result<Expr, String> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(format!("expression is nested too deeply (at most {} levels)",
+                               MAX_NESTING_DEPTH));
+        }
+        self.depth += 1;
+        let parsed = parse(self);
+        self.depth -= 1;
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::add_computed_column;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table() -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["sales".to_string(), "visits".to_string(), "cost (円)".to_string()],
+                          rows: vec![vec![Value::from(30), Value::from("10"), Value::from(5)],
+                                     vec![Value::from(12), Value::from(0), Value::from(2)],
+                                     vec![Value::Null, Value::from(4), Value::from(1)]],
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn ratio_column_is_appended_with_nulls_for_zero_division_and_missing_cells() {
+        let computed = add_computed_column(&table(), "per_visit", "sales / visits").unwrap();
+
+        assert_eq!(computed.headers.last().unwrap(), "per_visit");
+        let ratios: Vec<&Value> = computed.rows.iter().map(|row| &row[3]).collect();
+        assert_eq!(ratios, vec![&Value::from(3.0), &Value::Null, &Value::Null]);
+        assert!(computed.note
+                        .as_deref()
+                        .unwrap()
+                        .contains("ゼロ除算となった 1 行"));
+        computed.validate().unwrap();
+
+        let margin = add_computed_column(&table(), "margin", "-(`cost (円)` - sales) * 2 + 1").unwrap();
+        assert_eq!(margin.rows[0][3], Value::from(51.0));
+    }
+
+    #[test]
+    fn malformed_or_unsafe_expressions_are_rejected() {
+        for (expr, message) in [("sales / (visits", "missing ')'"),
+                                ("sales * * visits", "unexpected '*'"),
+                                ("sales visits", "unexpected column 'visits'"),
+                                ("profit / sales", "column 'profit' is not in the dataset"),
+                                ("sales; drop", "character ';' at position 6 is not allowed"),
+                                ("", "expression ended unexpectedly")]
+        {
+            let err = add_computed_column(&table(), "x", expr).unwrap_err();
+            assert!(err.contains(message), "{}: {}", expr, err);
+        }
+        assert!(add_computed_column(&table(), "sales", "visits").is_err());
+    }
+
+    #[test]
+    fn deeply_nested_or_very_long_expressions_are_rejected_without_overflowing() {
+        let parens = format!("{}sales{}", "(".repeat(100_000), ")".repeat(100_000));
+        let negations = format!("{}sales", "-".repeat(100_000));
+        let chain = vec!["sales"; 100_000].join(" + ");
+        for expr in [&parens, &negations, &chain] {
+            let err = add_computed_column(&table(), "x", expr).unwrap_err();
+            assert!(err.contains("too long"), "{}", err);
+        }
+
+        let err = add_computed_column(&table(), "x", &format!("{}sales", "-".repeat(65))).unwrap_err();
+        assert!(err.contains("nested too deeply"), "{}", err);
+        let computed = add_computed_column(&table(), "x", &format!("{}sales", "-".repeat(64))).unwrap();
+        assert_eq!(computed.rows[0][3], Value::from(30.0));
+        let nested = format!("{}sales{}", "(".repeat(64), ")".repeat(64));
+        assert!(add_computed_column(&table(), "x", &nested).is_ok());
+    }
+}
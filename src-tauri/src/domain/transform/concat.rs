@@ -0,0 +1,156 @@
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    infer_column_types,
+};
+
+/// Name of the optional column recording which file each row came from.
+pub(crate) const SOURCE_FILE_COLUMN: &str = "source_file";
+
+/// Row-binds tables that share the same columns, in the order given.
+/// Each entry is `(label, table)`, where the label identifies the file in error messages and in
+/// the optional `source_file` column. Headers are compared after trimming surrounding
+/// whitespace; the trimmed headers of the first table become the output headers.
+pub(crate) fn concat_tables(tables: Vec<(String, ParsedDataTable)>,
+                            add_source_column: bool)
+                            -> Result<ParsedDataTable, String> {
+    let mut tables = tables.into_iter();
+    let Some((first_label, first)) = tables.next() else {
+        return Err("at least one file is required".to_string());
+    };
+    let headers: Vec<String> = first.headers.iter().map(|h| h.trim().to_string()).collect();
+    if add_source_column && headers.iter().any(|h| h == SOURCE_FILE_COLUMN) {
+        return Err(format!("column '{}' already exists in '{}'",
+                           SOURCE_FILE_COLUMN, first_label));
+    }
+
+    let mut file_count = 1usize;
+    let mut rows = with_source(first.rows, &first_label, add_source_column);
+    for (label, table) in tables {
+        let trimmed: Vec<String> = table.headers.iter().map(|h| h.trim().to_string()).collect();
+        if trimmed != headers {
+            return Err(header_mismatch(&first_label, &headers, &label, &trimmed));
+        }
+        rows.extend(with_source(table.rows, &label, add_source_column));
+        file_count += 1;
+    }
+
+    let mut headers = headers;
+    if add_source_column {
+        headers.push(SOURCE_FILE_COLUMN.to_string());
+    }
+    let column_types = infer_column_types(headers.len(), &rows);
+    Ok(ParsedDataTable { headers,
+                         note: Some(format!("{} 個のファイルを結合しました ({} 行)",
+                                            file_count,
+                                            rows.len())),
+                         rows,
+                         title: None,
+                         column_types })
+}
+
+fn with_source(rows: Vec<Vec<Value>>,
+               label: &str,
+               add_source_column: bool)
+               -> Vec<Vec<Value>> {
+    if !add_source_column {
+        return rows;
+    }
+    rows.into_iter()
+        .map(|mut row| {
+            row.push(Value::String(label.to_string()));
+            row
+        })
+        .collect()
+}
+
+fn header_mismatch(expected_label: &str,
+                   expected: &[String],
+                   label: &str,
+                   actual: &[String])
+                   -> String {
+    let missing: Vec<&str> = expected.iter()
+                                     .filter(|h| !actual.contains(h))
+                                     .map(String::as_str)
+                                     .collect();
+    let unexpected: Vec<&str> = actual.iter()
+                                      .filter(|h| !expected.contains(h))
+                                      .map(String::as_str)
+                                      .collect();
+    let mut details = Vec::new();
+    if !missing.is_empty() {
+        details.push(format!("missing [{}]", missing.join(", ")));
+    }
+    if !unexpected.is_empty() {
+        details.push(format!("unexpected [{}]", unexpected.join(", ")));
+    }
+    if details.is_empty() {
+        details.push(format!("column order differs: [{}]", actual.join(", ")));
+    }
+    format!("headers of '{}' do not match '{}': {}",
+            label,
+            expected_label,
+            details.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::concat_tables;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(headers: &[&str],
+             rows: Vec<Vec<Value>>)
+             -> ParsedDataTable {
+        ParsedDataTable { headers: headers.iter().map(|h| h.to_string()).collect(),
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn matching_files_are_row_bound_with_their_source() {
+        let jan = table(&["date", "sales"], vec![vec!["01-01".into(), 10.into()]]);
+        let feb = table(&[" date", "sales "],
+                        vec![vec!["02-01".into(), 12.into()], vec!["02-02".into(), Value::Null]]);
+
+        let merged = concat_tables(vec![("jan.csv".to_string(), jan), ("feb.csv".to_string(), feb)],
+                                   true).unwrap();
+
+        assert_eq!(merged.headers, vec!["date", "sales", "source_file"]);
+        assert_eq!(merged.rows.len(), 3);
+        assert_eq!(merged.rows[0],
+                   vec![Value::from("01-01"), Value::from(10), Value::from("jan.csv")]);
+        assert_eq!(merged.rows[2],
+                   vec![Value::from("02-02"), Value::Null, Value::from("feb.csv")]);
+        merged.validate().unwrap();
+    }
+
+    #[test]
+    fn mismatched_file_is_named_with_its_differing_columns() {
+        let jan = table(&["date", "sales"], vec![]);
+        let feb = table(&["date", "sales"], vec![]);
+        let mar = table(&["date", "revenue"], vec![]);
+
+        let err = concat_tables(vec![("jan.csv".to_string(), jan),
+                                     ("feb.csv".to_string(), feb),
+                                     ("mar.csv".to_string(), mar)],
+                                false).unwrap_err();
+
+        assert!(err.contains("'mar.csv'"), "{}", err);
+        assert!(err.contains("missing [sales]"), "{}", err);
+        assert!(err.contains("unexpected [revenue]"), "{}", err);
+    }
+
+    #[test]
+    fn reordered_columns_are_a_mismatch() {
+        let a = table(&["x", "y"], vec![]);
+        let b = table(&["y", "x"], vec![]);
+
+        let err = concat_tables(vec![("a.csv".to_string(), a), ("b.csv".to_string(), b)], false).unwrap_err();
+        assert!(err.contains("column order differs"), "{}", err);
+    }
+}
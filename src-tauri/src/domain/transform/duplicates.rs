@@ -0,0 +1,268 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+
+/// Only the first this many columns are combined into two-column key candidates, which bounds the
+/// search to about 1,200 pairs however wide the table is.
+pub(crate) const MAX_KEY_PAIR_COLUMNS: usize = 50;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DuplicateRows {
+    /// 0-based indices (into `rows`) of rows that repeat an earlier row, in ascending order.
+    pub duplicate_row_indices: Vec<usize>,
+    /// Number of distinct keys that occur more than once.
+    pub duplicate_group_count: usize,
+}
+
+/// Finds rows whose key cells equal those of an earlier row.
+/// The key is `key_columns` when given (and non-empty), otherwise every column.
+/// Strings are compared after trimming, blank strings equal null, and numbers compare by value (`1` == `1.0`).
+pub(crate) fn find_duplicate_rows(table: &ParsedDataTable,
+                                  key_columns: Option<&[String]>)
+                                  -> Result<DuplicateRows, String> {
+    let key_indices = resolve_key_indices(table, key_columns)?;
+
+    let mut occurrences: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut duplicate_row_indices = Vec::new();
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let key = key_indices.iter()
+                             .map(|&col_index| normalized_key(row.get(col_index)))
+                             .collect();
+        let seen = occurrences.entry(key).or_insert(0);
+        *seen += 1;
+        if *seen > 1 {
+            duplicate_row_indices.push(row_index);
+        }
+    }
+
+    Ok(DuplicateRows { duplicate_row_indices,
+                       duplicate_group_count: occurrences.values().filter(|&&count| count > 1).count() })
+}
+
+/// Lists candidate keys: single columns, then pairs of columns that are not candidates on their
+/// own, whose non-null values (compared as in `find_duplicate_rows`) never repeat. A candidate is
+/// usable as a key only without nulls; a pair has a null when either of its cells is null.
+pub(crate) fn detect_key_columns(table: &ParsedDataTable) -> Result<ParsedDataTable, String> {
+    if table.rows.is_empty() {
+        return Err("the table has no rows".to_string());
+    }
+    let keys: Vec<Vec<Option<String>>> =
+        (0..table.headers.len()).map(|col_index| column_keys(table, col_index))
+                                .collect();
+
+    let mut candidates = Vec::new();
+    let mut single_keys = HashSet::new();
+    for (col_index, column) in keys.iter().enumerate() {
+        if let Some(candidate) = key_candidate(table.headers[col_index].clone(), column.iter().cloned()) {
+            single_keys.insert(col_index);
+            candidates.push(candidate);
+        }
+    }
+    let pair_columns = keys.len().min(MAX_KEY_PAIR_COLUMNS);
+    for first in (0..pair_columns).filter(|index| !single_keys.contains(index)) {
+        for second in (first + 1..pair_columns).filter(|index| !single_keys.contains(index)) {
+            let combined =
+                keys[first].iter()
+                           .zip(&keys[second])
+                           .map(|(a, b)| Some(format!("{}\u{1f}{}", a.as_ref()?, b.as_ref()?)));
+            let label = format!("{} + {}", table.headers[first], table.headers[second]);
+            candidates.extend(key_candidate(label, combined));
+        }
+    }
+
+    let row_count = table.rows.len() as f64;
+    let rows = candidates.into_iter()
+                         .map(|candidate| {
+                             vec![Value::String(candidate.label),
+                                  number_cell(Some(candidate.distinct as f64 / row_count)),
+                                  Value::from(candidate.nulls),
+                                  Value::Bool(candidate.nulls == 0)]
+                         })
+                         .collect::<Vec<_>>();
+
+    let empty_note = rows.is_empty()
+                         .then(|| "値が重複しない列・2 列の組み合わせはありません".to_string());
+    let bound_note = (keys.len() > MAX_KEY_PAIR_COLUMNS).then(|| {
+                         format!("列数が {} を超えるため、2 列の組み合わせは先頭 {} 列の中だけで探しました",
+                                 MAX_KEY_PAIR_COLUMNS, MAX_KEY_PAIR_COLUMNS)
+                     });
+    Ok(ParsedDataTable { headers: vec!["列".to_string(),
+                                       "一意性".to_string(),
+                                       "欠損行数".to_string(),
+                                       "キーとして使用可".to_string()],
+                         rows,
+                         note: join_notes([Some("一意性 = 欠損でない異なる値の数 / 行数。欠損のない候補だけがキーとして使えます".to_string()),
+                                           empty_note,
+                                           bound_note]),
+                         title: Some("キー列の候補".to_string()),
+                         column_types: Vec::new() })
+}
+
+struct KeyCandidate {
+    label: String,
+    distinct: usize,
+    nulls: usize,
+}
+
+/// Normalized cell keys of one column; `None` for null and blank cells.
+fn column_keys(table: &ParsedDataTable,
+               col_index: usize)
+               -> Vec<Option<String>> {
+    table.rows
+         .iter()
+         .map(|row| {
+             let key = normalized_key(row.get(col_index));
+             (key != "null").then_some(key)
+         })
+         .collect()
+}
+
+/// `None` when a non-null key repeats.
+fn key_candidate(label: String,
+                 keys: impl Iterator<Item = Option<String>>)
+                 -> Option<KeyCandidate> {
+    let mut seen = HashSet::new();
+    let mut nulls = 0;
+    for key in keys {
+        match key {
+            Some(key) => {
+                if !seen.insert(key) {
+                    return None;
+                }
+            },
+            None => nulls += 1,
+        }
+    }
+    Some(KeyCandidate { label,
+                        distinct: seen.len(),
+                        nulls })
+}
+
+fn resolve_key_indices(table: &ParsedDataTable,
+                       key_columns: Option<&[String]>)
+                       -> Result<Vec<usize>, String> {
+    match key_columns {
+        Some(columns) if !columns.is_empty() => {
+            columns.iter().map(|column| table.column_index(column)).collect()
+        },
+        _ => Ok((0..table.headers.len()).collect()),
+    }
+}
+
+fn normalized_key(cell: Option<&Value>) -> String {
+    match cell {
+        None | Some(Value::Null) => "null".to_string(),
+        Some(Value::String(value)) => {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                "null".to_string()
+            } else {
+                format!("s:{}", trimmed)
+            }
+        },
+        Some(Value::Number(number)) => match number.as_f64() {
+            Some(value) if value.fract() == 0.0 && value.abs() < 1e15 => format!("n:{}", value as i64),
+            Some(value) => format!("n:{}", value),
+            None => format!("n:{}", number),
+        },
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        detect_key_columns,
+        find_duplicate_rows,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(rows: Vec<Vec<Value>>) -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["id".to_string(), "name".to_string()],
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn full_row_duplicates_are_reported_after_first_occurrence() {
+        let table = table(vec![vec![Value::from(1), Value::from("a")],
+                               vec![Value::from(2), Value::from("b")],
+                               vec![Value::from(1.0), Value::from(" a ")],
+                               vec![Value::from(1), Value::from("a")]]);
+
+        let result = find_duplicate_rows(&table, None).unwrap();
+
+        assert_eq!(result.duplicate_row_indices, vec![2, 3]);
+        assert_eq!(result.duplicate_group_count, 1);
+    }
+
+    #[test]
+    fn key_column_duplicates_ignore_other_columns() {
+        let table = table(vec![vec![Value::from(1), Value::from("a")],
+                               vec![Value::from(1), Value::from("b")],
+                               vec![Value::from(2), Value::from("c")],
+                               vec![Value::from(2), Value::from("d")]]);
+
+        let result = find_duplicate_rows(&table, Some(&["id".to_string()])).unwrap();
+
+        assert_eq!(result.duplicate_row_indices, vec![1, 3]);
+        assert_eq!(result.duplicate_group_count, 2);
+    }
+
+    #[test]
+    fn unknown_key_column_is_rejected() {
+        let table = table(vec![]);
+        assert!(find_duplicate_rows(&table, Some(&["missing".to_string()])).is_err());
+    }
+
+    #[test]
+    fn unique_id_column_is_a_key_and_repeated_columns_are_not() {
+        let table = ParsedDataTable { headers: vec!["id".to_string(),
+                                                    "name".to_string(),
+                                                    "year".to_string(),
+                                                    "code".to_string()],
+                                      rows: vec![vec![Value::from(1),
+                                                      Value::from("a"),
+                                                      Value::from(2020),
+                                                      Value::from("x")],
+                                                 vec![Value::from(2),
+                                                      Value::from("a"),
+                                                      Value::from(2021),
+                                                      Value::Null],
+                                                 vec![Value::from(3),
+                                                      Value::from("b"),
+                                                      Value::from(2020),
+                                                      Value::from("y")]],
+                                      note: None,
+                                      title: None,
+                                      column_types: Vec::new() };
+
+        let keys = detect_key_columns(&table).unwrap();
+
+        let labels: Vec<&Value> = keys.rows.iter().map(|row| &row[0]).collect();
+        assert_eq!(labels,
+                   vec![&Value::from("id"),
+                        &Value::from("code"),
+                        &Value::from("name + year")]);
+        assert_eq!(keys.rows[0][1].as_f64(), Some(1.0));
+        assert_eq!(keys.rows[0][3], Value::Bool(true));
+        // "code" never repeats but has a null, so it is not a usable key.
+        assert_eq!(keys.rows[1][2], Value::from(1));
+        assert_eq!(keys.rows[1][3], Value::Bool(false));
+    }
+}
@@ -0,0 +1,171 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+
+/// Suggested column correspondences between two header sets, for concatenating or joining files
+/// whose headers are spelled slightly differently.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HeaderMatches {
+    /// One row per unambiguous pair: header A, header B and their similarity.
+    pub mappings: ParsedDataTable,
+    /// Every candidate pair of a header that has more than one candidate on the other side.
+    pub ambiguous: ParsedDataTable,
+}
+
+/// Pairs headers of `headers_a` and `headers_b` whose similarity is at least `threshold`.
+///
+/// Headers are compared after lowercasing and keeping only letters and digits, so "Age ", "age"
+/// and "AGE_" are identical. The similarity is the Levenshtein ratio
+/// `1 - distance / max(length)` on characters. A pair is suggested only when each header has
+/// exactly one candidate; otherwise all candidates of the headers involved are listed as
+/// ambiguous, leaving the choice to the user.
+pub(crate) fn match_headers(headers_a: &[String],
+                            headers_b: &[String],
+                            threshold: f64)
+                            -> Result<HeaderMatches, String> {
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(format!("threshold must be in (0, 1]: {}", threshold));
+    }
+    let keys_a: Vec<String> = headers_a.iter().map(|header| match_key(header)).collect();
+    let keys_b: Vec<String> = headers_b.iter().map(|header| match_key(header)).collect();
+
+    // (index in A, index in B, similarity)
+    let mut candidates = Vec::new();
+    for (a, key_a) in keys_a.iter().enumerate() {
+        for (b, key_b) in keys_b.iter().enumerate() {
+            if key_a.is_empty() || key_b.is_empty() {
+                continue;
+            }
+            let similarity = similarity(key_a, key_b);
+            if similarity >= threshold {
+                candidates.push((a, b, similarity));
+            }
+        }
+    }
+    let count_a = |a: usize| candidates.iter().filter(|(other, _, _)| *other == a).count();
+    let count_b = |b: usize| candidates.iter().filter(|(_, other, _)| *other == b).count();
+
+    let mut mappings = Vec::new();
+    let mut ambiguous = Vec::new();
+    for (a, b, similarity) in &candidates {
+        let row = vec![Value::from(headers_a[*a].as_str()),
+                       Value::from(headers_b[*b].as_str()),
+                       number_cell(Some(*similarity))];
+        if count_a(*a) == 1 && count_b(*b) == 1 {
+            mappings.push(row);
+        } else {
+            ambiguous.push(row);
+        }
+    }
+
+    let unmatched_a = (0..headers_a.len()).filter(|a| count_a(*a) == 0).count();
+    let unmatched_b = (0..headers_b.len()).filter(|b| count_b(*b) == 0).count();
+    let unmatched_note = (unmatched_a + unmatched_b > 0).then(|| {
+                                                            format!("候補のない列: A 側 {} 列、B 側 {} 列",
+                                                                    unmatched_a, unmatched_b)
+                                                        });
+    let ambiguous_note = (!ambiguous.is_empty()).then(|| {
+                             "候補が複数ある列は対応を自動で選ばず、「曖昧な対応」に列挙しました".to_string()
+                         });
+    let headers = vec!["列 A".to_string(), "列 B".to_string(), "類似度".to_string()];
+
+    Ok(HeaderMatches { mappings: ParsedDataTable { headers: headers.clone(),
+                                                   rows: mappings,
+                                                   note: join_notes([Some(format!("類似度 {} 以上の列を対応付けました",
+                                                                                  threshold)),
+                                                                     ambiguous_note,
+                                                                     unmatched_note]),
+                                                   title: Some("列名の対応".to_string()),
+                                                   column_types: Vec::new() },
+                       ambiguous: ParsedDataTable { headers,
+                                                    rows: ambiguous,
+                                                    note: None,
+                                                    title: Some("曖昧な対応".to_string()),
+                                                    column_types: Vec::new() } })
+}
+
+/// Lowercased letters and digits of `header`; spaces, punctuation and brackets are dropped.
+fn match_key(header: &str) -> String {
+    header.chars()
+          .filter(|c| c.is_alphanumeric())
+          .flat_map(char::to_lowercase)
+          .collect()
+}
+
+/// Levenshtein ratio of two non-empty strings, in [0, 1].
+fn similarity(a: &str,
+              b: &str)
+              -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[b.len()] as f64 / a.len().max(b.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        match_headers,
+        similarity,
+    };
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn near_duplicate_headers_are_paired() {
+        let result = match_headers(&headers(&["Age ", "Height_cm", "氏名", "id"]),
+                                   &headers(&["age", "height (cm)", "名前", "ID"]),
+                                   0.8).unwrap();
+
+        let pairs: Vec<(&Value, &Value)> = result.mappings
+                                                 .rows
+                                                 .iter()
+                                                 .map(|row| (&row[0], &row[1]))
+                                                 .collect();
+        assert_eq!(pairs,
+                   vec![(&Value::from("Age "), &Value::from("age")),
+                        (&Value::from("Height_cm"), &Value::from("height (cm)")),
+                        (&Value::from("id"), &Value::from("ID"))]);
+        assert_eq!(result.mappings.rows[0][2], Value::from(1.0));
+        assert!(result.ambiguous.rows.is_empty());
+        assert!(result.mappings.note.unwrap().contains("A 側 1 列、B 側 1 列"));
+    }
+
+    #[test]
+    fn several_candidates_are_reported_not_chosen() {
+        let result = match_headers(&headers(&["score1", "name"]),
+                                   &headers(&["score2", "score3", "Name"]),
+                                   0.8).unwrap();
+
+        assert_eq!(result.mappings.rows,
+                   vec![vec![Value::from("name"), Value::from("Name"), Value::from(1.0)]]);
+        assert_eq!(result.ambiguous.rows.len(), 2);
+        assert!(result.ambiguous.rows.iter().all(|row| row[0] == "score1"));
+        assert!(match_headers(&headers(&["a"]), &headers(&["a"]), 0.0).is_err());
+    }
+
+    #[test]
+    fn ratio_counts_edits_over_the_longer_string() {
+        assert_eq!(similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert_eq!(similarity("abc", "abc"), 1.0);
+    }
+}
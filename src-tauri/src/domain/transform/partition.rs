@@ -0,0 +1,162 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+};
+use crate::domain::rng::SplitMix64;
+
+/// Train/test split of a parsed table. Both halves keep the original headers and row order.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct RowPartition {
+    pub train: ParsedDataTable,
+    pub test: ParsedDataTable,
+}
+
+/// Randomly splits the rows into train and test tables.
+/// `test_fraction` must be in (0, 1). With `stratify_by`, rows are split within each level of that
+/// column so level proportions are preserved; a level with a single row always goes to train.
+pub(crate) fn partition_rows(table: &ParsedDataTable,
+                             test_fraction: f64,
+                             seed: u64,
+                             stratify_by: Option<&str>)
+                             -> Result<RowPartition, String> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(format!("test_fraction must be between 0 and 1 (exclusive): {}",
+                           test_fraction));
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut test_rows = Vec::new();
+    let mut note = None;
+
+    match stratify_by {
+        None => {
+            let mut indices: Vec<usize> = (0..table.rows.len()).collect();
+            rng.shuffle(&mut indices);
+            let test_count = (indices.len() as f64 * test_fraction).round() as usize;
+            test_rows.extend_from_slice(&indices[..test_count]);
+        },
+        Some(column) => {
+            let col_index = table.column_index(column)?;
+            let mut singletons = Vec::new();
+            for (level, mut indices) in group_rows_by_level(table, col_index) {
+                if indices.len() == 1 {
+                    singletons.push(level);
+                    continue;
+                }
+                rng.shuffle(&mut indices);
+                let test_count = (indices.len() as f64 * test_fraction).round() as usize;
+                test_rows.extend_from_slice(&indices[..test_count]);
+            }
+            if !singletons.is_empty() {
+                note = Some(format!("1件しかない水準 ({}) は層化できないため学習データに含めました",
+                                    singletons.join(", ")));
+            }
+        },
+    }
+
+    let mut is_test = vec![false; table.rows.len()];
+    for index in test_rows {
+        is_test[index] = true;
+    }
+    let note = join_notes([table.note.clone(), note]);
+    let mut train = Vec::new();
+    let mut test = Vec::new();
+    for (row, is_test) in table.rows.iter().zip(is_test) {
+        if is_test { &mut test } else { &mut train }.push(row.clone());
+    }
+
+    let mut train = ParsedDataTable { headers: table.headers.clone(),
+                                      rows: train,
+                                      note: note.clone(),
+                                      title: Some("学習データ".to_string()),
+                                      column_types: table.column_types.clone() };
+    let mut test = ParsedDataTable { headers: table.headers.clone(),
+                                     rows: test,
+                                     note,
+                                     title: Some("テストデータ".to_string()),
+                                     column_types: table.column_types.clone() };
+    train.refresh_column_types();
+    test.refresh_column_types();
+    Ok(RowPartition { train, test })
+}
+
+fn group_rows_by_level(table: &ParsedDataTable,
+                       col_index: usize)
+                       -> IndexMap<String, Vec<usize>> {
+    let mut groups: IndexMap<String, Vec<usize>> = IndexMap::new();
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let level = match row.get(col_index) {
+            None | Some(Value::Null) => "(欠損)".to_string(),
+            Some(Value::String(value)) => value.clone(),
+            Some(value) => value.to_string(),
+        };
+        groups.entry(level).or_default().push(row_index);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::partition_rows;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table(levels: &[&str]) -> ParsedDataTable {
+        ParsedDataTable { headers: vec!["id".to_string(), "group".to_string()],
+                          rows: levels.iter()
+                                      .enumerate()
+                                      .map(|(index, level)| vec![Value::from(index), Value::from(*level)])
+                                      .collect(),
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    fn count_level(table: &ParsedDataTable,
+                   level: &str)
+                   -> usize {
+        table.rows.iter().filter(|row| row[1] == level).count()
+    }
+
+    #[test]
+    fn seeded_split_is_reproducible() {
+        let source = table(&["a"; 50]);
+        let first = partition_rows(&source, 0.2, 42, None).unwrap();
+        let second = partition_rows(&source, 0.2, 42, None).unwrap();
+
+        assert_eq!(first.test.rows, second.test.rows);
+        assert_eq!(first.test.rows.len(), 10);
+        assert_eq!(first.train.rows.len(), 40);
+    }
+
+    #[test]
+    fn stratification_preserves_level_proportions() {
+        let mut levels = vec!["a"; 80];
+        levels.extend(vec!["b"; 20]);
+        let partition = partition_rows(&table(&levels), 0.25, 7, Some("group")).unwrap();
+
+        let test_share_b = count_level(&partition.test, "b") as f64 / partition.test.rows.len() as f64;
+        assert!((test_share_b - 0.2).abs() < 0.05);
+        assert_eq!(count_level(&partition.test, "a"), 20);
+        assert_eq!(count_level(&partition.test, "b"), 5);
+    }
+
+    #[test]
+    fn singleton_level_goes_to_train_with_note() {
+        let partition = partition_rows(&table(&["a", "a", "a", "a", "solo"]), 0.5, 1, Some("group")).unwrap();
+
+        assert_eq!(count_level(&partition.train, "solo"), 1);
+        assert!(partition.train.note.unwrap().contains("solo"));
+    }
+
+    #[test]
+    fn test_fraction_out_of_range_is_rejected() {
+        assert!(partition_rows(&table(&["a", "b"]), 1.0, 0, None).is_err());
+        assert!(partition_rows(&table(&["a", "b"]), 0.0, 0, None).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::input::table::{
+    ParsedDataTable,
+    join_notes,
+    number_cell,
+};
+
+const VARIABLE_HEADER: &str = "variable";
+const VALUE_HEADER: &str = "value";
+const MISSING_NAME: &str = "(欠損)";
+const BLANK_NAME: &str = "(空白)";
+
+/// What `pivot_wider` does when one id × name cell receives more than one value.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WideCollision {
+    /// Reject the table, naming the first duplicated id × name pair.
+    #[default]
+    Error,
+    /// Keep the first value in row order.
+    First,
+    /// Keep the last value in row order.
+    Last,
+    /// Average the non-null values; every value must be numeric.
+    Mean,
+}
+
+/// Melts a wide table into long format with columns `[id_columns..., "variable", "value"]`.
+/// Each input row yields one output row per value column, in the order the value columns are given.
+/// Null value cells are kept unless `drop_nulls` is set.
+pub(crate) fn pivot_longer(table: &ParsedDataTable,
+                           id_columns: &[String],
+                           value_columns: &[String],
+                           drop_nulls: bool)
+                           -> Result<ParsedDataTable, String> {
+    if value_columns.is_empty() {
+        return Err("value_columns must not be empty".to_string());
+    }
+    if let Some(column) = id_columns.iter().find(|column| value_columns.contains(column)) {
+        return Err(format!("column '{}' cannot be both an id column and a value column",
+                           column));
+    }
+    if let Some(column) = id_columns.iter()
+                                    .find(|column| *column == VARIABLE_HEADER || *column == VALUE_HEADER)
+    {
+        return Err(format!("id column '{}' would collide with the '{}' / '{}' output columns",
+                           column, VARIABLE_HEADER, VALUE_HEADER));
+    }
+    let id_indices = column_indices(table, id_columns)?;
+    let value_indices = column_indices(table, value_columns)?;
+
+    let mut rows = Vec::with_capacity(table.rows.len() * value_columns.len());
+    let mut dropped = 0usize;
+    for row in &table.rows {
+        let ids: Vec<Value> = id_indices.iter()
+                                        .map(|&index| row.get(index).cloned().unwrap_or(Value::Null))
+                                        .collect();
+        for (name, &index) in value_columns.iter().zip(&value_indices) {
+            let value = row.get(index).cloned().unwrap_or(Value::Null);
+            if drop_nulls && value.is_null() {
+                dropped += 1;
+                continue;
+            }
+            let mut long_row = ids.clone();
+            long_row.push(Value::String(name.clone()));
+            long_row.push(value);
+            rows.push(long_row);
+        }
+    }
+
+    let mut headers = id_columns.to_vec();
+    headers.push(VARIABLE_HEADER.to_string());
+    headers.push(VALUE_HEADER.to_string());
+    let dropped_note = (dropped > 0).then(|| format!("値が欠損している {} 行を除外しました", dropped));
+
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: join_notes([table.note.clone(), dropped_note]),
+                         title: table.title.clone(),
+                         column_types: Vec::new() })
+}
+
+/// Spreads a long table into wide format: one row per distinct combination of `id_columns`
+/// (in order of first appearance) and one column per distinct value of `name_column`.
+/// Missing id × name combinations become null.
+///
+/// New column names are the name cells as text, trimmed, with line breaks and other control
+/// characters replaced by a space. Null names become `(欠損)` and blank names `(空白)`.
+/// A name that clashes with an id column or with an earlier name gets a `_2`, `_3`, ... suffix,
+/// so the headers are always unique; renamed columns are listed in the note.
+pub(crate) fn pivot_wider(table: &ParsedDataTable,
+                          id_columns: &[String],
+                          name_column: &str,
+                          value_column: &str,
+                          on_collision: WideCollision)
+                          -> Result<ParsedDataTable, String> {
+    if id_columns.iter()
+                 .any(|column| column == name_column || column == value_column)
+    {
+        return Err("id_columns must not include the name or value column".to_string());
+    }
+    if name_column == value_column {
+        return Err("name_column and value_column must differ".to_string());
+    }
+    let id_indices = column_indices(table, id_columns)?;
+    let name_index = column_indices(table, &[name_column.to_string()])?[0];
+    let value_index = column_indices(table, &[value_column.to_string()])?[0];
+
+    // Keyed by the raw name text so that distinct names are never merged by sanitizing.
+    let mut names: IndexMap<String, usize> = IndexMap::new();
+    let mut groups: IndexMap<String, (Vec<Value>, Vec<Vec<Value>>)> = IndexMap::new();
+    for row in &table.rows {
+        let ids: Vec<Value> = id_indices.iter()
+                                        .map(|&index| row.get(index).cloned().unwrap_or(Value::Null))
+                                        .collect();
+        let name = name_text(row.get(name_index));
+        let next_slot = names.len();
+        let slot = *names.entry(name.clone()).or_insert(next_slot);
+        let value = row.get(value_index).cloned().unwrap_or(Value::Null);
+
+        let key = serde_json::to_string(&ids).expect("JSON values always serialize");
+        let (group_ids, cells) = groups.entry(key.clone()).or_insert_with(|| (ids, Vec::new()));
+        if cells.len() <= slot {
+            cells.resize_with(slot + 1, Vec::new);
+        }
+        if !cells[slot].is_empty() && on_collision == WideCollision::Error {
+            let id_label = if group_ids.is_empty() {
+                "(none)".to_string()
+            } else {
+                key
+            };
+            return Err(format!("multiple values for {} = {} at id {}",
+                               name_column, name, id_label));
+        }
+        cells[slot].push(value);
+    }
+
+    let mut headers = id_columns.to_vec();
+    let mut renamed = Vec::new();
+    for name in names.keys() {
+        let base = sanitize_header(name);
+        let mut header = base.clone();
+        let mut suffix = 2;
+        while headers.contains(&header) {
+            header = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        if header != *name {
+            renamed.push(format!("{} → {}", name, header));
+        }
+        headers.push(header);
+    }
+
+    let mut rows = Vec::with_capacity(groups.len());
+    for (ids, mut cells) in groups.into_values() {
+        cells.resize_with(names.len(), Vec::new);
+        let mut row = ids;
+        for values in cells {
+            row.push(collapse_values(values, on_collision, value_column)?);
+        }
+        rows.push(row);
+    }
+
+    let renamed_note = (!renamed.is_empty()).then(|| format!("列名を変更しました: {}", renamed.join(", ")));
+    Ok(ParsedDataTable { headers,
+                         rows,
+                         note: join_notes([table.note.clone(), renamed_note]),
+                         title: table.title.clone(),
+                         column_types: Vec::new() })
+}
+
+fn name_text(cell: Option<&Value>) -> String {
+    match cell {
+        None | Some(Value::Null) => MISSING_NAME.to_string(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn sanitize_header(name: &str) -> String {
+    let cleaned: String = name.chars()
+                              .map(|c| if c.is_control() { ' ' } else { c })
+                              .collect();
+    let trimmed = cleaned.trim();
+    if trimmed.is_empty() {
+        BLANK_NAME.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn collapse_values(mut values: Vec<Value>,
+                   on_collision: WideCollision,
+                   value_column: &str)
+                   -> Result<Value, String> {
+    if values.len() <= 1 {
+        return Ok(values.pop().unwrap_or(Value::Null));
+    }
+    match on_collision {
+        WideCollision::Error | WideCollision::First => Ok(values.swap_remove(0)),
+        WideCollision::Last => Ok(values.pop().expect("values is not empty")),
+        WideCollision::Mean => {
+            let mut numbers = Vec::with_capacity(values.len());
+            for value in &values {
+                match value {
+                    Value::Null => {},
+                    Value::Number(number) => numbers.push(number.as_f64().expect("JSON numbers are finite")),
+                    other => {
+                        return Err(format!("cannot average non-numeric value {} in column '{}'",
+                                           other, value_column));
+                    },
+                }
+            }
+            let mean = (!numbers.is_empty()).then(|| numbers.iter().sum::<f64>() / numbers.len() as f64);
+            Ok(number_cell(mean))
+        },
+    }
+}
+
+fn column_indices(table: &ParsedDataTable,
+                  columns: &[String])
+                  -> Result<Vec<usize>, String> {
+    columns.iter().map(|column| table.column_index(column)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        WideCollision,
+        pivot_longer,
+        pivot_wider,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn wide() -> ParsedDataTable {
+        ParsedDataTable { headers: ["subject", "group", "t1", "t2", "t3"].iter()
+                                                                         .map(|h| h.to_string())
+                                                                         .collect(),
+                          rows: vec![vec!["s1".into(), "A".into(), 1.into(), 2.into(), 3.into()],
+                                     vec!["s2".into(), "B".into(), 4.into(), Value::Null, 6.into()]],
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    fn names(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn two_id_three_value_table_melts_to_one_row_per_cell() {
+        let long = pivot_longer(&wide(),
+                                &names(&["subject", "group"]),
+                                &names(&["t1", "t2", "t3"]),
+                                false).unwrap();
+
+        assert_eq!(long.headers, names(&["subject", "group", "variable", "value"]));
+        assert_eq!(long.rows.len(), 6);
+        assert_eq!(long.rows[0],
+                   vec![Value::from("s1"), "A".into(), "t1".into(), 1.into()]);
+        assert_eq!(long.rows[2],
+                   vec![Value::from("s1"), "A".into(), "t3".into(), 3.into()]);
+        assert_eq!(long.rows[4],
+                   vec![Value::from("s2"), "B".into(), "t2".into(), Value::Null]);
+        assert!(long.note.is_none());
+    }
+
+    #[test]
+    fn null_values_are_dropped_on_request() {
+        let long = pivot_longer(&wide(),
+                                &names(&["subject", "group"]),
+                                &names(&["t1", "t2", "t3"]),
+                                true).unwrap();
+
+        assert_eq!(long.rows.len(), 5);
+        assert!(long.rows.iter().all(|row| !row[3].is_null()));
+        assert!(long.note.unwrap().contains("1 行"));
+    }
+
+    #[test]
+    fn overlapping_or_unknown_columns_are_rejected() {
+        assert!(pivot_longer(&wide(), &names(&["t1"]), &names(&["t1", "t2"]), false).is_err());
+        let err = pivot_longer(&wide(), &names(&["subject"]), &names(&["t9"]), false).unwrap_err();
+        assert!(err.contains("'t9'"));
+    }
+
+    #[test]
+    fn longer_then_wider_round_trips_to_the_original_table() {
+        let original = wide();
+        let ids = names(&["subject", "group"]);
+        let long = pivot_longer(&original, &ids, &names(&["t1", "t2", "t3"]), false).unwrap();
+
+        let back = pivot_wider(&long, &ids, "variable", "value", WideCollision::Error).unwrap();
+
+        assert_eq!(back.headers, original.headers);
+        assert_eq!(back.rows, original.rows);
+        assert!(back.note.is_none());
+    }
+
+    #[test]
+    fn missing_combinations_are_null_and_collisions_follow_the_policy() {
+        let long = ParsedDataTable { headers: names(&["id", "name", "value"]),
+                                     rows: vec![vec!["a".into(), "x".into(), 1.into()],
+                                                vec!["a".into(), "x".into(), 3.into()],
+                                                vec!["b".into(), "y".into(), 5.into()]],
+                                     note: None,
+                                     title: None,
+                                     column_types: Vec::new() };
+        let ids = names(&["id"]);
+
+        let err = pivot_wider(&long, &ids, "name", "value", WideCollision::Error).unwrap_err();
+        assert!(err.contains("name = x"), "{}", err);
+
+        let mean = pivot_wider(&long, &ids, "name", "value", WideCollision::Mean).unwrap();
+        assert_eq!(mean.rows,
+                   vec![vec![Value::from("a"), 2.0.into(), Value::Null],
+                        vec![Value::from("b"), Value::Null, 5.into()]]);
+
+        let last = pivot_wider(&long, &ids, "name", "value", WideCollision::Last).unwrap();
+        assert_eq!(last.rows[0][1], Value::from(3));
+    }
+
+    #[test]
+    fn clashing_and_illegal_names_are_made_unique() {
+        let long = ParsedDataTable { headers: names(&["id", "name", "value"]),
+                                     rows: vec![vec!["a".into(), "id".into(), 1.into()],
+                                                vec!["a".into(), "line\nbreak".into(), 2.into()],
+                                                vec!["a".into(), Value::Null, 3.into()]],
+                                     note: None,
+                                     title: None,
+                                     column_types: Vec::new() };
+
+        let wide = pivot_wider(&long, &names(&["id"]), "name", "value", WideCollision::Error).unwrap();
+
+        assert_eq!(wide.headers, names(&["id", "id_2", "line break", "(欠損)"]));
+        assert!(wide.note.unwrap().contains("id → id_2"));
+    }
+}
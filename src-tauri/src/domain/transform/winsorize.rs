@@ -0,0 +1,117 @@
+use crate::domain::input::table::{
+    ParsedDataTable,
+    column_values,
+    join_notes,
+    number_cell,
+};
+use crate::domain::statistics::sample::sorted_quantile;
+
+/// Clamps each selected column to its `lower` and `1 - upper` quantiles (R's default type 7).
+/// Both limits are fractions in [0, 0.5) whose sum is below 1. Row order, other columns and
+/// missing cells are left as they are; only cells outside the bounds are replaced, by the bound.
+pub(crate) fn winsorize(table: &ParsedDataTable,
+                        columns: &[String],
+                        lower: f64,
+                        upper: f64)
+                        -> Result<ParsedDataTable, String> {
+    for (name, limit) in [("lower", lower), ("upper", upper)] {
+        if !(0.0..0.5).contains(&limit) {
+            return Err(format!("{} limit must be in [0, 0.5): {}", name, limit));
+        }
+    }
+    if columns.is_empty() {
+        return Err("No columns selected".to_string());
+    }
+
+    let mut cleaned = table.clone();
+    let mut notes = Vec::new();
+    for column in columns {
+        let col_index = table.column_index(column)?;
+        let values = column_values(table, col_index, column)?;
+
+        let mut sorted: Vec<f64> = values.iter().flatten().copied().collect();
+        if sorted.is_empty() {
+            continue;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let low = sorted_quantile(&sorted, lower);
+        let high = sorted_quantile(&sorted, 1.0 - upper);
+
+        let mut clamped = 0usize;
+        for (row, value) in cleaned.rows.iter_mut().zip(&values) {
+            let Some(value) = *value else {
+                continue;
+            };
+            if value < low || value > high {
+                row[col_index] = number_cell(Some(value.clamp(low, high)));
+                clamped += 1;
+            }
+        }
+        if clamped > 0 {
+            notes.push(format!("{}: {} 個の値を [{}, {}] に置き換えました",
+                               column, clamped, low, high));
+        }
+    }
+
+    cleaned.note = join_notes([table.note.clone(), (!notes.is_empty()).then(|| notes.join(" / "))]);
+    cleaned.refresh_column_types();
+    Ok(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::winsorize;
+    use crate::domain::input::table::ParsedDataTable;
+
+    fn table() -> ParsedDataTable {
+        let rows = (1..=10).map(|i| {
+                               let score = if i == 10 { Value::from(100) } else { Value::from(i) };
+                               let raw = if i == 5 {
+                                   Value::Null
+                               } else {
+                                   Value::from(i.to_string())
+                               };
+                               vec![Value::from(format!("r{}", i)), score, raw]
+                           })
+                           .collect();
+        ParsedDataTable { headers: vec!["id".to_string(), "score".to_string(), "raw".to_string()],
+                          rows,
+                          note: None,
+                          title: None,
+                          column_types: Vec::new() }
+    }
+
+    #[test]
+    fn values_beyond_the_limits_are_clamped_to_the_quantiles() {
+        let cleaned = winsorize(&table(), &["score".to_string()], 0.1, 0.1).unwrap();
+
+        // Type 7 quantiles of 1..9, 100: 10% = 1 + 0.9 * (2 - 1), 90% = 9 + 0.1 * (100 - 9)
+        let scores: Vec<Value> = cleaned.rows.iter().map(|row| row[1].clone()).collect();
+        assert!((scores[0].as_f64().unwrap() - 1.9).abs() < 1e-12);
+        assert_eq!(scores[1], Value::from(2));
+        assert_eq!(scores[8], Value::from(9));
+        assert!((scores[9].as_f64().unwrap() - 18.1).abs() < 1e-12);
+        assert_eq!(cleaned.rows[0][0], Value::from("r1"));
+        assert_eq!(cleaned.rows[0][2], Value::from("1"));
+        assert!(cleaned.note.unwrap().contains("score: 2 個"));
+    }
+
+    #[test]
+    fn missing_values_pass_through_numeric_text_columns() {
+        let cleaned = winsorize(&table(), &["raw".to_string()], 0.2, 0.0).unwrap();
+
+        assert_eq!(cleaned.rows[4][2], Value::Null);
+        assert_eq!(cleaned.rows[9][2], Value::from("10"));
+        assert!((cleaned.rows[0][2].as_f64().unwrap() - 2.6).abs() < 1e-12);
+        assert_eq!(cleaned.rows[2][2], Value::from("3"));
+    }
+
+    #[test]
+    fn limits_outside_the_allowed_range_are_rejected() {
+        assert!(winsorize(&table(), &["score".to_string()], 0.5, 0.0).is_err());
+        assert!(winsorize(&table(), &["score".to_string()], -0.1, 0.1).is_err());
+        assert!(winsorize(&table(), &["id".to_string()], 0.1, 0.1).is_err());
+    }
+}
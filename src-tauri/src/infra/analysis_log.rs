@@ -1,2 +1,3 @@
 pub(crate) mod jsonl_repository;
+mod reverse_reader;
 pub(crate) mod session_repository;
@@ -4,10 +4,12 @@ use std::fs::{
     OpenOptions,
 };
 use std::io::{
-    BufRead,
-    BufReader,
+    self,
+    Read,
+    Seek,
     Write,
 };
+use std::ops::ControlFlow;
 use std::path::{
     Path,
     PathBuf,
@@ -26,6 +28,8 @@ use crate::usecase::analysis_log::ports::{
     AnalysisLogWriter,
 };
 
+use super::reverse_reader::ReverseLineReader;
+
 const LOG_FILE_PREFIX: &str = "analysis-log-";
 const LOG_FILE_SUFFIX: &str = ".jsonl";
 
@@ -104,34 +108,82 @@ impl JsonlAnalysisLogRepository {
         Ok(path.clone())
     }
 
-    fn read_records_from_file(path: &Path) -> Result<Vec<AnalysisLogRecord>, String> {
-        let file = File::open(path).map_err(|e| {
-                                       format!("failed to open analysis log file '{}': {}", path.display(), e)
-                                   })?;
-        let reader = BufReader::new(file);
-
-        let mut records = Vec::new();
-        for (line_index, line) in reader.lines().enumerate() {
-            let line = line.map_err(|e| {
-                               format!("failed to read analysis log file '{}' line {}: {}",
-                                       path.display(),
-                                       line_index + 1,
-                                       e)
-                           })?;
-            let trimmed = line.trim();
+    /// Visits records newest first, reading the file backward so that only as much of it is loaded
+    /// as the visitor consumes before breaking.
+    /// A trailing line without a newline that does not parse is a record still being written and is skipped.
+    /// `file` is the opened log at `path`, which is only used in error messages.
+    fn visit_records_rev<R: Read + Seek>(file: R,
+                                         path: &Path,
+                                         mut visit: impl FnMut(AnalysisLogRecord) -> ControlFlow<()>)
+                                         -> Result<ControlFlow<()>, String> {
+        let mut reader = ReverseLineReader::new(file).map_err(|e| {
+                                                         format!("failed to read analysis log file '{}': {}",
+                                                                 path.display(),
+                                                                 e)
+                                                     })?;
+
+        while let Some(line) =
+            reader.next_line()
+                  .map_err(|e| format!("failed to read analysis log file '{}': {}", path.display(), e))?
+        {
+            let trimmed = line.bytes.trim_ascii();
             if trimmed.is_empty() {
                 continue;
             }
-            let record = serde_json::from_str::<AnalysisLogRecord>(trimmed).map_err(|e| {
-                             format!("failed to parse analysis log file '{}' line {}: {}",
-                                     path.display(),
-                                     line_index + 1,
-                                     e)
-                         })?;
-            records.push(record);
+            let record = match serde_json::from_slice::<AnalysisLogRecord>(trimmed) {
+                Ok(record) => record,
+                Err(e) if !line.terminated => {
+                    log::warn!("analysis_log.read skipped incomplete last line path={} err={}",
+                               path.display(),
+                               e);
+                    continue;
+                },
+                Err(e) => {
+                    return Err(format!("failed to parse analysis log file '{}' at byte {}: {}",
+                                       path.display(),
+                                       line.offset,
+                                       e));
+                },
+            };
+            if visit(record).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// `list` over log files opened with `open`, so that tests can see how much of each file is read.
+    fn list_with<R: Read + Seek>(&self,
+                                 limit: Option<usize>,
+                                 open: impl Fn(&Path) -> io::Result<R>)
+                                 -> Result<Vec<AnalysisLogSummary>, String> {
+        let _guard = self.shared
+                         .io_lock
+                         .lock()
+                         .map_err(|_| "failed to lock analysis log repository".to_string())?;
+        let files = self.list_log_files()?;
+        let mut summaries = Vec::new();
+
+        if limit == Some(0) {
+            return Ok(summaries);
         }
 
-        Ok(records)
+        for (_, path) in files.into_iter().rev() {
+            let file = open_log_file(&path, &open)?;
+            let flow = Self::visit_records_rev(file, &path, |record| {
+                summaries.push(record.summary());
+                match limit {
+                    Some(limit) if summaries.len() >= limit => ControlFlow::Break(()),
+                    _ => ControlFlow::Continue(()),
+                }
+            })?;
+            if flow.is_break() {
+                break;
+            }
+        }
+
+        Ok(summaries)
     }
 }
 
@@ -170,26 +222,7 @@ impl AnalysisLogReader for JsonlAnalysisLogRepository {
     fn list(&self,
             limit: Option<usize>)
             -> Result<Vec<AnalysisLogSummary>, String> {
-        let _guard = self.shared
-                         .io_lock
-                         .lock()
-                         .map_err(|_| "failed to lock analysis log repository".to_string())?;
-        let files = self.list_log_files()?;
-        let mut summaries = Vec::new();
-
-        for (_, path) in files.into_iter().rev() {
-            let records = Self::read_records_from_file(&path)?;
-            for record in records.into_iter().rev() {
-                summaries.push(record.summary());
-                if let Some(limit) = limit
-                   && summaries.len() >= limit
-                {
-                    return Ok(summaries);
-                }
-            }
-        }
-
-        Ok(summaries)
+        self.list_with(limit, |path| File::open(path))
     }
 
     fn get(&self,
@@ -201,19 +234,32 @@ impl AnalysisLogReader for JsonlAnalysisLogRepository {
                          .map_err(|_| "failed to lock analysis log repository".to_string())?;
         let files = self.list_log_files()?;
 
+        let mut found = None;
         for (_, path) in files.into_iter().rev() {
-            let records = Self::read_records_from_file(&path)?;
-            for record in records.into_iter().rev() {
+            let file = open_log_file(&path, |path| File::open(path))?;
+            let flow = Self::visit_records_rev(file, &path, |record| {
                 if record.id == id {
-                    return Ok(Some(record));
+                    found = Some(record);
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
                 }
+            })?;
+            if flow.is_break() {
+                break;
             }
         }
 
-        Ok(None)
+        Ok(found)
     }
 }
 
+fn open_log_file<R>(path: &Path,
+                    open: impl Fn(&Path) -> io::Result<R>)
+                    -> Result<R, String> {
+    open(path).map_err(|e| format!("failed to open analysis log file '{}': {}", path.display(), e))
+}
+
 fn parse_log_sequence(file_name: &str) -> Option<u32> {
     let suffix_stripped = file_name.strip_suffix(LOG_FILE_SUFFIX)?;
     let sequence_str = suffix_stripped.strip_prefix(LOG_FILE_PREFIX)?;
@@ -234,6 +280,18 @@ fn file_size(path: &Path) -> Result<u64, String> {
 
 #[cfg(test)]
 mod tests {
+    use std::fs::File;
+    use std::io::{
+        Read,
+        Seek,
+        SeekFrom,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{
+        AtomicU64,
+        Ordering,
+    };
+
     use tempfile::tempdir;
 
     use crate::domain::analysis::model::AnalysisResult;
@@ -250,6 +308,30 @@ mod tests {
 
     use super::JsonlAnalysisLogRepository;
 
+    /// Counts the bytes `list` reads from each log file it opens.
+    struct CountingReader {
+        file: File,
+        bytes_read: Arc<AtomicU64>,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self,
+                buf: &mut [u8])
+                -> std::io::Result<usize> {
+            let read = self.file.read(buf)?;
+            self.bytes_read.fetch_add(read as u64, Ordering::Relaxed);
+            Ok(read)
+        }
+    }
+
+    impl Seek for CountingReader {
+        fn seek(&mut self,
+                pos: SeekFrom)
+                -> std::io::Result<u64> {
+            self.file.seek(pos)
+        }
+    }
+
     fn sample_record(id: &str,
                      timestamp: &str)
                      -> AnalysisLogRecord {
@@ -267,9 +349,11 @@ mod tests {
                                                                                   rows:
                                                                                       vec![vec!["x".into()]],
                                                                                   note: None,
-                                                                                  title: None } },
+                                                                                  title: None,
+                                                                                  column_types: Vec::new() } },
                             n: None,
-                            n_note: None }
+                            n_note: None,
+                            column_metadata: Default::default() }
     }
 
     #[test]
@@ -316,4 +400,52 @@ mod tests {
         assert_eq!(files[0], "analysis-log-000001.jsonl");
         assert_eq!(files[1], "analysis-log-000002.jsonl");
     }
+
+    #[test]
+    fn list_reads_only_the_tail_of_a_large_log() {
+        let temp = tempdir().expect("tempdir");
+        let dir = temp.path().join("analysis-logs");
+        let repository = JsonlAnalysisLogRepository::new(dir.clone(), 64 * 1024 * 1024);
+        for index in 1..=5000 {
+            repository.append(&sample_record(&index.to_string(), "2026-03-13 10:00:00"))
+                      .expect("append record");
+        }
+
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let open = |path: &std::path::Path| {
+            File::open(path).map(|file| CountingReader { file,
+                                                         bytes_read: Arc::clone(&bytes_read) })
+        };
+        let summaries = repository.list_with(Some(3), open).expect("list summaries");
+        let ids = summaries.iter()
+                           .map(|summary| summary.id.as_str())
+                           .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["5000", "4999", "4998"]);
+
+        let file_len = std::fs::metadata(dir.join("analysis-log-000001.jsonl")).expect("metadata")
+                                                                               .len();
+        assert!(bytes_read.load(Ordering::Relaxed) < file_len / 100);
+    }
+
+    #[test]
+    fn partially_written_last_line_is_skipped() {
+        let temp = tempdir().expect("tempdir");
+        let dir = temp.path().join("analysis-logs");
+        let repository = JsonlAnalysisLogRepository::new(dir.clone(), 5 * 1024 * 1024);
+        repository.append(&sample_record("1", "2026-03-13 10:00:00"))
+                  .expect("append record 1");
+        repository.append(&sample_record("2", "2026-03-13 10:05:00"))
+                  .expect("append record 2");
+        let mut file = std::fs::OpenOptions::new().append(true)
+                                                  .open(dir.join("analysis-log-000001.jsonl"))
+                                                  .expect("open log");
+        std::io::Write::write_all(&mut file, br#"{"schemaVersion":1,"id":"3","#).expect("write torn line");
+
+        let summaries = repository.list(None).expect("list summaries");
+        assert_eq!(summaries.iter()
+                            .map(|summary| summary.id.as_str())
+                            .collect::<Vec<_>>(),
+                   vec!["2", "1"]);
+        assert!(repository.get("1").expect("get record").is_some());
+    }
 }
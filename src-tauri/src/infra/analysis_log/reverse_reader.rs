@@ -0,0 +1,118 @@
+use std::io::{
+    self,
+    Read,
+    Seek,
+    SeekFrom,
+};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// One line read from the end of a file, without its trailing newline.
+pub(super) struct ReverseLine {
+    pub bytes: Vec<u8>,
+    /// Byte offset of the line start, for error messages.
+    pub offset: u64,
+    /// False only for the text after the last newline, which may be a record still being written.
+    pub terminated: bool,
+}
+
+/// Reads lines from the end of a file towards the start, one chunk at a time,
+/// so that only the tail needed for the requested lines is loaded.
+pub(super) struct ReverseLineReader<R> {
+    inner: R,
+    /// File offset where `buf` starts; everything before it is still unread.
+    pos: u64,
+    buf: Vec<u8>,
+    returned_tail: bool,
+    finished: bool,
+}
+
+impl<R: Read + Seek> ReverseLineReader<R> {
+    pub(super) fn new(mut inner: R) -> io::Result<Self> {
+        let pos = inner.seek(SeekFrom::End(0))?;
+        Ok(Self { inner,
+                  pos,
+                  buf: Vec::new(),
+                  returned_tail: false,
+                  finished: false })
+    }
+
+    pub(super) fn next_line(&mut self) -> io::Result<Option<ReverseLine>> {
+        if self.finished {
+            return Ok(None);
+        }
+        loop {
+            if let Some(newline) = self.buf.iter().rposition(|byte| *byte == b'\n') {
+                let bytes = self.buf.split_off(newline + 1);
+                self.buf.truncate(newline);
+                let offset = self.pos + newline as u64 + 1;
+                return Ok(Some(self.line(bytes, offset)));
+            }
+            if self.pos == 0 {
+                self.finished = true;
+                let bytes = std::mem::take(&mut self.buf);
+                return Ok(Some(self.line(bytes, 0)));
+            }
+            self.read_previous_chunk()?;
+        }
+    }
+
+    fn line(&mut self,
+            bytes: Vec<u8>,
+            offset: u64)
+            -> ReverseLine {
+        let terminated = self.returned_tail;
+        self.returned_tail = true;
+        ReverseLine { bytes,
+                      offset,
+                      terminated }
+    }
+
+    fn read_previous_chunk(&mut self) -> io::Result<()> {
+        let chunk_len = self.pos.min(CHUNK_SIZE as u64);
+        let start = self.pos - chunk_len;
+        self.inner.seek(SeekFrom::Start(start))?;
+
+        let mut chunk = vec![0; chunk_len as usize];
+        self.inner.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&self.buf);
+        self.buf = chunk;
+        self.pos = start;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::ReverseLineReader;
+
+    fn collect(text: &str) -> Vec<(String, bool)> {
+        let mut reader = ReverseLineReader::new(Cursor::new(text.as_bytes().to_vec())).unwrap();
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().unwrap() {
+            lines.push((String::from_utf8(line.bytes).unwrap(), line.terminated));
+        }
+        lines
+    }
+
+    #[test]
+    fn yields_lines_last_first_and_flags_the_unterminated_tail() {
+        assert_eq!(collect("a\nbb\nccc"),
+                   vec![("ccc".to_string(), false),
+                        ("bb".to_string(), true),
+                        ("a".to_string(), true)]);
+        assert_eq!(collect("a\n"),
+                   vec![(String::new(), false), ("a".to_string(), true)]);
+    }
+
+    #[test]
+    fn lines_longer_than_a_chunk_are_kept_whole() {
+        let long = "x".repeat(super::CHUNK_SIZE * 2 + 3);
+        let lines = collect(&format!("{}\nshort\n", long));
+
+        assert_eq!(lines[1].0, "short");
+        assert_eq!(lines[2].0, long);
+    }
+}
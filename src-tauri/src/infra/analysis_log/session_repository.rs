@@ -113,9 +113,11 @@ mod tests {
                                                                                   rows:
                                                                                       vec![vec!["x".into()]],
                                                                                   note: None,
-                                                                                  title: None } },
+                                                                                  title: None,
+                                                                                  column_types: Vec::new() } },
                             n: None,
-                            n_note: None }
+                            n_note: None,
+                            column_metadata: Default::default() }
     }
 
     #[test]
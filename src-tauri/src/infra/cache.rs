@@ -1,2 +1,3 @@
+pub(crate) mod csv_export;
 pub(crate) mod dataset_cache;
 pub(crate) mod repository;
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::sync::Mutex;
+use std::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use serde_json::Value;
+
+use crate::domain::input::table::CsvTableWriter;
+
+/// Exports left without a chunk for this long are dropped, which closes their file.
+const CSV_EXPORT_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+struct OpenExport {
+    path: String,
+    writer: CsvTableWriter<File>,
+    last_written_at: Instant,
+}
+
+/// CSV exports in progress, keyed by the token `begin` hands out. The frontend sends a large
+/// table in chunks of rows, so neither side holds the whole table or its CSV text for one call.
+#[derive(Default)]
+pub(crate) struct CsvExportRegistry {
+    exports: Mutex<HashMap<String, OpenExport>>,
+    counter: AtomicU64,
+}
+
+impl CsvExportRegistry {
+    /// Creates the file at `path`, writes the header row and returns the export token.
+    pub(crate) fn begin(&self,
+                        path: &str,
+                        headers: &[String])
+                        -> Result<String, String> {
+        let file = File::create(path).map_err(|e| format!("failed to create CSV: {}", e))?;
+        let writer = CsvTableWriter::new(file, headers)?;
+        let token = format!("csv_{}", self.counter.fetch_add(1, Ordering::Relaxed) + 1);
+        let now = Instant::now();
+
+        let mut exports = self.lock()?;
+        exports.retain(|_, export| now.duration_since(export.last_written_at) <= CSV_EXPORT_IDLE_TTL);
+        exports.insert(token.clone(),
+                       OpenExport { path: path.to_string(),
+                                    writer,
+                                    last_written_at: now });
+        Ok(token)
+    }
+
+    /// Appends `rows` to the export. A failed chunk drops the export, so the caller starts over.
+    pub(crate) fn append(&self,
+                         token: &str,
+                         rows: &[Vec<Value>])
+                         -> Result<(), String> {
+        let mut exports = self.lock()?;
+        let export = exports.get_mut(token)
+                            .ok_or_else(|| format!("CSV export '{}' is not open", token))?;
+        if let Err(e) = export.writer.write_rows(rows) {
+            exports.remove(token);
+            return Err(e);
+        }
+        export.last_written_at = Instant::now();
+        Ok(())
+    }
+
+    /// Flushes and closes the export, returning its path and the number of rows written.
+    pub(crate) fn finish(&self,
+                         token: &str)
+                         -> Result<(String, usize), String> {
+        let export = self.lock()?
+                         .remove(token)
+                         .ok_or_else(|| format!("CSV export '{}' is not open", token))?;
+        let rows = export.writer.finish()?;
+        Ok((export.path, rows))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, OpenExport>>, String> {
+        self.exports
+            .lock()
+            .map_err(|_| "CSV export registry lock poisoned".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::CsvExportRegistry;
+    use crate::domain::input::table::CsvTableWriter;
+
+    #[test]
+    fn chunked_export_matches_the_csv_of_the_whole_table() {
+        let rows: Vec<Vec<Value>> = (0..100).map(|i| vec![Value::from(format!("a,{}", i)), Value::from(i)])
+                                            .collect();
+        let headers = vec!["label".to_string(), "n".to_string()];
+        let mut expected = Vec::new();
+        let mut writer = CsvTableWriter::new(&mut expected, &headers).unwrap();
+        writer.write_rows(&rows).unwrap();
+        writer.finish().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("table.csv");
+        let registry = CsvExportRegistry::default();
+
+        let token = registry.begin(path.to_str().unwrap(), &headers).unwrap();
+        for chunk in rows.chunks(30) {
+            registry.append(&token, chunk).unwrap();
+        }
+        let (_, written) = registry.finish(&token).unwrap();
+
+        assert_eq!(written, 100);
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+        assert!(registry.finish(&token).is_err());
+    }
+
+    #[test]
+    fn a_malformed_chunk_closes_the_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("table.csv");
+        let registry = CsvExportRegistry::default();
+        let token = registry.begin(path.to_str().unwrap(), &["a".to_string(), "b".to_string()])
+                            .unwrap();
+
+        let err = registry.append(&token, &[vec![Value::from(1)]]).unwrap_err();
+
+        assert!(err.contains("row 0 length 1 != headers 2"), "{}", err);
+        assert!(registry.append(&token, &[vec![Value::from(1), Value::from(2)]])
+                        .is_err());
+    }
+}
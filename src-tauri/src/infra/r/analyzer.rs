@@ -1,7 +1,11 @@
 use serde_json::Value;
 
 use crate::domain::analysis::method::Method;
-use crate::domain::analysis::model::AnalysisResult;
+use crate::domain::analysis::model::{
+    AnalysisPartial,
+    AnalysisResult,
+};
+use crate::domain::analysis::rule::memory_limit_mb;
 use crate::domain::input::numeric::NumericDataset;
 use crate::domain::input::string_mixed::StringMixedDataset;
 use crate::usecase::analysis::ports::AnalysisRunner;
@@ -17,14 +21,23 @@ impl AnalysisRunner for RAnalyzer {
                       dataset: &NumericDataset,
                       options: &Value)
                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-        runner::run_r_analysis(method, dataset, options)
+        runner::run_r_analysis(method, dataset, options, memory_limit_mb(options))
+    }
+
+    fn run_r_analysis_streaming(&self,
+                                method: Method,
+                                dataset: &NumericDataset,
+                                options: &Value,
+                                on_partial: &mut dyn FnMut(AnalysisPartial))
+                                -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+        runner::run_r_analysis_streaming(method, dataset, options, memory_limit_mb(options), on_partial)
     }
 
     fn run_r_analysis_without_dataset(&self,
                                       method: Method,
                                       options: &Value)
                                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-        runner::run_r_analysis_without_dataset(method, options)
+        runner::run_r_analysis_without_dataset(method, options, memory_limit_mb(options))
     }
 
     fn run_r_analysis_string_mixed(&self,
@@ -32,6 +45,6 @@ impl AnalysisRunner for RAnalyzer {
                                    dataset: &StringMixedDataset,
                                    options: &Value)
                                    -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-        runner::run_r_analysis_string_mixed(method, dataset, options)
+        runner::run_r_analysis_string_mixed(method, dataset, options, memory_limit_mb(options))
     }
 }
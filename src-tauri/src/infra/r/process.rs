@@ -1,8 +1,23 @@
+use std::io::{
+    BufRead,
+    BufReader,
+    Read,
+};
 use std::process::{
+    Child,
     Command,
+    ExitStatus,
     Stdio,
 };
-use std::time::Duration;
+use std::sync::mpsc::{
+    self,
+    RecvTimeoutError,
+};
+use std::thread;
+use std::time::{
+    Duration,
+    Instant,
+};
 
 use wait_timeout::ChildExt;
 
@@ -12,18 +27,27 @@ use crate::domain::analysis::error::{
     classified_error_with_source,
 };
 
-const R_ANALYSIS_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const R_ANALYSIS_TIMEOUT: Duration = Duration::from_secs(30);
 const R_OUTPUT_SNIPPET_LIMIT: usize = 4000;
 
-pub(crate) fn run_rscript_with_timeout(mut command: Command) -> Result<Vec<u8>, String> {
+/// Runs Rscript and returns its stdout; the process is killed once `timeout` elapses.
+/// With `memory_limit_mb`, the child's address space is capped (Unix only) and an allocation failure
+/// is reported as `RMemoryLimitExceeded` instead of a generic R failure.
+pub(crate) fn run_rscript_with_timeout(mut command: Command,
+                                       timeout: Duration,
+                                       memory_limit_mb: Option<u64>)
+                                       -> Result<Vec<u8>, String> {
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(limit_mb) = memory_limit_mb {
+        apply_memory_limit(&mut command, limit_mb);
+    }
 
     let mut child = command.spawn().map_err(|e| {
                                         classified_error_with_source(AnalysisErrorKind::RExecutionFailure,
                                                                      "failed to spawn Rscript process",
                                                                      e)
                                     })?;
-    match child.wait_timeout(R_ANALYSIS_TIMEOUT) {
+    match child.wait_timeout(timeout) {
         Ok(Some(_)) => {
             let output = child.wait_with_output()
                               .map_err(|e| {
@@ -31,11 +55,7 @@ pub(crate) fn run_rscript_with_timeout(mut command: Command) -> Result<Vec<u8>,
                                                                "failed to read Rscript output",
                                                                e)
                               })?;
-            if output.status.success() {
-                Ok(output.stdout)
-            } else {
-                Err(format_r_failure(&output.stderr, &output.stdout))
-            }
+            finish_output(&output.status, output.stdout, &output.stderr, memory_limit_mb)
         },
         Ok(None) => {
             let _ = child.kill();
@@ -45,7 +65,7 @@ pub(crate) fn run_rscript_with_timeout(mut command: Command) -> Result<Vec<u8>,
                                                                "failed to read timed out output",
                                                                e)
                               })?;
-            Err(format_r_timeout(&output.stderr, &output.stdout))
+            Err(format_r_timeout(timeout, &output.stderr, &output.stdout))
         },
         Err(e) => Err(classified_error_with_source(AnalysisErrorKind::RExecutionFailure,
                                                    "failed to wait for Rscript process",
@@ -53,6 +73,149 @@ pub(crate) fn run_rscript_with_timeout(mut command: Command) -> Result<Vec<u8>,
     }
 }
 
+/// Like `run_rscript_with_timeout`, but hands each stderr line to `on_stderr_line` as soon as R writes it.
+/// Lines the callback does not consume (returns `false` for) are kept for error reporting.
+/// The timeout covers the whole run, including the time spent in the callback.
+pub(crate) fn run_rscript_streaming(mut command: Command,
+                                    timeout: Duration,
+                                    memory_limit_mb: Option<u64>,
+                                    on_stderr_line: &mut dyn FnMut(&str) -> bool)
+                                    -> Result<Vec<u8>, String> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(limit_mb) = memory_limit_mb {
+        apply_memory_limit(&mut command, limit_mb);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn().map_err(|e| {
+                                        classified_error_with_source(AnalysisErrorKind::RExecutionFailure,
+                                                                     "failed to spawn Rscript process",
+                                                                     e)
+                                    })?;
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let (line_tx, line_rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stderr_kept = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match line_rx.recv_timeout(remaining) {
+            Ok(line) => {
+                if !on_stderr_line(&line) {
+                    stderr_kept.extend_from_slice(line.as_bytes());
+                    stderr_kept.push(b'\n');
+                }
+            },
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                let stdout = kill_and_collect(&mut child, stdout_reader);
+                return Err(format_r_timeout(timeout, &stderr_kept, &stdout));
+            },
+        }
+    }
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match child.wait_timeout(remaining) {
+        Ok(Some(status)) => {
+            let stdout = stdout_reader.join().unwrap_or_default();
+            finish_output(&status, stdout, &stderr_kept, memory_limit_mb)
+        },
+        Ok(None) => {
+            let stdout = kill_and_collect(&mut child, stdout_reader);
+            Err(format_r_timeout(timeout, &stderr_kept, &stdout))
+        },
+        Err(e) => Err(classified_error_with_source(AnalysisErrorKind::RExecutionFailure,
+                                                   "failed to wait for Rscript process",
+                                                   e)),
+    }
+}
+
+fn kill_and_collect(child: &mut Child,
+                    stdout_reader: thread::JoinHandle<Vec<u8>>)
+                    -> Vec<u8> {
+    let _ = child.kill();
+    let _ = child.wait();
+    stdout_reader.join().unwrap_or_default()
+}
+
+fn finish_output(status: &ExitStatus,
+                 stdout: Vec<u8>,
+                 stderr: &[u8],
+                 memory_limit_mb: Option<u64>)
+                 -> Result<Vec<u8>, String> {
+    if status.success() {
+        Ok(stdout)
+    } else if let Some(limit_mb) = memory_limit_mb
+              && exceeded_memory_limit(status, stderr)
+    {
+        Err(classified_error(AnalysisErrorKind::RMemoryLimitExceeded,
+                             format!("R exceeded memory limit ({} MB)",
+                                     limit_mb)))
+    } else {
+        Err(format_r_failure(stderr, &stdout))
+    }
+}
+
+#[cfg(unix)]
+fn apply_memory_limit(command: &mut Command,
+                      limit_mb: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let bytes = limit_mb.saturating_mul(1024 * 1024);
+    // SAFETY: the closure runs in the forked child before exec and only calls setrlimit(2),
+    // which is async-signal-safe and does not allocate.
+    unsafe {
+        command.pre_exec(move || rlimit::setrlimit(rlimit::Resource::AS, bytes, bytes));
+    }
+}
+
+/// R removed `--max-mem-size` / `memory.limit()` in 4.2, so there is no per-process cap to pass on
+/// other platforms; the analysis runs without a limit.
+#[cfg(not(unix))]
+fn apply_memory_limit(_command: &mut Command,
+                      limit_mb: u64) {
+    log::warn!("r.memory_limit unsupported on this platform limit_mb={}",
+               limit_mb);
+}
+
+/// R reports a failed allocation (ENOMEM under the lowered limit) as "cannot allocate vector of
+/// size ..." (or "... memory block"). A SIGKILL, which the kernel sends when it runs out of memory,
+/// counts too; other signals, such as SIGSEGV or SIGTERM, are crashes or cancellations.
+fn exceeded_memory_limit(status: &ExitStatus,
+                         stderr: &[u8])
+                         -> bool {
+    String::from_utf8_lossy(stderr).contains("cannot allocate") || killed_by_sigkill(status)
+}
+
+#[cfg(unix)]
+fn killed_by_sigkill(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    const SIGKILL: i32 = 9;
+    status.signal() == Some(SIGKILL)
+}
+
+#[cfg(not(unix))]
+fn killed_by_sigkill(_status: &ExitStatus) -> bool {
+    false
+}
+
 fn format_r_failure(stderr: &[u8],
                     stdout: &[u8])
                     -> String {
@@ -70,12 +233,13 @@ fn format_r_failure(stderr: &[u8],
     }
 }
 
-fn format_r_timeout(stderr: &[u8],
+fn format_r_timeout(timeout: Duration,
+                    stderr: &[u8],
                     stdout: &[u8])
                     -> String {
     let stderr_snippet = summarize_output(stderr);
     let stdout_snippet = summarize_output(stdout);
-    let base = format!("R analysis timed out after {}s", R_ANALYSIS_TIMEOUT.as_secs());
+    let base = format!("R analysis timed out after {}s", timeout.as_secs());
     match (stderr_snippet.is_empty(), stdout_snippet.is_empty()) {
         (false, false) => classified_error(AnalysisErrorKind::RExecutionFailure,
                                            format!("{}; stderr: {}; stdout: {}",
@@ -106,3 +270,100 @@ fn summarize_output(bytes: &[u8]) -> String {
                               .collect();
     format!("{} ... {}", head, tail)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::Command;
+
+    use std::time::Duration;
+
+    use super::{
+        R_ANALYSIS_TIMEOUT,
+        run_rscript_streaming,
+        run_rscript_with_timeout,
+    };
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[test]
+    fn memory_limit_is_applied_to_the_child_process() {
+        let output =
+            run_rscript_with_timeout(shell("ulimit -v"), R_ANALYSIS_TIMEOUT, Some(64)).expect("run sh");
+
+        assert_eq!(String::from_utf8_lossy(&output).trim(), "65536");
+    }
+
+    #[test]
+    fn allocation_failure_under_limit_is_a_memory_limit_error() {
+        let script = "echo 'Error: cannot allocate vector of size 512.0 Mb' >&2; exit 1";
+        let err = run_rscript_with_timeout(shell(script), R_ANALYSIS_TIMEOUT, Some(64)).unwrap_err();
+
+        assert!(err.starts_with("[ANALYSIS_R_MEMORY_LIMIT_EXCEEDED]"), "{}", err);
+        assert!(err.contains("64 MB"));
+    }
+
+    #[test]
+    fn only_sigkill_among_signals_is_a_memory_limit_error() {
+        let killed =
+            run_rscript_with_timeout(shell("kill -KILL $$"), R_ANALYSIS_TIMEOUT, Some(64)).unwrap_err();
+        let crashed =
+            run_rscript_with_timeout(shell("kill -SEGV $$"), R_ANALYSIS_TIMEOUT, Some(64)).unwrap_err();
+        let cancelled =
+            run_rscript_with_timeout(shell("kill -TERM $$"), R_ANALYSIS_TIMEOUT, Some(64)).unwrap_err();
+
+        assert!(killed.starts_with("[ANALYSIS_R_MEMORY_LIMIT_EXCEEDED]"),
+                "{}",
+                killed);
+        assert!(crashed.starts_with("[ANALYSIS_R_EXECUTION_FAILURE]"),
+                "{}",
+                crashed);
+        assert!(cancelled.starts_with("[ANALYSIS_R_EXECUTION_FAILURE]"),
+                "{}",
+                cancelled);
+    }
+
+    #[test]
+    fn allocation_failure_without_limit_stays_a_generic_failure() {
+        let script = "echo 'Error: cannot allocate vector of size 512.0 Mb' >&2; exit 1";
+        let err = run_rscript_with_timeout(shell(script), R_ANALYSIS_TIMEOUT, None).unwrap_err();
+
+        assert!(err.starts_with("[ANALYSIS_R_EXECUTION_FAILURE]"), "{}", err);
+    }
+
+    #[test]
+    fn streaming_hands_over_stderr_lines_and_keeps_the_rest() {
+        let script = "echo 'P 1' >&2; echo 'warning' >&2; echo 'P 2' >&2; printf '{}'; exit 0";
+        let mut seen = Vec::new();
+        let output = run_rscript_streaming(shell(script), R_ANALYSIS_TIMEOUT, None, &mut |line| {
+                         if let Some(rest) = line.strip_prefix("P ") {
+                             seen.push(rest.to_string());
+                             true
+                         } else {
+                             false
+                         }
+                     }).expect("run sh");
+
+        assert_eq!(output, b"{}");
+        assert_eq!(seen, vec!["1", "2"]);
+
+        let err = run_rscript_streaming(shell("echo 'P 1' >&2; echo boom >&2; exit 1"),
+                                        R_ANALYSIS_TIMEOUT,
+                                        None,
+                                        &mut |line| line.starts_with("P ")).unwrap_err();
+        assert!(err.contains("boom") && !err.contains("P 1"), "{}", err);
+    }
+
+    #[test]
+    fn streaming_stops_at_the_timeout() {
+        let err = run_rscript_streaming(shell("exec sleep 5"),
+                                        Duration::from_millis(100),
+                                        None,
+                                        &mut |_| true).unwrap_err();
+
+        assert!(err.contains("timed out"), "{}", err);
+    }
+}
@@ -1,6 +1,7 @@
 use std::env;
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 
 use serde::Serialize;
 use serde_json::Value;
@@ -10,30 +11,61 @@ use crate::domain::analysis::error::{
     classified_error_with_source,
 };
 use crate::domain::analysis::method::Method;
-use crate::domain::analysis::model::AnalysisResult;
+use crate::domain::analysis::model::{
+    AnalysisPartial,
+    AnalysisResult,
+};
 use crate::domain::input::numeric::NumericDataset;
 use crate::domain::input::string_mixed::StringMixedDataset;
-use crate::infra::r::process::run_rscript_with_timeout;
+use crate::infra::r::process::{
+    R_ANALYSIS_TIMEOUT,
+    run_rscript_streaming,
+    run_rscript_with_timeout,
+};
 use crate::infra::r::temp_json::JsonTempFile;
 
+/// Prefix of the stderr lines cli.R writes for each finished column under `--stream true`.
+const PARTIAL_LINE_PREFIX: &str = "SAI_PARTIAL ";
+
 pub fn run_r_analysis(method: Method,
                       dataset: &NumericDataset,
-                      options: &Value)
+                      options: &Value,
+                      memory_limit_mb: Option<u64>)
                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-    run_r_job(method, Some(dataset), options)
+    run_r_job(method, Some(dataset), options, memory_limit_mb)
+}
+
+/// Runs a per-column analysis with `--stream true`, passing each column's rows to `on_partial`
+/// as R reports them. The returned result is the same payload the batch run produces.
+pub fn run_r_analysis_streaming(method: Method,
+                                dataset: &NumericDataset,
+                                options: &Value,
+                                memory_limit_mb: Option<u64>,
+                                on_partial: &mut dyn FnMut(AnalysisPartial))
+                                -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+    let (mut command, temp_files) = prepare_r_job(method, Some(dataset), options)?;
+    command.arg("--stream").arg("true");
+    let output = run_streaming_with_temp_files(command,
+                                               temp_files,
+                                               R_ANALYSIS_TIMEOUT,
+                                               memory_limit_mb,
+                                               on_partial)?;
+    parse_analysis_output(&output)
 }
 
 pub fn run_r_analysis_without_dataset(method: Method,
-                                      options: &Value)
+                                      options: &Value,
+                                      memory_limit_mb: Option<u64>)
                                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-    run_r_job::<Value>(method, None, options)
+    run_r_job::<Value>(method, None, options, memory_limit_mb)
 }
 
 pub fn run_r_analysis_string_mixed(method: Method,
                                    dataset: &StringMixedDataset,
-                                   options: &Value)
+                                   options: &Value,
+                                   memory_limit_mb: Option<u64>)
                                    -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
-    run_r_job(method, Some(dataset), options)
+    run_r_job(method, Some(dataset), options, memory_limit_mb)
 }
 
 fn resolve_cli_path() -> Result<PathBuf, String> {
@@ -48,8 +80,19 @@ fn resolve_cli_path() -> Result<PathBuf, String> {
 
 fn run_r_job<T: Serialize>(method: Method,
                            dataset: Option<&T>,
-                           options: &Value)
+                           options: &Value,
+                           memory_limit_mb: Option<u64>)
                            -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+    let (command, temp_files) = prepare_r_job(method, dataset, options)?;
+    let output = run_with_temp_files(command, temp_files, R_ANALYSIS_TIMEOUT, memory_limit_mb)?;
+    parse_analysis_output(&output)
+}
+
+/// Writes the dataset / options temp files and builds the Rscript command that reads them.
+fn prepare_r_job<T: Serialize>(method: Method,
+                               dataset: Option<&T>,
+                               options: &Value)
+                               -> Result<(Command, Vec<JsonTempFile>), String> {
     let dataset_file = match dataset {
         Some(ds) => Some(JsonTempFile::create("sai_dataset", ds).map_err(|e| {
                              classified_error_with_source(AnalysisErrorKind::RExecutionFailure,
@@ -63,7 +106,13 @@ fn run_r_job<T: Serialize>(method: Method,
                                                         "failed to create options temp file",
                                                         e)
                        })?;
-    let cli_path = resolve_cli_path()?;
+    let cli_path = match resolve_cli_path() {
+        Ok(path) => path,
+        Err(e) => {
+            remove_temp_files(dataset_file.into_iter().chain([options_file]));
+            return Err(e);
+        },
+    };
 
     let mut command = Command::new("Rscript");
     command.arg(cli_path)
@@ -77,8 +126,51 @@ fn run_r_job<T: Serialize>(method: Method,
                .arg("--input-format")
                .arg("json");
     }
-    let output = run_rscript_with_timeout(command)?;
-    parse_analysis_output(&output)
+    let temp_files = dataset_file.into_iter().chain([options_file]).collect();
+    Ok((command, temp_files))
+}
+
+/// Runs the command, then removes the job's temp files explicitly on every exit path
+/// (success, non-zero exit, timeout kill, spawn error) rather than relying on Drop alone.
+fn run_with_temp_files(command: Command,
+                       temp_files: impl IntoIterator<Item = JsonTempFile>,
+                       timeout: Duration,
+                       memory_limit_mb: Option<u64>)
+                       -> Result<Vec<u8>, String> {
+    let output = run_rscript_with_timeout(command, timeout, memory_limit_mb);
+    remove_temp_files(temp_files);
+    output
+}
+
+/// Streaming counterpart of `run_with_temp_files`. Malformed partial lines are logged and skipped;
+/// they never fail the run, since the final payload is parsed on its own.
+fn run_streaming_with_temp_files(command: Command,
+                                 temp_files: impl IntoIterator<Item = JsonTempFile>,
+                                 timeout: Duration,
+                                 memory_limit_mb: Option<u64>,
+                                 on_partial: &mut dyn FnMut(AnalysisPartial))
+                                 -> Result<Vec<u8>, String> {
+    let output = run_rscript_streaming(command, timeout, memory_limit_mb, &mut |line| {
+        let Some(json) = line.strip_prefix(PARTIAL_LINE_PREFIX) else {
+            return false;
+        };
+        match serde_json::from_str::<AnalysisPartial>(json).map_err(|e| e.to_string())
+                                                           .and_then(|partial| {
+                                                               partial.table.validate().map(|_| partial)
+                                                           }) {
+            Ok(partial) => on_partial(partial),
+            Err(e) => log::warn!("r.partial ignored malformed line err={}", e),
+        }
+        true
+    });
+    remove_temp_files(temp_files);
+    output
+}
+
+fn remove_temp_files(temp_files: impl IntoIterator<Item = JsonTempFile>) {
+    for file in temp_files {
+        file.remove();
+    }
 }
 
 fn parse_analysis_output(output: &[u8]) -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
@@ -110,3 +202,74 @@ fn parse_analysis_output(output: &[u8]) -> Result<(AnalysisResult, Option<u32>,
                       })?;
     Ok((result, n, n_note))
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::Command;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::infra::r::temp_json::{
+        JsonTempFile,
+        is_registered,
+    };
+
+    use super::{
+        parse_analysis_output,
+        run_streaming_with_temp_files,
+        run_with_temp_files,
+    };
+    use crate::domain::analysis::model::AnalysisResult;
+
+    #[test]
+    fn temp_files_are_removed_after_timeout() {
+        let dataset = JsonTempFile::create("sai_dataset", &json!({ "x": [1, 2] })).expect("dataset file");
+        let options = JsonTempFile::create("sai_options", &json!({})).expect("options file");
+        let paths = [dataset.path().to_path_buf(), options.path().to_path_buf()];
+
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("exec sleep 5");
+        let err =
+            run_with_temp_files(command, [dataset, options], Duration::from_millis(100), None).unwrap_err();
+
+        assert!(err.contains("timed out"), "{}", err);
+        for path in &paths {
+            assert!(!path.exists(), "{} still exists", path.display());
+            assert!(!is_registered(path));
+        }
+    }
+
+    #[test]
+    fn partial_events_fire_per_column_and_the_final_table_is_complete() {
+        let options = JsonTempFile::create("sai_options", &json!({})).expect("options file");
+        let options_path = options.path().to_path_buf();
+        let script = r#"
+            echo 'SAI_PARTIAL {"column":"a","index":1,"total":2,"table":{"headers":["変数","平均値"],"rows":[["a","1.000"]]}}' >&2
+            echo 'Loading required package' >&2
+            echo 'SAI_PARTIAL {"column":"b","index":2,"total":2,"table":{"headers":["変数","平均値"],"rows":[["b","2.000"]]}}' >&2
+            printf '%s' '{"kind":"table","table":{"headers":["変数","平均値"],"rows":[["a","1.000"],["b","2.000"]]},"n":4}'
+        "#;
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+
+        let mut partials = Vec::new();
+        let output =
+            run_streaming_with_temp_files(command, [options], Duration::from_secs(5), None, &mut |partial| {
+                partials.push(partial)
+            }).expect("run");
+        let (result, n, _) = parse_analysis_output(&output).expect("final payload");
+
+        assert_eq!(partials.iter().map(|p| p.column.as_str()).collect::<Vec<_>>(),
+                   vec!["a", "b"]);
+        assert_eq!(partials[1].index, 2);
+        assert_eq!(partials[1].total, 2);
+        let AnalysisResult::Table { table } = result else {
+            panic!("expected a table result");
+        };
+        let assembled: Vec<_> = partials.iter().flat_map(|p| p.table.rows.clone()).collect();
+        assert_eq!(table.rows, assembled);
+        assert_eq!(n, Some(4));
+        assert!(!options_path.exists());
+    }
+}
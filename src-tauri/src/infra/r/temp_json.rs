@@ -1,22 +1,86 @@
 use serde::Serialize;
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::Mutex;
 use tempfile::{
     Builder,
-    NamedTempFile,
+    TempPath,
 };
 
+/// Temp files that are still on disk; removed on app shutdown in case a job never reached its own cleanup.
+static LIVE_TEMP_FILES: TempFileRegistry = TempFileRegistry::new();
+
+/// Set of temp file paths that are still on disk.
+pub(crate) struct TempFileRegistry {
+    live: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl TempFileRegistry {
+    pub(crate) const fn new() -> Self {
+        Self { live: Mutex::new(BTreeSet::new()) }
+    }
+
+    fn register(&self,
+                path: &Path) {
+        if let Ok(mut live) = self.live.lock() {
+            live.insert(path.to_path_buf());
+        }
+    }
+
+    fn unregister(&self,
+                  path: &Path) {
+        if let Ok(mut live) = self.live.lock() {
+            live.remove(path);
+        }
+    }
+
+    #[cfg(test)]
+    fn contains(&self,
+                path: &Path)
+                -> bool {
+        self.live.lock().map(|live| live.contains(path)).unwrap_or(false)
+    }
+
+    /// Removes every registered file and empties the registry.
+    pub(crate) fn remove_all(&self) {
+        let paths: Vec<PathBuf> = match self.live.lock() {
+            Ok(mut live) => std::mem::take(&mut *live).into_iter().collect(),
+            Err(_) => return,
+        };
+        for path in paths {
+            remove_file_logged(&path);
+        }
+    }
+}
+
+/// JSON temp file handed to Rscript.
+/// Only the path is kept (the write handle is closed after serializing), so no open handle keeps the
+/// file locked on Windows while R reads it or while it is being removed.
 pub(crate) struct JsonTempFile {
-    file: NamedTempFile,
+    path: TempPath,
+    registry: &'static TempFileRegistry,
 }
 
 impl JsonTempFile {
     pub(crate) fn path(&self) -> &Path {
-        self.file.path()
+        &self.path
     }
 
     pub(crate) fn create(prefix: &str,
                          value: &impl Serialize)
                          -> Result<Self, String> {
+        Self::create_in(&LIVE_TEMP_FILES, prefix, value)
+    }
+
+    /// Same as `create`, but the file is tracked by `registry` instead of the app-wide one.
+    pub(crate) fn create_in(registry: &'static TempFileRegistry,
+                            prefix: &str,
+                            value: &impl Serialize)
+                            -> Result<Self, String> {
         let mut file = Builder::new().prefix(prefix)
                                      .suffix(".json")
                                      .tempfile()
@@ -24,6 +88,76 @@ impl JsonTempFile {
         serde_json::to_writer(file.as_file_mut(), value).map_err(|e| {
                                                             format!("Failed to serialize json: {}", e)
                                                         })?;
-        Ok(Self { file })
+        let path = file.into_temp_path();
+        registry.register(&path);
+        Ok(Self { path, registry })
+    }
+
+    /// Removes the file now instead of waiting for Drop; failures are logged, not returned.
+    pub(crate) fn remove(self) {
+        remove_file_logged(&self.path);
+    }
+}
+
+impl Drop for JsonTempFile {
+    fn drop(&mut self) {
+        self.registry.unregister(&self.path);
+    }
+}
+
+/// Removes every temp file that is still registered. Called when the app exits.
+pub(crate) fn remove_registered_temp_files() {
+    LIVE_TEMP_FILES.remove_all();
+}
+
+fn remove_file_logged(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {},
+        Err(e) if e.kind() == ErrorKind::NotFound => {},
+        Err(e) => log::warn!("r.temp_file remove failed path={} err={}", path.display(), e),
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn is_registered(path: &Path) -> bool {
+    LIVE_TEMP_FILES.contains(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        JsonTempFile,
+        TempFileRegistry,
+        is_registered,
+    };
+
+    #[test]
+    fn remove_deletes_file_and_unregisters_it() {
+        let file = JsonTempFile::create("sai_test", &json!({ "a": 1 })).expect("create temp file");
+        let path = file.path().to_path_buf();
+        assert!(path.exists());
+
+        file.remove();
+
+        assert!(!path.exists());
+        assert!(!is_registered(&path));
+    }
+
+    #[test]
+    fn shutdown_cleanup_removes_files_still_alive() {
+        // A registry of its own, so the cleanup cannot touch files of tests running in parallel.
+        static REGISTRY: TempFileRegistry = TempFileRegistry::new();
+        let file = JsonTempFile::create_in(&REGISTRY, "sai_test", &json!([1, 2])).expect("create temp file");
+        let path = file.path().to_path_buf();
+        assert!(REGISTRY.contains(&path));
+        assert!(!is_registered(&path));
+
+        REGISTRY.remove_all();
+
+        assert!(!path.exists());
+        assert!(!REGISTRY.contains(&path));
+        drop(file);
     }
 }
@@ -1,8 +1,16 @@
 mod csv;
+mod sheet_properties;
 mod xlsx;
 
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::sheet::{
+    SheetInfo,
+    SheetProperties,
+    SheetReadiness,
+};
 use crate::domain::input::source_kind::DataSourceKind;
 use crate::domain::input::table::ParsedDataTable;
+use crate::domain::input::verify::ParseMismatch;
 use crate::usecase::import::ports::{
     LoadedNumericDataset,
     LoadedStringMixedDataset,
@@ -15,26 +23,109 @@ pub(crate) struct DataResolver;
 impl TableReader for DataResolver {
     fn read_sheets(&self,
                    kind: DataSourceKind,
-                   path: &str)
-                   -> Result<Vec<String>, String> {
+                   path: &str,
+                   include_hidden: bool)
+                   -> Result<Vec<SheetInfo>, String> {
         match kind {
-            DataSourceKind::Csv => Ok(vec![]),
-            DataSourceKind::Xlsx => xlsx::get_xlsx_sheets(path),
+            DataSourceKind::Csv | DataSourceKind::Tsv => Ok(vec![]),
+            DataSourceKind::Xlsx => xlsx::get_xlsx_sheets(path, include_hidden),
+        }
+    }
+
+    fn read_sheet_properties(&self,
+                             kind: DataSourceKind,
+                             path: &str)
+                             -> Result<Vec<SheetProperties>, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                Err("Sheet properties are only available for XLSX files".to_string())
+            },
+            DataSourceKind::Xlsx => sheet_properties::read_sheet_properties(path),
         }
     }
 
     fn read_table(&self,
                   kind: DataSourceKind,
                   path: &str,
-                  sheet: Option<&str>)
+                  sheet: Option<&str>,
+                  options: &TableReadOptions)
                   -> Result<ParsedDataTable, String> {
         match kind {
-            DataSourceKind::Csv => csv::parse_csv_table(path),
+            DataSourceKind::Csv | DataSourceKind::Tsv => csv::parse_csv_table(path, delimiter(kind), options),
             DataSourceKind::Xlsx => {
                 let sheet = required_xlsx_sheet(sheet)?;
-                let rows = xlsx::read_xlsx_sheet_rows(path, sheet)?;
-                xlsx::create_parsed_data_table(rows)
+                let rows = xlsx::read_xlsx_sheet_rows(path, sheet, options)?;
+                xlsx::create_parsed_data_table(rows, options)
+            },
+        }
+    }
+
+    fn read_headers(&self,
+                    kind: DataSourceKind,
+                    path: &str,
+                    sheet: Option<&str>,
+                    options: &TableReadOptions)
+                    -> Result<Vec<String>, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                csv::read_csv_headers(path, delimiter(kind), options)
             },
+            DataSourceKind::Xlsx => xlsx::read_xlsx_headers(path, required_xlsx_sheet(sheet)?, options),
+        }
+    }
+
+    fn verify_parse(&self,
+                    kind: DataSourceKind,
+                    path: &str,
+                    sheet: Option<&str>,
+                    options: &TableReadOptions)
+                    -> Result<Vec<ParseMismatch>, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                csv::verify_csv_parse(path, delimiter(kind), options)
+            },
+            DataSourceKind::Xlsx => xlsx::verify_xlsx_parse(path, required_xlsx_sheet(sheet)?, options),
+        }
+    }
+
+    fn validate_workbook(&self,
+                         kind: DataSourceKind,
+                         path: &str,
+                         options: &TableReadOptions)
+                         -> Result<Vec<SheetReadiness>, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                Err("Workbook validation is only available for XLSX files".to_string())
+            },
+            DataSourceKind::Xlsx => xlsx::validate_xlsx_workbook(path, options),
+        }
+    }
+
+    fn read_formula_table(&self,
+                          kind: DataSourceKind,
+                          path: &str,
+                          sheet: &str,
+                          options: &TableReadOptions)
+                          -> Result<ParsedDataTable, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                Err("Formulas are only available in XLSX files".to_string())
+            },
+            DataSourceKind::Xlsx => xlsx::read_xlsx_formula_table(path, sheet, options),
+        }
+    }
+
+    fn read_named_table(&self,
+                        kind: DataSourceKind,
+                        path: &str,
+                        table_name: &str,
+                        options: &TableReadOptions)
+                        -> Result<ParsedDataTable, String> {
+        match kind {
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                Err("Named tables are only available in XLSX files".to_string())
+            },
+            DataSourceKind::Xlsx => xlsx::read_xlsx_named_table(path, table_name, options),
         }
     }
 
@@ -42,20 +133,24 @@ impl TableReader for DataResolver {
                              kind: DataSourceKind,
                              path: &str,
                              sheet: Option<&str>,
-                             variables: &[String])
+                             variables: &[String],
+                             options: &TableReadOptions)
                              -> Result<LoadedNumericDataset, String> {
         match kind {
-            DataSourceKind::Csv => {
-                let dataset = csv::build_numeric_dataset_from_csv(path, variables)?;
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                let (dataset, warnings) =
+                    csv::build_numeric_dataset_from_csv(path, delimiter(kind), variables, options)?;
                 Ok(LoadedNumericDataset { dataset,
-                                          sheet_name: "CSV".to_string() })
+                                          sheet_name: kind.as_str().to_ascii_uppercase(),
+                                          warnings })
             },
             DataSourceKind::Xlsx => {
                 let sheet = required_xlsx_sheet(sheet)?;
-                let rows = xlsx::read_xlsx_sheet_rows(path, sheet)?;
-                let dataset = xlsx::build_numeric_dataset_from_xlsx(rows, variables)?;
+                let rows = xlsx::read_xlsx_sheet_rows(path, sheet, options)?;
+                let (dataset, warnings) = xlsx::build_numeric_dataset_from_xlsx(rows, variables, options)?;
                 Ok(LoadedNumericDataset { dataset,
-                                          sheet_name: sheet.to_string() })
+                                          sheet_name: sheet.to_string(),
+                                          warnings })
             },
         }
     }
@@ -63,18 +158,20 @@ impl TableReader for DataResolver {
                                   kind: DataSourceKind,
                                   path: &str,
                                   sheet: Option<&str>,
-                                  variables: &[String])
+                                  variables: &[String],
+                                  options: &TableReadOptions)
                                   -> Result<LoadedStringMixedDataset, String> {
         match kind {
-            DataSourceKind::Csv => {
-                let dataset = csv::build_string_mixed_dataset_from_csv(path, variables)?;
+            DataSourceKind::Csv | DataSourceKind::Tsv => {
+                let dataset =
+                    csv::build_string_mixed_dataset_from_csv(path, delimiter(kind), variables, options)?;
                 Ok(LoadedStringMixedDataset { dataset,
-                                              sheet_name: "CSV".to_string() })
+                                              sheet_name: kind.as_str().to_ascii_uppercase() })
             },
             DataSourceKind::Xlsx => {
                 let sheet = required_xlsx_sheet(sheet)?;
-                let rows = xlsx::read_xlsx_sheet_rows(path, sheet)?;
-                let dataset = xlsx::build_string_mixed_dataset_from_xlsx(rows, variables)?;
+                let rows = xlsx::read_xlsx_sheet_rows(path, sheet, options)?;
+                let dataset = xlsx::build_string_mixed_dataset_from_xlsx(rows, variables, options)?;
                 Ok(LoadedStringMixedDataset { dataset,
                                               sheet_name: sheet.to_string() })
             },
@@ -82,6 +179,14 @@ impl TableReader for DataResolver {
     }
 }
 
+/// Field delimiter of a delimited text source.
+fn delimiter(kind: DataSourceKind) -> u8 {
+    match kind {
+        DataSourceKind::Tsv => b'\t',
+        DataSourceKind::Csv | DataSourceKind::Xlsx => b',',
+    }
+}
+
 fn required_xlsx_sheet(sheet: Option<&str>) -> Result<&str, String> {
     sheet.ok_or_else(|| "Sheet is required for XLSX file".to_string())
 }
@@ -1,31 +1,41 @@
+use std::fs::File;
+
 use csv::{
+    Reader,
     ReaderBuilder,
     StringRecord,
 };
 
 use crate::domain::input::numeric::{
+    CellWarnings,
     NumericCellContext,
     NumericDataset,
     parse_numeric_string,
 };
+use crate::domain::input::read_options::{
+    TableReadOptions,
+    missing_token_note,
+};
 use crate::domain::input::table::{
     ParsedDataTable,
     collect_ordered_selected_columns,
+    infer_column_types,
+    join_notes,
     normalize_rows,
     validate_unique_headers,
 };
+use crate::domain::input::verify::ParseMismatch;
 
 pub(super) fn build_numeric_dataset_from_csv(path: &str,
-                                             variables: &[String])
-                                             -> Result<NumericDataset, String> {
+                                             delimiter: u8,
+                                             variables: &[String],
+                                             options: &TableReadOptions)
+                                             -> Result<(NumericDataset, Vec<String>), String> {
     if variables.is_empty() {
         return Err("No variables selected".to_string());
     }
 
-    let mut reader = ReaderBuilder::new().has_headers(true)
-                                         .flexible(true)
-                                         .from_path(path)
-                                         .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+    let mut reader = open_reader(path, delimiter)?;
 
     let headers_record = reader.headers()
                                .map_err(|e| format!("Failed to read CSV headers: {}", e))?
@@ -35,7 +45,7 @@ pub(super) fn build_numeric_dataset_from_csv(path: &str,
         return Err("CSV is empty".to_string());
     }
 
-    let headers = compute_headers_from_record(&headers_record)?;
+    let headers = compute_headers_from_record(&headers_record, options)?;
     let selected_columns = collect_ordered_selected_columns(&headers, variables)?;
 
     let mut dataset = NumericDataset::with_capacity(selected_columns.len());
@@ -43,32 +53,36 @@ pub(super) fn build_numeric_dataset_from_csv(path: &str,
         dataset.insert(header.clone(), Vec::new());
     }
 
+    let mut warnings = CellWarnings::default();
     for (row_index, record) in reader.records().enumerate() {
         let record = record.map_err(|e| format!("Failed to read CSV row: {}", e))?;
         for (header, col_index) in &selected_columns {
             let cell = record.get(*col_index);
-            let value = parse_csv_numeric_cell(cell, NumericCellContext::new(row_index, *col_index, header))?;
+            let context = NumericCellContext::new(row_index, *col_index, header);
+            let value = parse_csv_numeric_cell(cell, context, options)?;
+            if let Some(raw) = cell {
+                warnings.check_integer_text(raw, context);
+            }
             dataset.get_mut(header)
                    .expect("dataset column exists")
                    .push(value);
         }
     }
 
-    Ok(dataset)
+    Ok((dataset, warnings.finish()))
 }
 
 pub(super) fn build_string_mixed_dataset_from_csv(
     path: &str,
-    variables: &[String])
+    delimiter: u8,
+    variables: &[String],
+    options: &TableReadOptions)
     -> Result<crate::domain::input::string_mixed::StringMixedDataset, String> {
     if variables.is_empty() {
         return Err("No variables selected".to_string());
     }
 
-    let mut reader = ReaderBuilder::new().has_headers(true)
-                                         .flexible(true)
-                                         .from_path(path)
-                                         .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+    let mut reader = open_reader(path, delimiter)?;
 
     let headers_record = reader.headers()
                                .map_err(|e| format!("Failed to read CSV headers: {}", e))?
@@ -78,7 +92,7 @@ pub(super) fn build_string_mixed_dataset_from_csv(
         return Err("CSV is empty".to_string());
     }
 
-    let headers = compute_headers_from_record(&headers_record)?;
+    let headers = compute_headers_from_record(&headers_record, options)?;
     let selected_columns = collect_ordered_selected_columns(&headers, variables)?;
 
     let mut dataset =
@@ -91,9 +105,8 @@ pub(super) fn build_string_mixed_dataset_from_csv(
         let record = record.map_err(|e| format!("Failed to read CSV row: {}", e))?;
         for (header, col_index) in &selected_columns {
             let value = record.get(*col_index)
-                              .map(|cell| cell.trim())
-                              .filter(|cell| !cell.is_empty())
-                              .map(|cell| cell.to_string());
+                              .filter(|cell| !options.is_missing_text(cell))
+                              .map(|cell| cell.trim().to_string());
             dataset.get_mut(header)
                    .expect("dataset column exists")
                    .push(value);
@@ -103,11 +116,11 @@ pub(super) fn build_string_mixed_dataset_from_csv(
     Ok(dataset)
 }
 
-pub(super) fn parse_csv_table(path: &str) -> Result<ParsedDataTable, String> {
-    let mut reader = ReaderBuilder::new().has_headers(true)
-                                         .flexible(true)
-                                         .from_path(path)
-                                         .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+pub(super) fn parse_csv_table(path: &str,
+                              delimiter: u8,
+                              options: &TableReadOptions)
+                              -> Result<ParsedDataTable, String> {
+    let mut reader = open_reader(path, delimiter)?;
 
     let headers_record = reader.headers()
                                .map_err(|e| format!("Failed to read CSV headers: {}", e))?
@@ -117,15 +130,23 @@ pub(super) fn parse_csv_table(path: &str) -> Result<ParsedDataTable, String> {
         return Ok(ParsedDataTable { headers: vec![],
                                     rows: vec![],
                                     note: None,
-                                    title: None });
+                                    title: None,
+                                    column_types: Vec::new() });
     }
 
-    let headers = compute_headers_from_record(&headers_record)?;
+    let headers = compute_headers_from_record(&headers_record, options)?;
+    let mut converted_tokens = 0;
     let rows = reader.records()
                      .map(|record| {
                          record.map(|row| {
                                    row.iter()
-                                      .map(csv_cell_to_json_value)
+                                      .map(|cell| {
+                                          if options.is_missing_token(cell) {
+                                              converted_tokens += 1;
+                                              return serde_json::Value::Null;
+                                          }
+                                          csv_cell_to_json_value(cell)
+                                      })
                                       .collect::<Vec<serde_json::Value>>()
                                })
                                .map_err(|e| format!("Failed to read CSV row: {}", e))
@@ -133,26 +154,94 @@ pub(super) fn parse_csv_table(path: &str) -> Result<ParsedDataTable, String> {
                      .collect::<Result<Vec<_>, String>>()?;
 
     let normalized = normalize_rows(rows, headers.len());
+    let column_types = infer_column_types(headers.len(), &normalized.rows);
 
     Ok(ParsedDataTable { headers,
                          rows: normalized.rows,
-                         note: normalized.note,
-                         title: None })
+                         note: join_notes([missing_token_note(converted_tokens), normalized.note]),
+                         title: None,
+                         column_types })
+}
+
+/// Reads only the header record; the names match `parse_csv_table`.
+pub(super) fn read_csv_headers(path: &str,
+                               delimiter: u8,
+                               options: &TableReadOptions)
+                               -> Result<Vec<String>, String> {
+    let mut reader = open_reader(path, delimiter)?;
+    let headers_record = reader.headers()
+                               .map_err(|e| format!("Failed to read CSV headers: {}", e))?;
+    if headers_record.is_empty() {
+        return Ok(vec![]);
+    }
+    compute_headers_from_record(headers_record, options)
+}
+
+/// Parses the file, then reads the raw records again and reports every non-null parsed cell that
+/// is not the exact source text. CSV cells are kept as text, so any difference is a parsing bug.
+pub(super) fn verify_csv_parse(path: &str,
+                               delimiter: u8,
+                               options: &TableReadOptions)
+                               -> Result<Vec<ParseMismatch>, String> {
+    let parsed = parse_csv_table(path, delimiter, options)?;
+    let mut reader = open_reader(path, delimiter)?;
+
+    let mut mismatches = Vec::new();
+    for (row_index, (record, row)) in reader.records().zip(&parsed.rows).enumerate() {
+        let record = record.map_err(|e| format!("Failed to read CSV row: {}", e))?;
+        for (col_index, value) in row.iter().enumerate() {
+            if value.is_null() {
+                continue;
+            }
+            let source = record.get(col_index).unwrap_or("");
+            if value.as_str() != Some(source) {
+                mismatches.push(ParseMismatch { row: row_index + 2,
+                                                column: parsed.headers[col_index].clone(),
+                                                parsed: value.clone(),
+                                                source: source.to_string() });
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+/// Opens a delimited text file (`b','` for CSV, `b'\t'` for TSV). Quoted fields may contain the
+/// delimiter and line breaks; rows may have any number of fields.
+fn open_reader(path: &str,
+               delimiter: u8)
+               -> Result<Reader<File>, String> {
+    ReaderBuilder::new().has_headers(true)
+                        .flexible(true)
+                        .delimiter(delimiter)
+                        .from_path(path)
+                        .map_err(|e| format!("Failed to open CSV file: {}", e))
 }
 
 fn parse_csv_numeric_cell(cell: Option<&str>,
-                          context: NumericCellContext<'_>)
+                          context: NumericCellContext<'_>,
+                          options: &TableReadOptions)
                           -> Result<Option<f64>, String> {
     let Some(raw) = cell else {
         return Ok(None);
     };
+    if options.is_missing_token(raw) {
+        return Ok(None);
+    }
     parse_numeric_string(raw, context)
 }
 
-fn compute_headers_from_record(record: &StringRecord) -> Result<Vec<String>, String> {
+fn compute_headers_from_record(record: &StringRecord,
+                               options: &TableReadOptions)
+                               -> Result<Vec<String>, String> {
+    if options.header_row.is_some_and(|row| row != 1) {
+        return Err("header_row is only supported for XLSX files".to_string());
+    }
+    if options.range.is_some() {
+        return Err("range is only supported for XLSX files".to_string());
+    }
     let headers: Vec<String> = record.iter()
                                      .enumerate()
-                                     .map(|(index, cell)| csv_cell_to_header_name(cell, index))
+                                     .map(|(index, cell)| csv_cell_to_header_name(cell, index, options))
                                      .collect();
 
     validate_unique_headers(&headers)?;
@@ -160,11 +249,12 @@ fn compute_headers_from_record(record: &StringRecord) -> Result<Vec<String>, Str
 }
 
 fn csv_cell_to_header_name(cell: &str,
-                           col_index: usize)
+                           col_index: usize,
+                           options: &TableReadOptions)
                            -> String {
     let trimmed = cell.trim();
     if trimmed.is_empty() {
-        format!("col_{}", col_index + 1)
+        options.blank_header_name(col_index, 0)
     } else {
         trimmed.to_string()
     }
@@ -178,3 +268,35 @@ fn csv_cell_to_json_value(cell: &str) -> serde_json::Value {
         serde_json::Value::String(cell.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{
+        build_numeric_dataset_from_csv,
+        parse_csv_table,
+    };
+    use crate::domain::input::read_options::TableReadOptions;
+
+    #[test]
+    fn tsv_keeps_quoted_tabs_and_line_breaks_and_reads_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.tsv");
+        std::fs::write(&path,
+                       "id\tcomment\tscore\n1\t\"a\tb, c\"\t3.5\n2\t\"line 1\nline 2\"\t-2\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let table = parse_csv_table(path, b'\t', &TableReadOptions::default()).unwrap();
+        assert_eq!(table.headers, vec!["id", "comment", "score"]);
+        assert_eq!(table.rows,
+                   vec![vec![Value::from("1"), Value::from("a\tb, c"), Value::from("3.5")],
+                        vec![Value::from("2"), Value::from("line 1\nline 2"), Value::from("-2")]]);
+
+        let (dataset, _) = build_numeric_dataset_from_csv(path,
+                                                          b'\t',
+                                                          &["score".to_string()],
+                                                          &TableReadOptions::default()).unwrap();
+        assert_eq!(dataset["score"], vec![Some(3.5), Some(-2.0)]);
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{
+    BufRead,
+    BufReader,
+    Read,
+    Seek,
+};
+
+use calamine::{
+    Reader as _,
+    SheetVisible,
+    open_workbook_auto,
+};
+use quick_xml::Reader as XmlReader;
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{
+    BytesStart,
+    Event,
+};
+use zip::ZipArchive;
+
+use crate::domain::input::sheet::{
+    SheetProperties,
+    SheetVisibility,
+};
+
+/// Protection flag and tab color of one worksheet, read from its OOXML part.
+#[derive(Debug, Default, PartialEq)]
+struct WorksheetXmlProperties {
+    protected: bool,
+    tab_color: Option<String>,
+}
+
+/// Lists every sheet with its visibility, and for `.xlsx` files also its protection and tab color.
+/// Names and visibility come from calamine, which supports every workbook format. Protection and
+/// tab color are read from the worksheet XML and stay `None` when that is not available.
+pub(super) fn read_sheet_properties(path: &str) -> Result<Vec<SheetProperties>, String> {
+    let workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let metadata = workbook.sheets_metadata().to_vec();
+    if metadata.is_empty() {
+        return Err("Failed to get sheet names".to_string());
+    }
+
+    let xml_properties = match read_worksheet_xml_properties(path) {
+        Ok(properties) => properties,
+        Err(e) => {
+            log::info!("sheet properties: protection and tab color unavailable path={} reason={}",
+                       path,
+                       e);
+            HashMap::new()
+        },
+    };
+
+    Ok(metadata.into_iter()
+               .map(|sheet| {
+                   let xml = xml_properties.get(&sheet.name);
+                   SheetProperties { visibility: match sheet.visible {
+                                         SheetVisible::Visible => SheetVisibility::Visible,
+                                         SheetVisible::Hidden => SheetVisibility::Hidden,
+                                         SheetVisible::VeryHidden => SheetVisibility::VeryHidden,
+                                     },
+                                     protected: xml.map(|xml| xml.protected),
+                                     tab_color: xml.and_then(|xml| xml.tab_color.clone()),
+                                     name: sheet.name }
+               })
+               .collect())
+}
+
+/// Maps sheet names to the properties found in their worksheet parts.
+/// Fails when the file is not an OOXML package (e.g. `.xls`).
+fn read_worksheet_xml_properties(path: &str) -> Result<HashMap<String, WorksheetXmlProperties>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("not an OOXML package: {}", e))?;
+
+    let sheets = read_workbook_sheets(&mut archive)?;
+    let targets = read_workbook_relationships(&mut archive)?;
+
+    let mut properties = HashMap::new();
+    for (name, relationship_id) in sheets {
+        let Some(target) = targets.get(&relationship_id) else {
+            continue;
+        };
+        let part = archive.by_name(target)
+                          .map_err(|e| format!("missing worksheet part {}: {}", target, e))?;
+        properties.insert(name, scan_worksheet(BufReader::new(part))?);
+    }
+    Ok(properties)
+}
+
+/// `(sheet name, relationship id)` pairs from `xl/workbook.xml`, in workbook order.
+fn read_workbook_sheets<R: Read + Seek>(archive: &mut ZipArchive<R>)
+                                        -> Result<Vec<(String, String)>, String> {
+    let part = archive.by_name("xl/workbook.xml")
+                      .map_err(|e| format!("missing xl/workbook.xml: {}", e))?;
+    let mut sheets = Vec::new();
+    for_each_element(BufReader::new(part), |element, decoder| {
+        if element.local_name().as_ref() != b"sheet" {
+            return Ok(());
+        }
+        let mut name = None;
+        let mut relationship_id = None;
+        for attribute in element.attributes().flatten() {
+            let value = attribute.decode_and_unescape_value(decoder)
+                                 .map_err(|e| format!("invalid attribute in xl/workbook.xml: {}", e))?
+                                 .into_owned();
+            match (attribute.key.prefix().is_some(), attribute.key.local_name().as_ref()) {
+                (false, b"name") => name = Some(value),
+                (true, b"id") => relationship_id = Some(value),
+                _ => {},
+            }
+        }
+        if let (Some(name), Some(relationship_id)) = (name, relationship_id) {
+            sheets.push((name, relationship_id));
+        }
+        Ok(())
+    })?;
+    Ok(sheets)
+}
+
+/// Relationship id -> archive path, from `xl/_rels/workbook.xml.rels`.
+fn read_workbook_relationships<R: Read + Seek>(archive: &mut ZipArchive<R>)
+                                               -> Result<HashMap<String, String>, String> {
+    let part = archive.by_name("xl/_rels/workbook.xml.rels")
+                      .map_err(|e| format!("missing xl/_rels/workbook.xml.rels: {}", e))?;
+    let mut targets = HashMap::new();
+    for_each_element(BufReader::new(part), |element, decoder| {
+        if element.local_name().as_ref() != b"Relationship" {
+            return Ok(());
+        }
+        let id = attribute_value(element, decoder, b"Id")?;
+        let target = attribute_value(element, decoder, b"Target")?;
+        if let (Some(id), Some(target)) = (id, target) {
+            // Targets are relative to xl/ unless they are absolute package paths.
+            let path = match target.strip_prefix('/') {
+                Some(absolute) => absolute.to_string(),
+                None => format!("xl/{}", target),
+            };
+            targets.insert(id, path);
+        }
+        Ok(())
+    })?;
+    Ok(targets)
+}
+
+/// Reads `<sheetPr><tabColor rgb>` and `<sheetProtection sheet>` from a worksheet part.
+/// A sheet is protected only when `sheetProtection` is present with `sheet="1"`.
+fn scan_worksheet(part: impl BufRead) -> Result<WorksheetXmlProperties, String> {
+    let mut properties = WorksheetXmlProperties::default();
+    for_each_element(part, |element, decoder| {
+        match element.local_name().as_ref() {
+            b"tabColor" => {
+                properties.tab_color =
+                    attribute_value(element, decoder, b"rgb")?.and_then(|argb| rgb_hex(&argb));
+            },
+            b"sheetProtection" => {
+                properties.protected = matches!(attribute_value(element, decoder, b"sheet")?.as_deref(),
+                                                Some("1" | "true"));
+            },
+            _ => {},
+        }
+        Ok(())
+    })?;
+    Ok(properties)
+}
+
+/// Calls `visit` for every start or empty element of the document.
+fn for_each_element(part: impl BufRead,
+                    mut visit: impl FnMut(&BytesStart<'_>, Decoder) -> Result<(), String>)
+                    -> Result<(), String> {
+    let mut reader = XmlReader::from_reader(part);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)
+                    .map_err(|e| format!("invalid XML: {}", e))?
+        {
+            Event::Start(element) | Event::Empty(element) => visit(&element, reader.decoder())?,
+            Event::Eof => return Ok(()),
+            _ => {},
+        }
+        buf.clear();
+    }
+}
+
+fn attribute_value(element: &BytesStart<'_>,
+                   decoder: Decoder,
+                   key: &[u8])
+                   -> Result<Option<String>, String> {
+    let Some(attribute) = element.try_get_attribute(key)
+                                 .map_err(|e| format!("invalid attribute: {}", e))?
+    else {
+        return Ok(None);
+    };
+    let value = attribute.decode_and_unescape_value(decoder)
+                         .map_err(|e| format!("invalid attribute: {}", e))?;
+    Ok(Some(value.into_owned()))
+}
+
+/// Converts an OOXML `AARRGGBB` (or `RRGGBB`) color to `#RRGGBB`.
+fn rgb_hex(argb: &str) -> Option<String> {
+    let rgb = match argb.len() {
+        8 => &argb[2..],
+        6 => argb,
+        _ => return None,
+    };
+    rgb.chars()
+       .all(|c| c.is_ascii_hexdigit())
+       .then(|| format!("#{}", rgb.to_ascii_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_and_protected_sheets_are_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("properties.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        workbook.add_worksheet()
+                .set_name("Data")
+                .unwrap()
+                .set_tab_color(rust_xlsxwriter::Color::RGB(0x1F_77B4))
+                .write(0, 0, "x")
+                .unwrap();
+        workbook.add_worksheet()
+                .set_name("Helper")
+                .unwrap()
+                .set_hidden(true)
+                .write(0, 0, "lookup")
+                .unwrap();
+        workbook.add_worksheet()
+                .set_name("Locked")
+                .unwrap()
+                .protect()
+                .write(0, 0, "fixed")
+                .unwrap();
+        workbook.save(&path).unwrap();
+
+        let properties = read_sheet_properties(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(properties,
+                   vec![SheetProperties { name: "Data".to_string(),
+                                          visibility: SheetVisibility::Visible,
+                                          protected: Some(false),
+                                          tab_color: Some("#1F77B4".to_string()) },
+                        SheetProperties { name: "Helper".to_string(),
+                                          visibility: SheetVisibility::Hidden,
+                                          protected: Some(false),
+                                          tab_color: None },
+                        SheetProperties { name: "Locked".to_string(),
+                                          visibility: SheetVisibility::Visible,
+                                          protected: Some(true),
+                                          tab_color: None }]);
+    }
+
+    #[test]
+    fn argb_colors_drop_the_alpha_channel() {
+        assert_eq!(rgb_hex("FFff0000").as_deref(), Some("#FF0000"));
+        assert_eq!(rgb_hex("00FF00").as_deref(), Some("#00FF00"));
+        assert_eq!(rgb_hex("theme"), None);
+    }
+}
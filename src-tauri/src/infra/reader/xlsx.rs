@@ -1,26 +1,91 @@
+use std::collections::HashMap;
+use std::io::{
+    Read,
+    Seek,
+};
+
 use calamine::{
     CellErrorType,
     Data,
+    DataRef,
+    Range,
     Reader,
+    SheetVisible,
+    Sheets,
+    Xlsx,
+    open_workbook,
     open_workbook_auto,
 };
 
 use crate::domain::input::numeric::{
+    CellWarnings,
     NumericCellContext,
     NumericDataset,
     parse_finite_number,
     parse_numeric_string,
 };
+use crate::domain::input::read_options::{
+    BoolHeaderStrategy,
+    CellErrorPolicy,
+    TableReadOptions,
+    cell_error_note,
+    missing_token_note,
+};
+use crate::domain::input::sheet::{
+    SheetInfo,
+    SheetReadiness,
+};
 use crate::domain::input::table::{
+    ColumnType,
+    ColumnTypeInference,
     ParsedDataTable,
     collect_ordered_selected_columns,
+    duplicate_headers,
+    join_notes,
     normalize_rows,
     validate_unique_headers,
 };
+use crate::domain::input::verify::{
+    ParseMismatch,
+    numbers_match,
+};
+
+struct SheetHeaders {
+    names: Vec<String>,
+    note: Option<String>,
+}
+
+/// The rows of a sheet's table part, header row first, with the 0-based sheet row and column of
+/// its first cell so that columns can be named, and cells located, by their place on the sheet.
+#[derive(Debug, Default)]
+pub(super) struct SheetRows {
+    pub(super) rows: Vec<Vec<Data>>,
+    pub(super) first_row: usize,
+    pub(super) first_column: usize,
+}
+
+impl SheetRows {
+    fn from_range(range: &Range<Data>) -> Self {
+        let (first_row, first_column) = range.start().unwrap_or_default();
+        Self { rows: range.rows().map(|row| row.to_vec()).collect(),
+               first_row: first_row as usize,
+               first_column: first_column as usize }
+    }
+}
+
+impl From<Vec<Vec<Data>>> for SheetRows {
+    /// Rows that start in cell A1.
+    fn from(rows: Vec<Vec<Data>>) -> Self {
+        Self { rows,
+               ..Self::default() }
+    }
+}
 
-pub(super) fn build_numeric_dataset_from_xlsx(rows_data: Vec<Vec<Data>>,
-                                              variables: &[String])
-                                              -> Result<NumericDataset, String> {
+pub(super) fn build_numeric_dataset_from_xlsx(sheet: SheetRows,
+                                              variables: &[String],
+                                              options: &TableReadOptions)
+                                              -> Result<(NumericDataset, Vec<String>), String> {
+    let rows_data = sheet.rows;
     if rows_data.is_empty() {
         return Err("Sheet is empty".to_string());
     }
@@ -28,8 +93,8 @@ pub(super) fn build_numeric_dataset_from_xlsx(rows_data: Vec<Vec<Data>>,
         return Err("No variables selected".to_string());
     }
 
-    let headers = compute_headers_from_first_row(&rows_data[0])?;
-    let selected_columns = collect_ordered_selected_columns(&headers, variables)?;
+    let headers = compute_headers_from_first_row(&rows_data[0], sheet.first_column, options)?;
+    let selected_columns = collect_ordered_selected_columns(&headers.names, variables)?;
     let row_count = rows_data.len().saturating_sub(1);
 
     let mut dataset = NumericDataset::with_capacity(selected_columns.len());
@@ -37,24 +102,33 @@ pub(super) fn build_numeric_dataset_from_xlsx(rows_data: Vec<Vec<Data>>,
         dataset.insert(header.clone(), Vec::with_capacity(row_count));
     }
 
+    let mut warnings = CellWarnings::default();
     for (row_index, row) in rows_data.iter().skip(1).enumerate() {
         for (header, col_index) in &selected_columns {
             let cell = row.get(*col_index);
-            let value =
-                parse_xlsx_numeric_cell(cell, NumericCellContext::new(row_index, *col_index, header))?;
+            let context = NumericCellContext::new(row_index, *col_index, header);
+            let value = parse_xlsx_numeric_cell(cell, context, options)?;
+            match cell {
+                #[allow(deprecated)]
+                Some(Data::Int(integer)) => warnings.check_integer(*integer, context),
+                Some(Data::String(text)) => warnings.check_integer_text(text, context),
+                _ => {},
+            }
             dataset.get_mut(header)
                    .expect("dataset column exists")
                    .push(value);
         }
     }
 
-    Ok(dataset)
+    Ok((dataset, warnings.finish()))
 }
 
 pub(super) fn build_string_mixed_dataset_from_xlsx(
-    rows_data: Vec<Vec<Data>>,
-    variables: &[String])
+    sheet: SheetRows,
+    variables: &[String],
+    options: &TableReadOptions)
     -> Result<crate::domain::input::string_mixed::StringMixedDataset, String> {
+    let rows_data = sheet.rows;
     if rows_data.is_empty() {
         return Err("Sheet is empty".to_string());
     }
@@ -62,8 +136,8 @@ pub(super) fn build_string_mixed_dataset_from_xlsx(
         return Err("No variables selected".to_string());
     }
 
-    let headers = compute_headers_from_first_row(&rows_data[0])?;
-    let selected_columns = collect_ordered_selected_columns(&headers, variables)?;
+    let headers = compute_headers_from_first_row(&rows_data[0], sheet.first_column, options)?;
+    let selected_columns = collect_ordered_selected_columns(&headers.names, variables)?;
     let row_count = rows_data.len().saturating_sub(1);
 
     let mut dataset =
@@ -72,9 +146,15 @@ pub(super) fn build_string_mixed_dataset_from_xlsx(
         dataset.insert(header.clone(), Vec::with_capacity(row_count));
     }
 
-    for row in rows_data.iter().skip(1) {
+    for (row_index, row) in rows_data.iter().skip(1).enumerate() {
         for (header, col_index) in &selected_columns {
-            let value = row.get(*col_index).and_then(xlsx_cell_to_string);
+            let cell = row.get(*col_index);
+            if let Some(Data::Error(error)) = cell
+               && options.cell_error_policy == CellErrorPolicy::Fail
+            {
+                return Err(cell_error_message(error, row_index, *col_index, header));
+            }
+            let value = cell.and_then(|cell| xlsx_cell_to_string(cell, options));
             dataset.get_mut(header)
                    .expect("dataset column exists")
                    .push(value);
@@ -84,51 +164,521 @@ pub(super) fn build_string_mixed_dataset_from_xlsx(
     Ok(dataset)
 }
 
-pub(super) fn create_parsed_data_table(rows_data: Vec<Vec<Data>>) -> Result<ParsedDataTable, String> {
+pub(super) fn create_parsed_data_table(sheet: SheetRows,
+                                       options: &TableReadOptions)
+                                       -> Result<ParsedDataTable, String> {
+    let rows_data = sheet.rows;
     if rows_data.is_empty() {
         return Ok(ParsedDataTable { headers: vec![],
                                     rows: vec![],
                                     note: None,
-                                    title: None });
+                                    title: None,
+                                    column_types: Vec::new() });
     }
 
-    let headers = compute_headers_from_first_row(&rows_data[0])?;
-    let rows = rows_data.into_iter()
-                        .skip(1)
-                        .map(|row| row.into_iter().map(cell_value_to_json_value).collect())
-                        .collect();
-    let normalized = normalize_rows(rows, headers.len());
+    let headers = compute_headers_from_first_row(&rows_data[0], sheet.first_column, options)?;
+    let mut converted_tokens = 0;
+    let mut converted_errors = 0;
+    let mut column_types = ColumnTypeInference::new(headers.names.len());
+    let mut rows = Vec::with_capacity(rows_data.len().saturating_sub(1));
+    for (row_index, row) in rows_data.into_iter().skip(1).enumerate() {
+        let mut values = Vec::with_capacity(row.len());
+        for (col_index, cell) in row.into_iter().enumerate() {
+            // Dates become serial-number text in the table, so they are typed from the cell.
+            let is_date = matches!(cell, Data::DateTime(_) | Data::DateTimeIso(_));
+            let value = match cell {
+                Data::String(value) if options.is_missing_token(&value) => {
+                    converted_tokens += 1;
+                    serde_json::Value::Null
+                },
+                Data::Error(error) => match options.cell_error_policy {
+                    CellErrorPolicy::Symbol => serde_json::Value::String(error_to_str(&error).to_string()),
+                    CellErrorPolicy::Null => {
+                        converted_errors += 1;
+                        serde_json::Value::Null
+                    },
+                    CellErrorPolicy::Fail => {
+                        let header = headers.names.get(col_index).map_or("", String::as_str);
+                        return Err(cell_error_message(&error, row_index, col_index, header));
+                    },
+                },
+                cell => cell_value_to_json_value(cell),
+            };
+            let cell_type = if is_date {
+                Some(ColumnType::Date)
+            } else {
+                ColumnType::of_cell(&value)
+            };
+            column_types.observe(col_index, cell_type);
+            values.push(value);
+        }
+        rows.push(values);
+    }
+    let normalized = normalize_rows(rows, headers.names.len());
 
-    Ok(ParsedDataTable { headers,
+    Ok(ParsedDataTable { headers: headers.names,
                          rows: normalized.rows,
-                         note: normalized.note,
-                         title: None })
+                         note: join_notes([headers.note,
+                                           missing_token_note(converted_tokens),
+                                           cell_error_note(converted_errors),
+                                           normalized.note]),
+                         title: None,
+                         column_types: column_types.finish() })
 }
 
-pub(super) fn get_xlsx_sheets(path: &str) -> Result<Vec<String>, String> {
+/// Lists the workbook's sheets in tab order. Hidden sheets are only included with `include_hidden`,
+/// but `read_xlsx_sheet_rows` can still read them by name.
+pub(super) fn get_xlsx_sheets(path: &str,
+                              include_hidden: bool)
+                              -> Result<Vec<SheetInfo>, String> {
     let workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let names = workbook.sheet_names().clone();
-    if names.is_empty() {
+    let metadata = workbook.sheets_metadata();
+    if metadata.is_empty() {
         return Err("Failed to get sheet names".to_string());
     }
-    Ok(names)
+    let sheets: Vec<SheetInfo> =
+        metadata.iter()
+                .map(|sheet| SheetInfo { name: sheet.name.clone(),
+                                         visible: sheet.visible == SheetVisible::Visible })
+                .filter(|sheet| include_hidden || sheet.visible)
+                .collect();
+    if sheets.is_empty() {
+        return Err("Workbook has no visible sheets (enable include_hidden to list hidden sheets)".to_string());
+    }
+    Ok(sheets)
 }
 
+/// Reads the rows of a sheet's table part, header row first (see `table_range`).
 pub(super) fn read_xlsx_sheet_rows(path: &str,
-                                   sheet: &str)
-                                   -> Result<Vec<Vec<Data>>, String> {
+                                   sheet: &str,
+                                   options: &TableReadOptions)
+                                   -> Result<SheetRows, String> {
     let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
     let range = workbook.worksheet_range(sheet)
                         .map_err(|e| format!("Failed to read sheet: {}", e))?;
-    Ok(range.rows().map(|row| row.to_vec()).collect())
+    Ok(SheetRows::from_range(&table_range(range, options)?))
+}
+
+/// Narrows a sheet range to the part read as a table: the cells inside `options.range`, clamped
+/// to the sheet's data, or the rows from `options.header_row` on. The result keeps the sheet
+/// positions of its cells.
+fn table_range(range: Range<Data>,
+               options: &TableReadOptions)
+               -> Result<Range<Data>, String> {
+    let cell_range = options.cell_range()?;
+    if cell_range.is_some() && options.header_row.is_some() {
+        return Err("header_row cannot be combined with range; the range's first row is the header".to_string());
+    }
+    let (Some((first_row, first_col)), Some((last_row, last_col))) = (range.start(), range.end()) else {
+        rows_above_header(None, 0, options)?;
+        return Ok(range);
+    };
+    if let Some(cell_range) = cell_range {
+        let start = (cell_range.start.0.max(first_row), cell_range.start.1.max(first_col));
+        let end = (cell_range.end.0.min(last_row), cell_range.end.1.min(last_col));
+        if start.0 > end.0 || start.1 > end.1 {
+            return Ok(Range::empty());
+        }
+        return Ok(range.range(start, end));
+    }
+    match rows_above_header(Some(first_row), range.height(), options)? {
+        0 => Ok(range),
+        skipped => Ok(range.range((first_row + skipped as u32, first_col), (last_row, last_col))),
+    }
+}
+
+/// Number of rows of a sheet range to skip so that it starts at `options.header_row`.
+/// `first_row` is the 0-based sheet row the range starts at (calamine starts it at the first
+/// non-empty row) and `height` its row count.
+fn rows_above_header(first_row: Option<u32>,
+                     height: usize,
+                     options: &TableReadOptions)
+                     -> Result<usize, String> {
+    let Some(header_row) = options.header_row else {
+        return Ok(0);
+    };
+    if header_row == 0 {
+        return Err("header_row is 1-based and must be at least 1".to_string());
+    }
+    let first_row = first_row.map_or(1, |row| row as usize + 1);
+    if header_row < first_row {
+        return Err(format!("header_row {} is above the first non-empty row ({})",
+                           header_row, first_row));
+    }
+    let skipped = header_row - first_row;
+    if skipped >= height {
+        return Err(format!("header_row {} is beyond the last row of the sheet ({})",
+                           header_row,
+                           first_row + height - 1));
+    }
+    Ok(skipped)
+}
+
+/// Computes the column names of a sheet the same way `create_parsed_data_table` does.
+/// The column extent (and so the names of blank header cells) depends on every row,
+/// so an `.xlsx` sheet is still scanned to the end, but only the header row's values are kept.
+pub(super) fn read_xlsx_headers(path: &str,
+                                sheet: &str,
+                                options: &TableReadOptions)
+                                -> Result<Vec<String>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let (header_row, first_column) = match &mut workbook {
+        // The streaming scan covers whole sheets; a bounded range is cut from the loaded range.
+        Sheets::Xlsx(xlsx) if options.range.is_none() => scan_xlsx_header_row(xlsx, sheet, options)?,
+        _ => {
+            let range = workbook.worksheet_range(sheet)
+                                .map_err(|e| format!("Failed to read sheet: {}", e))?;
+            let range = table_range(range, options)?;
+            let first_column = range.start().map_or(0, |(_, col)| col as usize);
+            (range.rows().next().map(|row| row.to_vec()).unwrap_or_default(), first_column)
+        },
+    };
+    if header_row.is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(compute_headers_from_first_row(&header_row, first_column, options)?.names)
+}
+
+/// Mirrors how calamine bounds a worksheet range: the first row and the column span are those
+/// of the non-empty cells. The header is the first of those rows unless `options.header_row`
+/// names another one. Returns the header cells and the 0-based sheet column of the first one.
+fn scan_xlsx_header_row<RS: Read + Seek>(workbook: &mut Xlsx<RS>,
+                                         sheet: &str,
+                                         options: &TableReadOptions)
+                                         -> Result<(Vec<Data>, usize), String> {
+    let mut reader = workbook.worksheet_cells_reader(sheet)
+                             .map_err(|e| format!("Failed to read sheet: {}", e))?;
+    let mut cells = Vec::new();
+    let (mut row_start, mut row_end) = (u32::MAX, 0);
+    let (mut col_start, mut col_end) = (u32::MAX, 0);
+    while let Some(cell) = reader.next_cell()
+                                 .map_err(|e| format!("Failed to read sheet: {}", e))?
+    {
+        if matches!(cell.get_value(), DataRef::Empty) {
+            continue;
+        }
+        let (row, col) = cell.get_position();
+        row_start = row_start.min(row);
+        row_end = row_end.max(row);
+        col_start = col_start.min(col);
+        col_end = col_end.max(col);
+        // Rows below a fixed header row can be dropped right away; otherwise the header is only
+        // known once the scan is over.
+        let header_candidate = match options.header_row {
+            Some(header_row) => row as usize + 1 == header_row,
+            None => row <= row_start,
+        };
+        if header_candidate {
+            cells.push((row, col, Data::from(cell.get_value().clone())));
+        }
+    }
+    let height = if row_start > row_end {
+        0
+    } else {
+        (row_end - row_start + 1) as usize
+    };
+    let header_row =
+        row_start as usize + rows_above_header((height > 0).then_some(row_start), height, options)?;
+    let header_cells: Vec<(u32, Data)> = cells.into_iter()
+                                              .filter(|(row, _, _)| *row as usize == header_row)
+                                              .map(|(_, col, value)| (col, value))
+                                              .collect();
+    if header_cells.is_empty() {
+        return Ok((vec![], 0));
+    }
+
+    let mut row = vec![Data::Empty; (col_end - col_start + 1) as usize];
+    for (col, value) in header_cells {
+        row[(col - col_start) as usize] = value;
+    }
+    Ok((row, col_start as usize))
+}
+
+/// Reads the formula text of a sheet as a table parallel to the value table: same headers and
+/// row/column positions, with `=`-prefixed formulas where a cell has one and null elsewhere.
+pub(super) fn read_xlsx_formula_table(path: &str,
+                                      sheet: &str,
+                                      options: &TableReadOptions)
+                                      -> Result<ParsedDataTable, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let values = workbook.worksheet_range(sheet)
+                         .map_err(|e| format!("Failed to read sheet: {}", e))?;
+    let values = table_range(values, options)?;
+    let formulas = workbook.worksheet_formula(sheet)
+                           .map_err(|e| format!("Failed to read formulas: {}", e))?;
+
+    let Some((start_row, start_col)) = values.start() else {
+        return Ok(ParsedDataTable { headers: vec![],
+                                    rows: vec![],
+                                    note: None,
+                                    title: None,
+                                    column_types: Vec::new() });
+    };
+    let mut value_rows = values.rows();
+    let header_row = value_rows.next().unwrap_or_default();
+    let headers = compute_headers_from_first_row(header_row, start_col as usize, options)?;
+
+    let column_count = headers.names.len() as u32;
+    let rows = (0..value_rows.count() as u32).map(|row_offset| {
+                   let row = start_row + 1 + row_offset;
+                   (start_col..start_col + column_count).map(|col| formula_cell(&formulas, row, col))
+                                                        .collect()
+               })
+               .collect();
+
+    Ok(ParsedDataTable { headers: headers.names,
+                         rows,
+                         note: headers.note,
+                         title: None,
+                         column_types: Vec::new() })
+}
+
+fn formula_cell(formulas: &Range<String>,
+                row: u32,
+                col: u32)
+                -> serde_json::Value {
+    match formulas.get_value((row, col)) {
+        Some(formula) if !formula.is_empty() => serde_json::Value::String(format!("={}", formula)),
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// Reads an Excel table (ListObject) by name. The header row comes from the table definition,
+/// and only the table's own data range is read, so surrounding cells on the sheet are ignored.
+pub(super) fn read_xlsx_named_table(path: &str,
+                                    table_name: &str,
+                                    options: &TableReadOptions)
+                                    -> Result<ParsedDataTable, String> {
+    let mut workbook: Xlsx<_> = open_workbook(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    workbook.load_tables()
+            .map_err(|e| format!("Failed to read tables: {}", e))?;
+
+    let names: Vec<String> = workbook.table_names().into_iter().cloned().collect();
+    if !names.iter().any(|name| name == table_name) {
+        let available = if names.is_empty() {
+            "(none)".to_string()
+        } else {
+            names.join(", ")
+        };
+        return Err(format!("Table '{}' was not found. Available tables: {}",
+                           table_name, available));
+    }
+
+    let table = workbook.table_by_name(table_name)
+                        .map_err(|e| format!("Failed to read table: {}", e))?;
+    let header_row: Vec<Data> = table.columns()
+                                     .iter()
+                                     .map(|column| Data::String(column.clone()))
+                                     .collect();
+    let data = table.data();
+    let mut rows_data = vec![header_row];
+    rows_data.extend(data.rows().map(|row| row.to_vec()));
+    // The table's header row sits directly above its data.
+    let (data_row, first_column) = data.start().unwrap_or_default();
+    let sheet = SheetRows { rows: rows_data,
+                            first_row: (data_row as usize).saturating_sub(1),
+                            first_column: first_column as usize };
+
+    let mut parsed = create_parsed_data_table(sheet, options)?;
+    parsed.title = Some(table_name.to_string());
+    Ok(parsed)
+}
+
+/// Parses the sheet, then reads its raw cells again and reports every non-null parsed cell that
+/// disagrees with the source: numbers (and date serials) within `NUMBER_TOLERANCE`, the rest exactly.
+pub(super) fn verify_xlsx_parse(path: &str,
+                                sheet: &str,
+                                options: &TableReadOptions)
+                                -> Result<Vec<ParseMismatch>, String> {
+    let sheet_rows = read_xlsx_sheet_rows(path, sheet, options)?;
+    let origin = (sheet_rows.first_row, sheet_rows.first_column);
+    let parsed = create_parsed_data_table(sheet_rows, options)?;
+    Ok(find_mismatches(&parsed, origin, &read_raw_cells(path, sheet)?))
+}
+
+/// Reads every stored cell of a sheet by its 0-based sheet position. The cells come straight
+/// from the worksheet, not through `table_range`, so a misplaced table shows up as mismatches.
+fn read_raw_cells(path: &str,
+                  sheet: &str)
+                  -> Result<HashMap<(u32, u32), Data>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut cells = HashMap::new();
+    match &mut workbook {
+        Sheets::Xlsx(xlsx) => {
+            let mut reader = xlsx.worksheet_cells_reader(sheet)
+                                 .map_err(|e| format!("Failed to read sheet: {}", e))?;
+            while let Some(cell) = reader.next_cell()
+                                         .map_err(|e| format!("Failed to read sheet: {}", e))?
+            {
+                cells.insert(cell.get_position(), Data::from(cell.get_value().clone()));
+            }
+        },
+        _ => {
+            let range = workbook.worksheet_range(sheet)
+                                .map_err(|e| format!("Failed to read sheet: {}", e))?;
+            let (first_row, first_col) = range.start().unwrap_or_default();
+            for (row, col, value) in range.used_cells() {
+                cells.insert((first_row + row as u32, first_col + col as u32), value.clone());
+            }
+        },
+    }
+    Ok(cells)
+}
+
+/// Compares a parsed table whose header cell is at the 0-based sheet position `origin` with the
+/// sheet's raw cells. Mismatches are reported by their 1-based sheet row.
+fn find_mismatches(parsed: &ParsedDataTable,
+                   (first_row, first_column): (usize, usize),
+                   source_cells: &HashMap<(u32, u32), Data>)
+                   -> Vec<ParseMismatch> {
+    let mut mismatches = Vec::new();
+    for (row_index, row) in parsed.rows.iter().enumerate() {
+        let sheet_row = first_row + 1 + row_index;
+        for (col_index, value) in row.iter().enumerate() {
+            if value.is_null() {
+                continue;
+            }
+            let position = (sheet_row as u32, (first_column + col_index) as u32);
+            let source = source_cells.get(&position).unwrap_or(&Data::Empty);
+            if !xlsx_cell_matches(source, value) {
+                mismatches.push(ParseMismatch { row: sheet_row + 1,
+                                                column: parsed.headers[col_index].clone(),
+                                                parsed: value.clone(),
+                                                source: source.to_string() });
+            }
+        }
+    }
+    mismatches
+}
+
+fn xlsx_cell_matches(source: &Data,
+                     parsed: &serde_json::Value)
+                     -> bool {
+    match source {
+        Data::Empty => false,
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => {
+            parsed.as_str() == Some(value.as_str())
+        },
+        Data::Float(value) => match special_value_to_str(*value) {
+            Some(special) => parsed.as_str() == Some(special),
+            None => parsed.as_f64()
+                          .is_some_and(|parsed| numbers_match(parsed, *value)),
+        },
+        #[allow(deprecated)]
+        Data::Int(value) => parsed.as_i64() == Some(*value),
+        Data::Bool(value) => parsed.as_bool() == Some(*value),
+        Data::DateTime(value) => parsed.as_str()
+                                       .and_then(|text| text.parse::<f64>().ok())
+                                       .is_some_and(|serial| numbers_match(serial, value.as_f64())),
+        Data::Error(error) => parsed.as_str() == Some(error_to_str(error)),
+    }
+}
+
+/// Checks every sheet in the workbook without failing on a single bad sheet.
+/// Only a workbook that cannot be opened at all is an error.
+pub(super) fn validate_xlsx_workbook(path: &str,
+                                     options: &TableReadOptions)
+                                     -> Result<Vec<SheetReadiness>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let sheet_names = workbook.sheet_names();
+
+    let mut results = Vec::with_capacity(sheet_names.len());
+    for name in sheet_names {
+        let readiness = match workbook.worksheet_range(&name) {
+            Ok(range) => match table_range(range, options) {
+                Ok(range) => assess_sheet_rows(name, &SheetRows::from_range(&range), options),
+                Err(e) => SheetReadiness { name,
+                                           error: Some(e),
+                                           ..SheetReadiness::default() },
+            },
+            Err(e) => SheetReadiness { name,
+                                       error: Some(format!("Failed to read sheet: {}", e)),
+                                       ..SheetReadiness::default() },
+        };
+        results.push(readiness);
+    }
+    Ok(results)
+}
+
+/// Same header naming as `create_parsed_data_table`, but duplicates are reported instead of rejected.
+fn assess_sheet_rows(name: String,
+                     sheet: &SheetRows,
+                     options: &TableReadOptions)
+                     -> SheetReadiness {
+    let rows = &sheet.rows;
+    let Some(header_row) = rows.first() else {
+        return SheetReadiness { name,
+                                readable: true,
+                                ..SheetReadiness::default() };
+    };
+    let headers: Vec<String> =
+        header_row.iter()
+                  .enumerate()
+                  .map(|(index, cell)| cell_value_to_header_name(cell, index, sheet.first_column, options))
+                  .collect();
+    let duplicates = duplicate_headers(&headers);
+    let numeric_column_count =
+        (0..headers.len()).filter(|&col_index| is_numeric_column(&rows[1..], col_index, &headers, options))
+                          .count();
+    let numeric_cell_count = rows[1..].iter()
+                                      .enumerate()
+                                      .map(|(row_index, row)| {
+                                          (0..headers.len()).filter(|&col_index| {
+                                              let context = NumericCellContext::new(row_index,
+                                                                                    col_index,
+                                                                                    &headers[col_index]);
+                                              matches!(parse_xlsx_numeric_cell(row.get(col_index),
+                                                                               context,
+                                                                               options),
+                                                       Ok(Some(_)))
+                                          })
+                                          .count()
+                                      })
+                                      .sum();
+    let text_header_count = header_row.iter()
+                                      .filter(|cell| {
+                                          matches!(cell, Data::String(value)
+                                                   if !value.trim().is_empty() && !options.is_missing_token(value))
+                                      })
+                                      .count();
+
+    SheetReadiness { name,
+                     readable: true,
+                     error: None,
+                     has_duplicate_headers: !duplicates.is_empty(),
+                     duplicate_headers: duplicates,
+                     row_count: rows.len() - 1,
+                     column_count: headers.len(),
+                     numeric_column_count,
+                     numeric_cell_count,
+                     text_header_count }
+}
+
+fn is_numeric_column(data_rows: &[Vec<Data>],
+                     col_index: usize,
+                     headers: &[String],
+                     options: &TableReadOptions)
+                     -> bool {
+    let mut has_value = false;
+    for (row_index, row) in data_rows.iter().enumerate() {
+        let context = NumericCellContext::new(row_index, col_index, &headers[col_index]);
+        match parse_xlsx_numeric_cell(row.get(col_index), context, options) {
+            Ok(Some(_)) => has_value = true,
+            Ok(None) => {},
+            Err(_) => return false,
+        }
+    }
+    has_value
 }
 
 fn parse_xlsx_numeric_cell(cell: Option<&Data>,
-                           context: NumericCellContext<'_>)
+                           context: NumericCellContext<'_>,
+                           options: &TableReadOptions)
                            -> Result<Option<f64>, String> {
     match cell {
         None => Ok(None),
         Some(Data::Empty) => Ok(None),
+        Some(Data::String(value)) if options.is_missing_token(value) => Ok(None),
         Some(Data::String(value)) => parse_numeric_string(value, context),
         Some(Data::Float(value)) => parse_finite_number(*value, context),
         #[allow(deprecated)]
@@ -137,19 +687,21 @@ fn parse_xlsx_numeric_cell(cell: Option<&Data>,
         Some(Data::DateTime(_)) | Some(Data::DateTimeIso(_)) | Some(Data::DurationIso(_)) => {
             Err(context.error("datetime value is not allowed"))
         },
+        Some(Data::Error(_)) if options.cell_error_policy == CellErrorPolicy::Null => Ok(None),
         Some(Data::Error(_)) => Err(context.error("cell has an Excel error")),
     }
 }
 
-fn xlsx_cell_to_string(cell: &Data) -> Option<String> {
+fn xlsx_cell_to_string(cell: &Data,
+                       options: &TableReadOptions)
+                       -> Option<String> {
     match cell {
         Data::Empty => None,
         Data::String(value) => {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
+            if options.is_missing_text(value) {
                 None
             } else {
-                Some(trimmed.to_string())
+                Some(value.trim().to_string())
             }
         },
         Data::Float(value) => {
@@ -165,27 +717,40 @@ fn xlsx_cell_to_string(cell: &Data) -> Option<String> {
         Data::DateTime(value) => Some(value.to_string()),
         Data::DateTimeIso(value) => Some(value.clone()),
         Data::DurationIso(value) => Some(value.clone()),
+        Data::Error(_) if options.cell_error_policy == CellErrorPolicy::Null => None,
         Data::Error(value) => Some(error_to_str(value).to_string()),
     }
 }
 
-fn compute_headers_from_first_row(row0: &[Data]) -> Result<Vec<String>, String> {
-    let headers: Vec<String> = row0.iter()
-                                   .enumerate()
-                                   .map(|(index, cell)| cell_value_to_header_name(cell, index))
-                                   .collect();
-    validate_unique_headers(&headers)?;
-    Ok(headers)
+fn compute_headers_from_first_row(row0: &[Data],
+                                  first_column: usize,
+                                  options: &TableReadOptions)
+                                  -> Result<SheetHeaders, String> {
+    let names: Vec<String> =
+        row0.iter()
+            .enumerate()
+            .map(|(index, cell)| cell_value_to_header_name(cell, index, first_column, options))
+            .collect();
+    validate_unique_headers(&names)?;
+
+    let note = if row0.iter().any(|cell| matches!(cell, Data::Bool(_))) {
+        Some("ヘッダ行に真偽値 (TRUE/FALSE) のセルが含まれています。ヘッダ行の指定が正しいか確認してください".to_string())
+    } else {
+        None
+    };
+    Ok(SheetHeaders { names, note })
 }
 
 fn cell_value_to_header_name(cell: &Data,
-                             col_index: usize)
+                             col_index: usize,
+                             first_column: usize,
+                             options: &TableReadOptions)
                              -> String {
     match cell {
         Data::String(value) => {
             let trimmed = value.trim();
             if trimmed.is_empty() {
-                format!("col_{}", col_index + 1)
+                options.blank_header_name(col_index, first_column)
             } else {
                 trimmed.to_string()
             }
@@ -195,16 +760,22 @@ fn cell_value_to_header_name(cell: &Data,
                 special.to_string()
             } else {
                 serde_json::Number::from_f64(*value).map(|number| number.to_string())
-                                                    .unwrap_or_else(|| format!("col_{}", col_index + 1))
+                                                    .unwrap_or_else(|| {
+                                                        options.blank_header_name(col_index, first_column)
+                                                    })
             }
         },
         #[allow(deprecated)]
         Data::Int(value) => value.to_string(),
-        Data::Bool(_) => "TRUE/FALSE".to_string(),
+        Data::Bool(value) => match options.bool_header_strategy {
+            BoolHeaderStrategy::Placeholder => "TRUE/FALSE".to_string(),
+            BoolHeaderStrategy::Literal => if *value { "TRUE" } else { "FALSE" }.to_string(),
+            BoolHeaderStrategy::ColumnN => options.blank_header_name(col_index, first_column),
+        },
         Data::DateTime(value) => value.to_string(),
         Data::DateTimeIso(value) => value.to_string(),
         Data::DurationIso(value) => value.to_string(),
-        Data::Empty => format!("col_{}", col_index + 1),
+        Data::Empty => options.blank_header_name(col_index, first_column),
         Data::Error(value) => error_to_str(value).to_string(),
     }
 }
@@ -227,6 +798,7 @@ fn cell_value_to_json_value(cell: Data) -> serde_json::Value {
                                                    .unwrap_or(serde_json::Value::Null)
             }
         },
+        // Kept as a JSON integer (never routed through f64) so IDs beyond 2^53 stay exact.
         #[allow(deprecated)]
         Data::Int(value) => serde_json::Value::from(value),
         Data::Bool(value) => serde_json::Value::Bool(value),
@@ -250,6 +822,19 @@ fn error_to_str(error: &CellErrorType) -> &'static str {
     }
 }
 
+/// Row and column numbers match `NumericCellContext`: 1-based, counting the header row as row 1.
+fn cell_error_message(error: &CellErrorType,
+                      row_index: usize,
+                      col_index: usize,
+                      header: &str)
+                      -> String {
+    format!("Excel error {} at row {} col {} ({})",
+            error_to_str(error),
+            row_index + 2,
+            col_index + 1,
+            header)
+}
+
 fn special_value_to_str(value: f64) -> Option<&'static str> {
     if value.is_nan() {
         return Some("NaN!");
@@ -259,3 +844,604 @@ fn special_value_to_str(value: f64) -> Option<&'static str> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::input::read_options::BlankHeaderStrategy;
+    use crate::domain::input::sheet::guess_data_sheet;
+
+    fn sheet_with_bool_header() -> Vec<Vec<Data>> {
+        vec![vec![Data::String("id".to_string()),
+                  Data::Bool(true),
+                  Data::Bool(false)],
+             vec![Data::Float(1.0), Data::Float(2.0), Data::Float(3.0)],]
+    }
+
+    fn options(strategy: BoolHeaderStrategy) -> TableReadOptions {
+        TableReadOptions { bool_header_strategy: strategy,
+                           ..TableReadOptions::default() }
+    }
+
+    #[test]
+    fn bool_header_uses_literal_value_by_default() {
+        let table =
+            create_parsed_data_table(sheet_with_bool_header().into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["id", "TRUE", "FALSE"]);
+        assert!(table.note.unwrap().contains("真偽値"));
+    }
+
+    #[test]
+    fn bool_header_strategy_column_n_falls_back_to_column_index() {
+        let table = create_parsed_data_table(sheet_with_bool_header().into(),
+                                             &options(BoolHeaderStrategy::ColumnN)).unwrap();
+
+        assert_eq!(table.headers, vec!["id", "col_2", "col_3"]);
+    }
+
+    #[test]
+    fn bool_header_strategy_placeholder_keeps_legacy_name() {
+        let rows = vec![vec![Data::String("id".to_string()), Data::Bool(true)]];
+        let table = create_parsed_data_table(rows.into(), &options(BoolHeaderStrategy::Placeholder)).unwrap();
+
+        assert_eq!(table.headers, vec!["id", "TRUE/FALSE"]);
+    }
+
+    #[test]
+    fn header_without_bool_has_no_note() {
+        let rows = vec![vec![Data::String("a".to_string()), Data::String("b".to_string())]];
+        let table = create_parsed_data_table(rows.into(), &TableReadOptions::default()).unwrap();
+
+        assert!(table.note.is_none());
+    }
+
+    fn sheet_with_blank_fourth_header() -> Vec<Vec<Data>> {
+        vec![vec![Data::String("a".to_string()),
+                  Data::String("b".to_string()),
+                  Data::String("c".to_string()),
+                  Data::Empty],
+             vec![Data::Float(1.0),
+                  Data::Float(2.0),
+                  Data::Float(3.0),
+                  Data::Float(4.0)],]
+    }
+
+    fn blank_header(strategy: BlankHeaderStrategy,
+                    prefix: &str)
+                    -> TableReadOptions {
+        TableReadOptions { blank_header_strategy: strategy,
+                           blank_header_prefix: prefix.to_string(),
+                           ..TableReadOptions::default() }
+    }
+
+    #[test]
+    fn blank_header_strategy_column_n_is_the_default() {
+        let table = create_parsed_data_table(sheet_with_blank_fourth_header().into(),
+                                             &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["a", "b", "c", "col_4"]);
+    }
+
+    #[test]
+    fn blank_header_strategy_excel_letter_names_the_column_by_position() {
+        let table = create_parsed_data_table(sheet_with_blank_fourth_header().into(),
+                                             &blank_header(BlankHeaderStrategy::ExcelLetter, "")).unwrap();
+        assert_eq!(table.headers, vec!["a", "b", "c", "D"]);
+
+        let options = blank_header(BlankHeaderStrategy::ExcelLetter, "");
+        assert_eq!(options.blank_header_name(25, 0), "Z");
+        assert_eq!(options.blank_header_name(26, 0), "AA");
+        assert_eq!(options.blank_header_name(701, 0), "ZZ");
+        assert_eq!(options.blank_header_name(702, 0), "AAA");
+    }
+
+    #[test]
+    fn excel_letter_names_the_sheet_column_when_data_starts_in_column_c() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offset.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write(0, 3, "score").unwrap();
+        sheet.write_row(1, 2, [1, 10]).unwrap();
+        sheet.write_row(2, 2, [2, 20]).unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let options = blank_header(BlankHeaderStrategy::ExcelLetter, "");
+
+        let parsed = create_parsed_data_table(read_xlsx_sheet_rows(path, "Data", &options).unwrap(),
+                                              &options).unwrap();
+        assert_eq!(parsed.headers, vec!["C", "score"]);
+        assert_eq!(read_xlsx_headers(path, "Data", &options).unwrap(), parsed.headers);
+        assert_eq!(read_xlsx_formula_table(path, "Data", &options).unwrap().headers,
+                   parsed.headers);
+        let ranged = TableReadOptions { range: Some("C1:D3".to_string()),
+                                        ..options.clone() };
+        assert_eq!(read_xlsx_headers(path, "Data", &ranged).unwrap(), parsed.headers);
+    }
+
+    #[test]
+    fn blank_header_strategy_custom_prefix_uses_the_prefix() {
+        let table = create_parsed_data_table(sheet_with_blank_fourth_header().into(),
+                                             &blank_header(BlankHeaderStrategy::CustomPrefix, "列")).unwrap();
+
+        assert_eq!(table.headers, vec!["a", "b", "c", "列4"]);
+    }
+
+    #[test]
+    fn generated_blank_header_still_goes_through_duplicate_detection() {
+        let rows = vec![vec![Data::String("D".to_string()),
+                             Data::String("b".to_string()),
+                             Data::String("c".to_string()),
+                             Data::Empty]];
+
+        assert!(create_parsed_data_table(rows.into(), &blank_header(BlankHeaderStrategy::ExcelLetter, "")).is_err());
+    }
+
+    #[test]
+    fn sixteen_digit_id_is_preserved_exactly_in_parsed_table() {
+        let rows = vec![vec![Data::String("id".to_string()), Data::String("big".to_string())],
+                        vec![Data::Float(1234567890123456.0), Data::Int(9007199254740993)],];
+        let table = create_parsed_data_table(rows.into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.rows[0][0].as_f64(), Some(1234567890123456.0));
+        assert_eq!(table.rows[0][1].to_string(), "9007199254740993");
+    }
+
+    #[test]
+    fn excel_dates_are_typed_as_dates() {
+        let rows = vec![vec![Data::String("day".to_string()), Data::String("n".to_string())],
+                        vec![Data::DateTimeIso("2024-01-15T00:00:00".to_string()),
+                             Data::Float(1.5)],
+                        vec![Data::Empty, Data::Int(2)]];
+        let table = create_parsed_data_table(rows.into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.column_types, vec![ColumnType::Date, ColumnType::Numeric]);
+    }
+
+    #[test]
+    fn integer_beyond_exact_range_loads_with_a_cell_warning() {
+        let rows = vec![vec![Data::String("id".to_string())],
+                        vec![Data::Int(9007199254740993)],
+                        vec![Data::String("9007199254740995".to_string())],
+                        vec![Data::Int(42)]];
+        let (dataset, warnings) = build_numeric_dataset_from_xlsx(rows.into(),
+                                                                  &["id".to_string()],
+                                                                  &TableReadOptions::default()).unwrap();
+
+        assert_eq!(dataset["id"].len(), 3);
+        assert_eq!(dataset["id"][2], Some(42.0));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("row 2 col 1 (id)") && warnings[0].contains("2^53"),
+                "{}",
+                warnings[0]);
+        assert!(warnings[1].contains("row 3 col 1"), "{}", warnings[1]);
+    }
+
+    #[test]
+    fn whole_number_floats_stay_floats_in_the_parsed_table() {
+        let rows = vec![vec![Data::String("x".to_string())], vec![Data::Float(3.0)]];
+        let table = create_parsed_data_table(rows.into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.rows[0][0], serde_json::json!(3.0));
+        assert!(table.rows[0][0].is_f64());
+    }
+
+    #[test]
+    fn missing_tokens_become_null_but_similar_words_stay_strings() {
+        let rows = vec![vec![Data::String("code".to_string())],
+                        vec![Data::String("NA".to_string())],
+                        vec![Data::String(" n/a ".to_string())],
+                        vec![Data::String("NATO".to_string())],];
+        let table = create_parsed_data_table(rows.into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.rows[0][0], serde_json::Value::Null);
+        assert_eq!(table.rows[1][0], serde_json::Value::Null);
+        assert_eq!(table.rows[2][0], serde_json::Value::String("NATO".to_string()));
+        assert!(table.note.unwrap().contains("2 個"));
+    }
+
+    #[test]
+    fn missing_tokens_are_null_in_numeric_dataset() {
+        let rows = vec![vec![Data::String("score".to_string())],
+                        vec![Data::String("N/A".to_string())],
+                        vec![Data::Float(3.5)],];
+        let (dataset, _) = build_numeric_dataset_from_xlsx(rows.into(),
+                                                           &["score".to_string()],
+                                                           &TableReadOptions::default()).unwrap();
+
+        assert_eq!(dataset["score"], vec![None, Some(3.5)]);
+    }
+
+    #[test]
+    fn case_sensitive_missing_tokens_keep_other_casing() {
+        let options = TableReadOptions { missing_tokens_case_sensitive: true,
+                                         ..TableReadOptions::default() };
+        let rows = vec![vec![Data::String("code".to_string())],
+                        vec![Data::String("na".to_string())]];
+        let table = create_parsed_data_table(rows.into(), &options).unwrap();
+
+        assert_eq!(table.rows[0][0], serde_json::Value::String("na".to_string()));
+    }
+
+    fn sheet_with_div0_cell() -> Vec<Vec<Data>> {
+        vec![vec![Data::String("x".to_string()), Data::String("ratio".to_string())],
+             vec![Data::Float(1.0), Data::Float(0.5)],
+             vec![Data::Float(2.0), Data::Error(CellErrorType::Div0)],]
+    }
+
+    fn error_policy(policy: CellErrorPolicy) -> TableReadOptions {
+        TableReadOptions { cell_error_policy: policy,
+                           ..TableReadOptions::default() }
+    }
+
+    #[test]
+    fn cell_error_policy_symbol_keeps_error_text_by_default() {
+        let table =
+            create_parsed_data_table(sheet_with_div0_cell().into(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.rows[1][1], serde_json::Value::String("#DIV/0!".to_string()));
+        assert!(table.note.is_none());
+    }
+
+    #[test]
+    fn cell_error_policy_null_reads_error_cells_as_missing() {
+        let options = error_policy(CellErrorPolicy::Null);
+        let table = create_parsed_data_table(sheet_with_div0_cell().into(), &options).unwrap();
+
+        assert_eq!(table.rows[1][1], serde_json::Value::Null);
+        assert!(table.note.unwrap().contains("1 個"));
+
+        let (dataset, _) = build_numeric_dataset_from_xlsx(sheet_with_div0_cell().into(),
+                                                           &["ratio".to_string()],
+                                                           &options).unwrap();
+        assert_eq!(dataset["ratio"], vec![Some(0.5), None]);
+    }
+
+    #[test]
+    fn cell_error_policy_fail_names_the_error_cell() {
+        let err = create_parsed_data_table(sheet_with_div0_cell().into(),
+                                           &error_policy(CellErrorPolicy::Fail)).unwrap_err();
+
+        assert_eq!(err, "Excel error #DIV/0! at row 3 col 2 (ratio)");
+    }
+
+    #[test]
+    fn hidden_sheets_are_listed_only_on_request_but_stay_readable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hidden.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        workbook.add_worksheet()
+                .set_name("Data")
+                .unwrap()
+                .write(0, 0, "x")
+                .unwrap();
+        workbook.add_worksheet()
+                .set_name("Helper")
+                .unwrap()
+                .set_hidden(true)
+                .write(0, 0, "lookup")
+                .unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+
+        let visible = get_xlsx_sheets(path, false).unwrap();
+        assert_eq!(visible,
+                   vec![SheetInfo { name: "Data".to_string(),
+                                    visible: true }]);
+
+        let all = get_xlsx_sheets(path, true).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1],
+                   SheetInfo { name: "Helper".to_string(),
+                               visible: false });
+
+        let rows = read_xlsx_sheet_rows(path, "Helper", &TableReadOptions::default()).unwrap();
+        assert_eq!(rows.rows[0][0], Data::String("lookup".to_string()));
+    }
+
+    #[test]
+    fn named_table_is_read_with_its_own_header_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tables.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.write(0, 0, "report title").unwrap();
+        sheet.write(3, 1, 12).unwrap();
+        sheet.write(3, 2, "apple").unwrap();
+        sheet.write(4, 1, 7.5).unwrap();
+        sheet.write(4, 2, "NA").unwrap();
+        let columns = [rust_xlsxwriter::TableColumn::new().set_header("qty"),
+                       rust_xlsxwriter::TableColumn::new().set_header("item")];
+        let table = rust_xlsxwriter::Table::new().set_name("Inventory")
+                                                 .set_columns(&columns);
+        sheet.add_table(2, 1, 4, 2, &table).unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+
+        let parsed = read_xlsx_named_table(path, "Inventory", &TableReadOptions::default()).unwrap();
+
+        assert_eq!(parsed.headers, vec!["qty".to_string(), "item".to_string()]);
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.rows[0][0], serde_json::json!(12.0));
+        assert_eq!(parsed.rows[1][1], serde_json::Value::Null);
+        assert_eq!(parsed.title.as_deref(), Some("Inventory"));
+
+        let err = read_xlsx_named_table(path, "Sales", &TableReadOptions::default()).unwrap_err();
+        assert!(err.contains("Available tables: Inventory"), "{}", err);
+    }
+
+    #[test]
+    fn workbook_validation_reports_duplicate_headers_per_sheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mixed.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let clean = workbook.add_worksheet().set_name("Clean").unwrap();
+        clean.write_row(0, 0, ["id", "score", "group"]).unwrap();
+        clean.write(1, 0, 1).unwrap();
+        clean.write(1, 1, 3.5).unwrap();
+        clean.write(1, 2, "A").unwrap();
+        clean.write(2, 0, 2).unwrap();
+        clean.write(2, 1, "NA").unwrap();
+        clean.write(2, 2, "B").unwrap();
+        let duplicated = workbook.add_worksheet().set_name("Dup").unwrap();
+        duplicated.write_row(0, 0, ["x", "y", "x"]).unwrap();
+        duplicated.write_row(1, 0, [1, 2, 3]).unwrap();
+        workbook.save(&path).unwrap();
+
+        let results = validate_xlsx_workbook(path.to_str().unwrap(), &TableReadOptions::default()).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0],
+                   SheetReadiness { name: "Clean".to_string(),
+                                    readable: true,
+                                    error: None,
+                                    has_duplicate_headers: false,
+                                    duplicate_headers: vec![],
+                                    row_count: 2,
+                                    column_count: 3,
+                                    numeric_column_count: 2,
+                                    numeric_cell_count: 3,
+                                    text_header_count: 3 });
+        assert!(results[1].readable);
+        assert!(results[1].has_duplicate_headers);
+        assert_eq!(results[1].duplicate_headers, vec!["x".to_string()]);
+        assert_eq!(results[1].numeric_column_count, 3);
+    }
+
+    #[test]
+    fn data_sheet_guess_prefers_the_table_over_a_notes_sheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let notes = workbook.add_worksheet().set_name("Notes").unwrap();
+        notes.write(0, 0, "Survey 2024").unwrap();
+        notes.write(2, 0, "Collected by the field team in spring.")
+             .unwrap();
+        notes.write(3, 0, "Scores are out of 100; see the data sheet.")
+             .unwrap();
+        notes.write(4, 0, "Contact: research office").unwrap();
+        let data = workbook.add_worksheet().set_name("Data").unwrap();
+        data.write_row(0, 0, ["id", "score", "group"]).unwrap();
+        for row in 1..=5u32 {
+            data.write(row, 0, row).unwrap();
+            data.write(row, 1, 50.0 + f64::from(row)).unwrap();
+            data.write(row, 2, if row % 2 == 0 { "A" } else { "B" }).unwrap();
+        }
+        workbook.save(&path).unwrap();
+
+        let sheets = validate_xlsx_workbook(path.to_str().unwrap(), &TableReadOptions::default()).unwrap();
+        let guess = guess_data_sheet(&sheets);
+
+        assert_eq!(guess.best.as_deref(), Some("Data"));
+        assert_eq!(guess.sheets.len(), 2);
+        assert!(guess.sheets[1].plausible_header);
+        assert_eq!(guess.sheets[1].used_cells, 15);
+        assert!(guess.sheets[0].score < guess.sheets[1].score);
+    }
+
+    #[test]
+    fn headers_match_a_full_parse_of_the_same_sheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("headers.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write(1, 1, "id").unwrap();
+        sheet.write(1, 3, " score ").unwrap();
+        sheet.write(1, 4, true).unwrap();
+        sheet.write_row(2, 1, [1, 2, 3, 4]).unwrap();
+        sheet.write(3, 5, "wider than the header").unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let options = TableReadOptions::default();
+
+        let headers = read_xlsx_headers(path, "Data", &options).unwrap();
+        let parsed = create_parsed_data_table(read_xlsx_sheet_rows(path, "Data", &options).unwrap(),
+                                              &options).unwrap();
+
+        assert_eq!(headers, parsed.headers);
+        assert_eq!(headers, vec!["id", "col_2", "score", "TRUE", "col_5"]);
+    }
+
+    #[test]
+    fn header_row_skips_the_title_rows_above_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("titled.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write(0, 0, "2024年度 調査結果").unwrap();
+        sheet.write(0, 2, 2024).unwrap();
+        sheet.write_row(2, 0, ["id", "score"]).unwrap();
+        sheet.write_row(3, 0, [1, 10]).unwrap();
+        sheet.write_row(4, 0, [2, 20]).unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let options = TableReadOptions { header_row: Some(3),
+                                         ..TableReadOptions::default() };
+
+        let rows = read_xlsx_sheet_rows(path, "Data", &options).unwrap();
+        let (dataset, _) = build_numeric_dataset_from_xlsx(rows, &["score".to_string()], &options).unwrap();
+        assert_eq!(dataset.get("score"), Some(&vec![Some(10.0), Some(20.0)]));
+        assert_eq!(read_xlsx_headers(path, "Data", &options).unwrap(),
+                   vec!["id", "score", "col_3"]);
+        assert_eq!(read_xlsx_formula_table(path, "Data", &options).unwrap()
+                                                                  .rows
+                                                                  .len(),
+                   2);
+
+        let beyond = TableReadOptions { header_row: Some(9),
+                                        ..TableReadOptions::default() };
+        let err = read_xlsx_sheet_rows(path, "Data", &beyond).unwrap_err();
+        assert!(err.contains("beyond the last row of the sheet (5)"), "{}", err);
+        assert!(read_xlsx_headers(path, "Data", &beyond).is_err());
+    }
+
+    #[test]
+    fn range_reads_only_the_cells_inside_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ranged.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write(0, 0, "注: 単位は点").unwrap();
+        sheet.write_row(2, 1, ["id", "score"]).unwrap();
+        sheet.write_row(3, 1, [1, 10]).unwrap();
+        sheet.write_row(4, 1, [2, 20]).unwrap();
+        sheet.write_row(2, 4, ["別表"]).unwrap();
+        sheet.write_row(3, 4, [99]).unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let ranged = |range: &str| TableReadOptions { range: Some(range.to_string()),
+                                                      ..TableReadOptions::default() };
+
+        // Reversed corners and `$` markers; the end row lies past the data and is clamped.
+        let options = ranged("$C$100:b3");
+        let rows = read_xlsx_sheet_rows(path, "Data", &options).unwrap();
+        assert_eq!(rows.rows.len(), 3);
+        let (dataset, _) = build_numeric_dataset_from_xlsx(rows, &["score".to_string()], &options).unwrap();
+        assert_eq!(dataset.get("score"), Some(&vec![Some(10.0), Some(20.0)]));
+        assert_eq!(read_xlsx_headers(path, "Data", &options).unwrap(),
+                   vec!["id", "score"]);
+        assert_eq!(read_xlsx_formula_table(path, "Data", &options).unwrap().headers,
+                   vec!["id", "score"]);
+
+        assert!(read_xlsx_sheet_rows(path, "Data", &ranged("A10:B20")).unwrap()
+                                                                      .rows
+                                                                      .is_empty());
+        let err = read_xlsx_sheet_rows(path, "Data", &ranged("B3-C9")).unwrap_err();
+        assert!(err.contains("Invalid range 'B3-C9'"), "{}", err);
+        let both = TableReadOptions { header_row: Some(3),
+                                      ..ranged("B3:C5") };
+        assert!(read_xlsx_sheet_rows(path, "Data", &both).is_err());
+    }
+
+    #[test]
+    fn headers_report_duplicates_like_a_full_parse() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dup.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        workbook.add_worksheet()
+                .set_name("Data")
+                .unwrap()
+                .write_row(0, 0, ["x", "x"])
+                .unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let options = TableReadOptions::default();
+
+        let err = read_xlsx_headers(path, "Data", &options).unwrap_err();
+        let full_err = create_parsed_data_table(read_xlsx_sheet_rows(path, "Data", &options).unwrap(),
+                                                &options).unwrap_err();
+        assert_eq!(err, full_err);
+    }
+
+    #[test]
+    fn formula_table_captures_formula_text_beside_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("formulas.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write_row(0, 0, ["price", "qty", "total"]).unwrap();
+        sheet.write_row(1, 0, [100, 2]).unwrap();
+        sheet.write_formula(1, 2, rust_xlsxwriter::Formula::new("=A2*B2").set_result("200"))
+             .unwrap();
+        sheet.write_row(2, 0, [50, 3]).unwrap();
+        sheet.write_formula(2, 2, rust_xlsxwriter::Formula::new("=A3*B3").set_result("150"))
+             .unwrap();
+        workbook.save(&path).unwrap();
+
+        let table =
+            read_xlsx_formula_table(path.to_str().unwrap(), "Data", &TableReadOptions::default()).unwrap();
+
+        assert_eq!(table.headers, vec!["price", "qty", "total"]);
+        assert_eq!(table.rows,
+                   vec![vec![serde_json::Value::Null, serde_json::Value::Null, "=A2*B2".into()],
+                        vec![serde_json::Value::Null, serde_json::Value::Null, "=A3*B3".into()]]);
+    }
+
+    #[test]
+    fn verify_parse_finds_no_mismatch_for_dates_and_floats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("verify.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let date_format = rust_xlsxwriter::Format::new().set_num_format("yyyy-mm-dd");
+        sheet.write_row(0, 0, ["date", "value", "label"]).unwrap();
+        sheet.write_datetime_with_format(1,
+                                         0,
+                                         rust_xlsxwriter::ExcelDateTime::from_ymd(2024, 1, 15).unwrap(),
+                                         &date_format)
+             .unwrap();
+        sheet.write(1, 1, 1.25).unwrap();
+        sheet.write(1, 2, "a").unwrap();
+        sheet.write_datetime_with_format(2,
+                                         0,
+                                         rust_xlsxwriter::ExcelDateTime::from_ymd(2024, 2, 29).unwrap(),
+                                         &date_format)
+             .unwrap();
+        sheet.write(2, 1, 0.1 + 0.2).unwrap();
+        sheet.write(2, 2, "NA").unwrap();
+        workbook.save(&path).unwrap();
+
+        let mismatches =
+            verify_xlsx_parse(path.to_str().unwrap(), "Sheet1", &TableReadOptions::default()).unwrap();
+
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+    }
+
+    #[test]
+    fn verify_parse_reports_sheet_rows_of_a_table_below_a_title() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offset.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.write(0, 2, "title").unwrap();
+        sheet.write_row(2, 2, ["x", "y"]).unwrap();
+        sheet.write_row(3, 2, [1.0, 2.0]).unwrap();
+        sheet.write_row(4, 2, [3.0, 4.0]).unwrap();
+        workbook.save(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let options = TableReadOptions { header_row: Some(3),
+                                         ..TableReadOptions::default() };
+
+        assert!(verify_xlsx_parse(path, "Sheet1", &options).unwrap().is_empty());
+
+        let sheet_rows = read_xlsx_sheet_rows(path, "Sheet1", &options).unwrap();
+        let origin = (sheet_rows.first_row, sheet_rows.first_column);
+        let mut parsed = create_parsed_data_table(sheet_rows, &options).unwrap();
+        parsed.rows[1][1] = serde_json::Value::from(5.0);
+        let mismatches = find_mismatches(&parsed, origin, &read_raw_cells(path, "Sheet1").unwrap());
+
+        assert_eq!(mismatches,
+                   vec![ParseMismatch { row: 5,
+                                        column: "y".to_string(),
+                                        parsed: serde_json::Value::from(5.0),
+                                        source: "4".to_string() }]);
+    }
+
+    #[test]
+    fn cell_comparison_flags_a_changed_value() {
+        assert!(xlsx_cell_matches(&Data::Float(3.0), &serde_json::Value::from(3)));
+        assert!(!xlsx_cell_matches(&Data::Float(3.0), &serde_json::Value::from(3.1)));
+        assert!(!xlsx_cell_matches(&Data::String("1".to_string()), &serde_json::Value::from(1)));
+    }
+}
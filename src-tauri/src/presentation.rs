@@ -1,15 +1,68 @@
 mod commands;
 
 pub(crate) fn attach_handlers(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<tauri::Wry> {
-    builder.invoke_handler(tauri::generate_handler![commands::build_numeric_dataset::build_numeric_dataset,
+    builder.invoke_handler(tauri::generate_handler![commands::acf::acf,
+                                                    commands::add_computed_column::add_computed_column,
+                                                    commands::aggregate_table::aggregate_table,
+                                                    commands::anomaly_detect::anomaly_detect,
+                                                    commands::append_table_csv::append_table_csv,
+                                                    commands::apply_codebook::apply_codebook,
+                                                    commands::begin_table_csv::begin_table_csv,
+                                                    commands::boxplot_stats::boxplot_stats,
+                                                    commands::build_numeric_dataset::build_numeric_dataset,
                                                     commands::build_string_mixed_dataset::build_string_mixed_dataset,
+                                                    commands::cast_column::cast_column,
+                                                    commands::categorical_diversity::categorical_diversity,
                                                     commands::clear_numeric_dataset_cache::clear_numeric_dataset_cache,
+                                                    commands::collinearity_screen::collinearity_screen,
+                                                    commands::concat_files::concat_files,
+                                                    commands::contingency_residuals::contingency_residuals,
+                                                    commands::contingency_table::contingency_table,
+                                                    commands::cramers_v::cramers_v,
+                                                    commands::cumulative_stats::cumulative_stats,
+                                                    commands::data_quality::data_quality,
+                                                    commands::detect_key_columns::detect_key_columns,
+                                                    commands::ecdf::ecdf,
+                                                    commands::export_results_workbook::export_results_workbook,
+                                                    commands::find_duplicate_rows::find_duplicate_rows,
+                                                    commands::find_low_variance_columns::find_low_variance_columns,
+                                                    commands::finish_table_csv::finish_table_csv,
+                                                    commands::generate_sample_dataset::generate_sample_dataset,
                                                     commands::get_analysis_log::get_analysis_log,
+                                                    commands::get_headers::get_headers,
                                                     commands::get_session_analysis_log::get_session_analysis_log,
+                                                    commands::get_sheet_properties::get_sheet_properties,
                                                     commands::get_sheets::get_sheets,
+                                                    commands::group_balance::group_balance,
+                                                    commands::group_zscore::group_zscore,
+                                                    commands::guess_data_sheet::guess_data_sheet,
+                                                    commands::histogram::histogram,
+                                                    commands::interaction_means::interaction_means,
                                                     commands::list_analysis_logs::list_analysis_logs,
                                                     commands::list_session_analysis_logs::list_session_analysis_logs,
+                                                    commands::match_headers::match_headers,
+                                                    commands::normalize_categories::normalize_categories,
+                                                    commands::normalize_table_widths::normalize_table_widths,
+                                                    commands::parse_pasted_tsv::parse_pasted_tsv,
                                                     commands::parse_table::parse_table,
+                                                    commands::partition_rows::partition_rows,
+                                                    commands::pivot_longer::pivot_longer,
+                                                    commands::pivot_wider::pivot_wider,
+                                                    commands::quick_normality::quick_normality,
+                                                    commands::quick_spearman::quick_spearman,
+                                                    commands::read_excel_formulas::read_excel_formulas,
+                                                    commands::read_excel_table::read_excel_table,
+                                                    commands::rolling_correlation::rolling_correlation,
+                                                    commands::row_completeness::row_completeness,
                                                     commands::run_analysis::run_analysis,
-                                                    commands::run_power_analysis::run_power_analysis,])
+                                                    commands::run_power_analysis::run_power_analysis,
+                                                    commands::save_dataset_csv::save_dataset_csv,
+                                                    commands::save_table_markdown::save_table_markdown,
+                                                    commands::simpsons_check::simpsons_check,
+                                                    commands::theil_sen::theil_sen,
+                                                    commands::trend_line::trend_line,
+                                                    commands::validate_workbook::validate_workbook,
+                                                    commands::variance_inflation::variance_inflation,
+                                                    commands::verify_parse::verify_parse,
+                                                    commands::winsorize::winsorize,])
 }
@@ -1,12 +1,65 @@
+pub(super) mod acf;
+pub(super) mod add_computed_column;
+pub(super) mod aggregate_table;
 pub(super) mod analysis_log_dto;
+pub(super) mod anomaly_detect;
+pub(super) mod append_table_csv;
+pub(super) mod apply_codebook;
+pub(super) mod begin_table_csv;
+pub(super) mod boxplot_stats;
 pub(super) mod build_numeric_dataset;
 pub(super) mod build_string_mixed_dataset;
+pub(super) mod cast_column;
+pub(super) mod categorical_diversity;
 pub(super) mod clear_numeric_dataset_cache;
+pub(super) mod collinearity_screen;
+pub(super) mod concat_files;
+pub(super) mod contingency_residuals;
+pub(super) mod contingency_table;
+pub(super) mod cramers_v;
+pub(super) mod cumulative_stats;
+pub(super) mod data_quality;
+pub(super) mod detect_key_columns;
+pub(super) mod ecdf;
+pub(super) mod export_results_workbook;
+pub(super) mod find_duplicate_rows;
+pub(super) mod find_low_variance_columns;
+pub(super) mod finish_table_csv;
+pub(super) mod generate_sample_dataset;
 pub(super) mod get_analysis_log;
+pub(super) mod get_headers;
 pub(super) mod get_session_analysis_log;
+pub(super) mod get_sheet_properties;
 pub(super) mod get_sheets;
+pub(super) mod group_balance;
+pub(super) mod group_zscore;
+pub(super) mod guess_data_sheet;
+pub(super) mod histogram;
+pub(super) mod interaction_means;
 pub(super) mod list_analysis_logs;
 pub(super) mod list_session_analysis_logs;
+pub(super) mod match_headers;
+pub(super) mod normalize_categories;
+pub(super) mod normalize_table_widths;
+pub(super) mod parse_pasted_tsv;
 pub(super) mod parse_table;
+pub(super) mod partition_rows;
+pub(super) mod pivot_longer;
+pub(super) mod pivot_wider;
+pub(super) mod quick_normality;
+pub(super) mod quick_spearman;
+pub(super) mod read_excel_formulas;
+pub(super) mod read_excel_table;
+pub(super) mod rolling_correlation;
+pub(super) mod row_completeness;
 pub(super) mod run_analysis;
 pub(super) mod run_power_analysis;
+pub(super) mod save_dataset_csv;
+pub(super) mod save_table_markdown;
+pub(super) mod simpsons_check;
+pub(super) mod theil_sen;
+pub(super) mod trend_line;
+pub(super) mod validate_workbook;
+pub(super) mod variance_inflation;
+pub(super) mod verify_parse;
+pub(super) mod winsorize;
@@ -0,0 +1,34 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn acf(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+           dataset_cache_id: String,
+           column: String,
+           max_lag: usize,
+           confidence: Option<bool>,
+           interpolate: Option<bool>)
+           -> Result<ParsedDataTable, String> {
+    let confidence = confidence.unwrap_or(false);
+    let interpolate = interpolate.unwrap_or(false);
+    log::info!("statistics.acf start dataset_cache_id={} column={} max_lag={} confidence={} interpolate={}",
+               dataset_cache_id,
+               column,
+               max_lag,
+               confidence,
+               interpolate);
+
+    let table = state.statistics_service
+                     .acf(&dataset_cache_id, &column, max_lag, confidence, interpolate)
+                     .map_err(|e| {
+                         log::error!("statistics.acf failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.acf ok dataset_cache_id={} column={} lags={}",
+               dataset_cache_id,
+               column,
+               table.rows.len());
+    Ok(table)
+}
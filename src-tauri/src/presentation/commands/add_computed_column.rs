@@ -0,0 +1,25 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::compute;
+
+#[tauri::command]
+pub fn add_computed_column(table: ParsedDataTable,
+                           name: String,
+                           expr: String)
+                           -> Result<ParsedDataTable, String> {
+    log::info!("data.add_computed_column start name={} expr={} rows={}",
+               name,
+               expr,
+               table.rows.len());
+
+    let computed = table.validate()
+                        .and_then(|_| compute::add_computed_column(&table, &name, &expr))
+                        .map_err(|e| {
+                            log::error!("data.add_computed_column failed name={} err={}", name, e);
+                            e
+                        })?;
+
+    log::info!("data.add_computed_column ok name={} rows={}",
+               name,
+               computed.rows.len());
+    Ok(computed)
+}
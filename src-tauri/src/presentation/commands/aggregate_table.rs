@@ -0,0 +1,28 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::aggregate::{
+    self,
+    Aggregation,
+};
+
+#[tauri::command]
+pub fn aggregate_table(table: ParsedDataTable,
+                       group_by: Vec<String>,
+                       value: String,
+                       agg: Aggregation)
+                       -> Result<ParsedDataTable, String> {
+    log::info!("data.aggregate_table start group_by={} value={} agg={:?} rows={}",
+               group_by.len(),
+               value,
+               agg,
+               table.rows.len());
+
+    let summary = table.validate()
+                       .and_then(|_| aggregate::aggregate_table(&table, &group_by, &value, agg))
+                       .map_err(|e| {
+                           log::error!("data.aggregate_table failed err={}", e);
+                           e
+                       })?;
+
+    log::info!("data.aggregate_table ok groups={}", summary.rows.len());
+    Ok(summary)
+}
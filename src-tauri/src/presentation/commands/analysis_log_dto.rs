@@ -6,6 +6,7 @@ use crate::domain::analysis_log::model::{
     AnalysisLogRecord,
     AnalysisLogSummary,
 };
+use crate::domain::input::metadata::ColumnMetadataMap;
 
 use super::run_analysis::{
     AnalysisResultDto,
@@ -62,6 +63,8 @@ pub(crate) struct AnalysisLogRecordDto {
     n: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     n_note: Option<String>,
+    #[serde(skip_serializing_if = "ColumnMetadataMap::is_empty")]
+    column_metadata: ColumnMetadataMap,
 }
 
 impl From<AnalysisLogRecord> for AnalysisLogRecordDto {
@@ -75,6 +78,7 @@ impl From<AnalysisLogRecord> for AnalysisLogRecordDto {
                options: value.options,
                result: AnalysisResultDto { sections: map_sections(value.result) },
                n: value.n,
-               n_note: value.n_note }
+               n_note: value.n_note,
+               column_metadata: value.column_metadata }
     }
 }
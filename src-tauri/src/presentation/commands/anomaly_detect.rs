@@ -0,0 +1,29 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::anomaly::DEFAULT_ANOMALY_THRESHOLD;
+
+#[tauri::command]
+pub fn anomaly_detect(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                      dataset_cache_id: String,
+                      column: String,
+                      threshold: Option<f64>)
+                      -> Result<ParsedDataTable, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_ANOMALY_THRESHOLD);
+    log::info!("statistics.anomaly_detect start dataset_cache_id={} column={} threshold={}",
+               dataset_cache_id,
+               column,
+               threshold);
+
+    let table = state.statistics_service
+                     .anomaly_detect(&dataset_cache_id, &column, threshold)
+                     .map_err(|e| {
+                         log::error!("statistics.anomaly_detect failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.anomaly_detect ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
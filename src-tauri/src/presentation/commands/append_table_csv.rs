@@ -0,0 +1,14 @@
+use serde_json::Value;
+
+#[tauri::command]
+pub fn append_table_csv(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        token: String,
+                        rows: Vec<Vec<Value>>)
+                        -> Result<(), String> {
+    state.csv_exports.append(&token, &rows).map_err(|e| {
+                                               log::error!("data.append_table_csv failed token={} err={}",
+                                                           token,
+                                                           e);
+                                               e
+                                           })
+}
@@ -0,0 +1,26 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::codebook::{
+    self,
+    CodedTable,
+};
+
+#[tauri::command]
+pub fn apply_codebook(table: ParsedDataTable,
+                      codebook: ParsedDataTable)
+                      -> Result<CodedTable, String> {
+    log::info!("data.apply_codebook start columns={} rows={} codebook_rows={}",
+               table.headers.len(),
+               table.rows.len(),
+               codebook.rows.len());
+
+    let result = table.validate()
+                      .and_then(|_| codebook.validate().map_err(|e| format!("codebook: {}", e)))
+                      .and_then(|_| codebook::apply_codebook(&table, &codebook))
+                      .map_err(|e| {
+                          log::error!("data.apply_codebook failed err={}", e);
+                          e
+                      })?;
+
+    log::info!("data.apply_codebook ok unmapped={}", result.unmapped.rows.len());
+    Ok(result)
+}
@@ -0,0 +1,18 @@
+#[tauri::command]
+pub fn begin_table_csv(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                       path: String,
+                       headers: Vec<String>)
+                       -> Result<String, String> {
+    log::info!("data.begin_table_csv start path={} cols={}", path, headers.len());
+
+    let token =
+        state.csv_exports.begin(&path, &headers).map_err(|e| {
+                                                     log::error!("data.begin_table_csv failed path={} err={}",
+                                                                 path,
+                                                                 e);
+                                                     e
+                                                 })?;
+
+    log::info!("data.begin_table_csv ok path={} token={}", path, token);
+    Ok(token)
+}
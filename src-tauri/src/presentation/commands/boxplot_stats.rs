@@ -0,0 +1,29 @@
+use crate::domain::statistics::boxplot::BoxplotStats;
+
+#[tauri::command]
+pub fn boxplot_stats(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                     dataset_cache_id: String,
+                     columns: Vec<String>,
+                     group: Option<String>)
+                     -> Result<BoxplotStats, String> {
+    let group = group.filter(|group| !group.trim().is_empty());
+    log::info!("statistics.boxplot_stats start dataset_cache_id={} columns={} group={:?}",
+               dataset_cache_id,
+               columns.len(),
+               group);
+
+    let stats = state.statistics_service
+                     .boxplot_stats(&dataset_cache_id, &columns, group.as_deref())
+                     .map_err(|e| {
+                         log::error!("statistics.boxplot_stats failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.boxplot_stats ok dataset_cache_id={} rows={} outliers={}",
+               dataset_cache_id,
+               stats.table.rows.len(),
+               stats.outliers.rows.len());
+    Ok(stats)
+}
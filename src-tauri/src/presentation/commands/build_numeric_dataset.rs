@@ -1,10 +1,14 @@
+use crate::domain::input::metadata::ColumnMetadataMap;
+use crate::domain::input::read_options::TableReadOptions;
 use crate::domain::input::source_kind::DataSourceKind;
 
 #[tauri::command]
 pub fn build_numeric_dataset(state: tauri::State<'_, crate::bootstrap::state::AppState>,
                              path: String,
                              sheet: Option<String>,
-                             variables: Vec<String>)
+                             variables: Vec<String>,
+                             read_options: Option<TableReadOptions>,
+                             column_metadata: Option<ColumnMetadataMap>)
                              -> Result<String, String> {
     let kind = DataSourceKind::from_path(&path)?;
     let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
@@ -15,7 +19,11 @@ pub fn build_numeric_dataset(state: tauri::State<'_, crate::bootstrap::state::Ap
                variables.len());
 
     let built = state.import_service
-                     .build_numeric_dataset(&path, sheet.as_deref(), &variables)
+                     .build_numeric_dataset(&path,
+                                            sheet.as_deref(),
+                                            &variables,
+                                            &read_options.unwrap_or_default(),
+                                            column_metadata)
                      .map_err(|e| {
                          log::error!("analysis.build_numeric_dataset failed path={} kind={} sheet={} err={}",
                                      path,
@@ -25,6 +33,9 @@ pub fn build_numeric_dataset(state: tauri::State<'_, crate::bootstrap::state::Ap
                          e
                      })?;
 
+    for warning in &built.warnings {
+        log::warn!("analysis.build_numeric_dataset path={} {}", path, warning);
+    }
     log::info!("analysis.build_numeric_dataset ok path={} kind={} sheet={} dataset_cache_id={} vars={} rows={}",
                path,
                kind.as_str(),
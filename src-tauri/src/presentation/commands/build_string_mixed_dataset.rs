@@ -1,10 +1,14 @@
+use crate::domain::input::metadata::ColumnMetadataMap;
+use crate::domain::input::read_options::TableReadOptions;
 use crate::domain::input::source_kind::DataSourceKind;
 
 #[tauri::command]
 pub fn build_string_mixed_dataset(state: tauri::State<'_, crate::bootstrap::state::AppState>,
                                   path: String,
                                   sheet: Option<String>,
-                                  variables: Vec<String>)
+                                  variables: Vec<String>,
+                                  read_options: Option<TableReadOptions>,
+                                  column_metadata: Option<ColumnMetadataMap>)
                                   -> Result<String, String> {
     let kind = DataSourceKind::from_path(&path)?;
     let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
@@ -16,7 +20,11 @@ pub fn build_string_mixed_dataset(state: tauri::State<'_, crate::bootstrap::stat
 
     let built =
         state.import_service
-             .build_string_mixed_dataset(&path, sheet.as_deref(), &variables)
+             .build_string_mixed_dataset(&path,
+                                         sheet.as_deref(),
+                                         &variables,
+                                         &read_options.unwrap_or_default(),
+                                         column_metadata)
              .map_err(|e| {
                  log::error!("analysis.build_string_mixed_dataset failed path={} kind={} sheet={} err={}",
                              path,
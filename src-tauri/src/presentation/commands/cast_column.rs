@@ -0,0 +1,31 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::cast::{
+    self,
+    CastColumnResult,
+    CastType,
+};
+
+#[tauri::command]
+pub fn cast_column(table: ParsedDataTable,
+                   column: String,
+                   to_type: CastType,
+                   strict: Option<bool>)
+                   -> Result<CastColumnResult, String> {
+    log::info!("data.cast_column start column={} to_type={:?} rows={}",
+               column,
+               to_type,
+               table.rows.len());
+
+    let result = table.validate()
+                      .and_then(|_| cast::cast_column(&table, &column, to_type, strict.unwrap_or(false)))
+                      .and_then(|result| result.table.validate().map(|_| result))
+                      .map_err(|e| {
+                          log::error!("data.cast_column failed column={} err={}", column, e);
+                          e
+                      })?;
+
+    log::info!("data.cast_column ok column={} failed={}",
+               column,
+               result.failed_count);
+    Ok(result)
+}
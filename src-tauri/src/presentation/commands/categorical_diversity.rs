@@ -0,0 +1,26 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn categorical_diversity(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                             dataset_cache_id: String,
+                             include_missing: Option<bool>)
+                             -> Result<ParsedDataTable, String> {
+    let include_missing = include_missing.unwrap_or(false);
+    log::info!("statistics.categorical_diversity start dataset_cache_id={} include_missing={}",
+               dataset_cache_id,
+               include_missing);
+
+    let table = state.statistics_service
+                     .categorical_diversity(&dataset_cache_id, include_missing)
+                     .map_err(|e| {
+                         log::error!("statistics.categorical_diversity failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.categorical_diversity ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
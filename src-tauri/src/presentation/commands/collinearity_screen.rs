@@ -0,0 +1,28 @@
+use crate::domain::statistics::collinearity::CollinearityScreen;
+
+#[tauri::command]
+pub fn collinearity_screen(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                           dataset_cache_id: String,
+                           threshold: f64,
+                           include_vif: Option<bool>)
+                           -> Result<CollinearityScreen, String> {
+    let include_vif = include_vif.unwrap_or(false);
+    log::info!("statistics.collinearity_screen start dataset_cache_id={} threshold={} include_vif={}",
+               dataset_cache_id,
+               threshold,
+               include_vif);
+
+    let screen = state.statistics_service
+                      .collinearity_screen(&dataset_cache_id, threshold, include_vif)
+                      .map_err(|e| {
+                          log::error!("statistics.collinearity_screen failed dataset_cache_id={} err={}",
+                                      dataset_cache_id,
+                                      e);
+                          e
+                      })?;
+
+    log::info!("statistics.collinearity_screen ok dataset_cache_id={} pairs={}",
+               dataset_cache_id,
+               screen.pairs.rows.len());
+    Ok(screen)
+}
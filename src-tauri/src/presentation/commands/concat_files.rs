@@ -0,0 +1,37 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn concat_files(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                    paths: Vec<String>,
+                    sheet: Option<String>,
+                    add_source_column: Option<bool>,
+                    read_options: Option<TableReadOptions>)
+                    -> Result<ParsedDataTable, String> {
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    let add_source_column = add_source_column.unwrap_or(false);
+    log::info!("data.concat_files start files={} sheet={} add_source_column={}",
+               paths.len(),
+               sheet_label,
+               add_source_column);
+
+    let table = state.import_service
+                     .concat_files(&paths,
+                                   sheet.as_deref(),
+                                   &read_options.unwrap_or_default(),
+                                   add_source_column)
+                     .map_err(|e| {
+                         log::error!("data.concat_files failed files={} sheet={} err={}",
+                                     paths.len(),
+                                     sheet_label,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("data.concat_files ok files={} sheet={} rows={} cols={}",
+               paths.len(),
+               sheet_label,
+               table.rows.len(),
+               table.headers.len());
+    Ok(table)
+}
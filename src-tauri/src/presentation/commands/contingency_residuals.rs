@@ -0,0 +1,21 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::association;
+
+#[tauri::command]
+pub fn contingency_residuals(table: ParsedDataTable) -> Result<ParsedDataTable, String> {
+    log::info!("statistics.contingency_residuals start rows={} columns={}",
+               table.rows.len(),
+               table.headers.len());
+
+    let result = table.validate()
+                      .and_then(|_| association::contingency_residuals(&table))
+                      .map_err(|e| {
+                          log::error!("statistics.contingency_residuals failed err={}", e);
+                          e
+                      })?;
+
+    log::info!("statistics.contingency_residuals ok rows={} columns={}",
+               result.rows.len(),
+               result.headers.len() - 1);
+    Ok(result)
+}
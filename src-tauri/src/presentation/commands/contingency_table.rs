@@ -0,0 +1,52 @@
+use serde::Deserialize;
+
+use crate::domain::statistics::association::{
+    ContingencyOptions,
+    ContingencyTable,
+};
+
+/// Requests Goodman-Kruskal's gamma; the two columns are ordinal with levels listed lowest first.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GammaOptionsDto {
+    row_order: Vec<String>,
+    column_order: Vec<String>,
+}
+
+#[tauri::command]
+pub fn contingency_table(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                         dataset_cache_id: String,
+                         row: String,
+                         column: String,
+                         residuals: Option<bool>,
+                         gamma: Option<GammaOptionsDto>)
+                         -> Result<ContingencyTable, String> {
+    let requested_gamma = gamma.is_some();
+    let (row_order, column_order) = gamma.map(|gamma| (Some(gamma.row_order), Some(gamma.column_order)))
+                                         .unwrap_or_default();
+    let options = ContingencyOptions { residuals: residuals.unwrap_or(false),
+                                       gamma: requested_gamma,
+                                       row_order,
+                                       column_order };
+    log::info!("statistics.contingency_table start dataset_cache_id={} row={} column={} residuals={} gamma={}",
+               dataset_cache_id,
+               row,
+               column,
+               options.residuals,
+               options.gamma);
+
+    let table = state.statistics_service
+                     .contingency_table(&dataset_cache_id, &row, &column, &options)
+                     .map_err(|e| {
+                         log::error!("statistics.contingency_table failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.contingency_table ok dataset_cache_id={} row={} column={}",
+               dataset_cache_id,
+               row,
+               column);
+    Ok(table)
+}
@@ -0,0 +1,31 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn cramers_v(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                 dataset_cache_id: String,
+                 row: String,
+                 column: String,
+                 bias_corrected: Option<bool>)
+                 -> Result<ParsedDataTable, String> {
+    let bias_corrected = bias_corrected.unwrap_or(false);
+    log::info!("statistics.cramers_v start dataset_cache_id={} row={} column={} bias_corrected={}",
+               dataset_cache_id,
+               row,
+               column,
+               bias_corrected);
+
+    let table = state.statistics_service
+                     .cramers_v(&dataset_cache_id, &row, &column, bias_corrected)
+                     .map_err(|e| {
+                         log::error!("statistics.cramers_v failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.cramers_v ok dataset_cache_id={} row={} column={}",
+               dataset_cache_id,
+               row,
+               column);
+    Ok(table)
+}
@@ -0,0 +1,31 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::cumulative::CumulativeStat;
+
+#[tauri::command]
+pub fn cumulative_stats(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        dataset_cache_id: String,
+                        column: String,
+                        stat: CumulativeStat,
+                        reset_on_missing: Option<bool>)
+                        -> Result<ParsedDataTable, String> {
+    let reset_on_missing = reset_on_missing.unwrap_or(false);
+    log::info!("statistics.cumulative_stats start dataset_cache_id={} column={} stat={:?} reset_on_missing={}",
+               dataset_cache_id,
+               column,
+               stat,
+               reset_on_missing);
+
+    let table = state.statistics_service
+                     .cumulative_stats(&dataset_cache_id, &column, stat, reset_on_missing)
+                     .map_err(|e| {
+                         log::error!("statistics.cumulative_stats failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.cumulative_stats ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
@@ -0,0 +1,42 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+use crate::domain::statistics::quality::DataQuality;
+
+#[tauri::command]
+pub fn data_quality(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                    path: String,
+                    sheet: Option<String>,
+                    key_columns: Option<Vec<String>>,
+                    read_options: Option<TableReadOptions>)
+                    -> Result<DataQuality, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    let key_columns = key_columns.unwrap_or_default();
+    log::info!("data.data_quality start path={} kind={} sheet={} key_columns={:?}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               key_columns);
+
+    let quality = state.import_service
+                       .data_quality(&path,
+                                     sheet.as_deref(),
+                                     &read_options.unwrap_or_default(),
+                                     &key_columns)
+                       .map_err(|e| {
+                           log::error!("data.data_quality failed path={} kind={} sheet={} err={}",
+                                       path,
+                                       kind.as_str(),
+                                       sheet_label,
+                                       e);
+                           e
+                       })?;
+
+    log::info!("data.data_quality ok path={} kind={} sheet={} columns={} score={:.3}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               quality.table.rows.len(),
+               quality.overall_score);
+    Ok(quality)
+}
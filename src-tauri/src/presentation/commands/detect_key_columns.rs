@@ -0,0 +1,19 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::duplicates;
+
+#[tauri::command]
+pub fn detect_key_columns(table: ParsedDataTable) -> Result<ParsedDataTable, String> {
+    log::info!("data.detect_key_columns start columns={} rows={}",
+               table.headers.len(),
+               table.rows.len());
+
+    let candidates = table.validate()
+                          .and_then(|_| duplicates::detect_key_columns(&table))
+                          .map_err(|e| {
+                              log::error!("data.detect_key_columns failed err={}", e);
+                              e
+                          })?;
+
+    log::info!("data.detect_key_columns ok candidates={}", candidates.rows.len());
+    Ok(candidates)
+}
@@ -0,0 +1,27 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn ecdf(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+            dataset_cache_id: String,
+            column: String)
+            -> Result<ParsedDataTable, String> {
+    log::info!("statistics.ecdf start dataset_cache_id={} column={}",
+               dataset_cache_id,
+               column);
+
+    let table = state.statistics_service
+                     .ecdf(&dataset_cache_id, &column)
+                     .map_err(|e| {
+                         log::error!("statistics.ecdf failed dataset_cache_id={} column={} err={}",
+                                     dataset_cache_id,
+                                     column,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.ecdf ok dataset_cache_id={} column={} points={}",
+               dataset_cache_id,
+               column,
+               table.rows.len());
+    Ok(table)
+}
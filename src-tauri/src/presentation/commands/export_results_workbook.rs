@@ -0,0 +1,26 @@
+use crate::domain::input::workbook::{
+    NamedTable,
+    results_workbook,
+};
+
+#[tauri::command]
+pub fn export_results_workbook(path: String,
+                               results: Vec<NamedTable>)
+                               -> Result<(), String> {
+    log::info!("data.export_results_workbook start path={} results={}",
+               path,
+               results.len());
+
+    results_workbook(&results).and_then(|bytes| {
+                                  std::fs::write(&path, bytes).map_err(|e| {
+                                                                  format!("failed to write workbook: {}", e)
+                                                              })
+                              })
+                              .map_err(|e| {
+                                  log::error!("data.export_results_workbook failed path={} err={}", path, e);
+                                  e
+                              })?;
+
+    log::info!("data.export_results_workbook ok path={}", path);
+    Ok(())
+}
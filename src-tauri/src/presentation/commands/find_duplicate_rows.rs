@@ -0,0 +1,26 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::duplicates::{
+    self,
+    DuplicateRows,
+};
+
+#[tauri::command]
+pub fn find_duplicate_rows(table: ParsedDataTable,
+                           key_columns: Option<Vec<String>>)
+                           -> Result<DuplicateRows, String> {
+    log::info!("data.find_duplicate_rows start rows={} keys={}",
+               table.rows.len(),
+               key_columns.as_ref().map_or(0, Vec::len));
+
+    let result = table.validate()
+                      .and_then(|_| duplicates::find_duplicate_rows(&table, key_columns.as_deref()))
+                      .map_err(|e| {
+                          log::error!("data.find_duplicate_rows failed err={}", e);
+                          e
+                      })?;
+
+    log::info!("data.find_duplicate_rows ok duplicates={} groups={}",
+               result.duplicate_row_indices.len(),
+               result.duplicate_group_count);
+    Ok(result)
+}
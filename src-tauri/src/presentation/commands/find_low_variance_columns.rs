@@ -0,0 +1,41 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn find_low_variance_columns(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                                 path: String,
+                                 sheet: Option<String>,
+                                 threshold: f64,
+                                 read_options: Option<TableReadOptions>)
+                                 -> Result<ParsedDataTable, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    log::info!("data.find_low_variance_columns start path={} kind={} sheet={} threshold={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               threshold);
+
+    let flagged =
+        state.import_service
+             .find_low_variance_columns(&path,
+                                        sheet.as_deref(),
+                                        &read_options.unwrap_or_default(),
+                                        threshold)
+             .map_err(|e| {
+                 log::error!("data.find_low_variance_columns failed path={} kind={} sheet={} err={}",
+                             path,
+                             kind.as_str(),
+                             sheet_label,
+                             e);
+                 e
+             })?;
+
+    log::info!("data.find_low_variance_columns ok path={} kind={} sheet={} flagged={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               flagged.rows.len());
+    Ok(flagged)
+}
@@ -0,0 +1,15 @@
+#[tauri::command]
+pub fn finish_table_csv(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        token: String)
+                        -> Result<(), String> {
+    let (path, rows) =
+        state.csv_exports.finish(&token).map_err(|e| {
+                                             log::error!("data.finish_table_csv failed token={} err={}",
+                                                         token,
+                                                         e);
+                                             e
+                                         })?;
+
+    log::info!("data.finish_table_csv ok path={} rows={}", path, rows);
+    Ok(())
+}
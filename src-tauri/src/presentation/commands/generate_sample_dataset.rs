@@ -0,0 +1,21 @@
+use crate::domain::input::synthetic::{
+    self,
+    SampleDatasetSpec,
+};
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn generate_sample_dataset(spec: SampleDatasetSpec) -> Result<ParsedDataTable, String> {
+    log::info!("data.generate_sample_dataset start rows={} columns={} seed={}",
+               spec.rows,
+               spec.columns.len(),
+               spec.seed);
+
+    let table = synthetic::generate_sample_dataset(&spec).map_err(|e| {
+                    log::error!("data.generate_sample_dataset failed err={}", e);
+                    e
+                })?;
+
+    log::info!("data.generate_sample_dataset ok rows={}", table.rows.len());
+    Ok(table)
+}
@@ -0,0 +1,34 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+
+#[tauri::command]
+pub fn get_headers(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                   path: String,
+                   sheet: Option<String>,
+                   read_options: Option<TableReadOptions>)
+                   -> Result<Vec<String>, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    log::info!("data.get_headers start path={} kind={} sheet={}",
+               path,
+               kind.as_str(),
+               sheet_label);
+
+    let headers = state.import_service
+                       .get_headers(&path, sheet.as_deref(), &read_options.unwrap_or_default())
+                       .map_err(|e| {
+                           log::error!("data.get_headers failed path={} kind={} sheet={} err={}",
+                                       path,
+                                       kind.as_str(),
+                                       sheet_label,
+                                       e);
+                           e
+                       })?;
+
+    log::info!("data.get_headers ok path={} kind={} sheet={} headers={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               headers.len());
+    Ok(headers)
+}
@@ -0,0 +1,28 @@
+use crate::domain::input::sheet::SheetProperties;
+use crate::domain::input::source_kind::DataSourceKind;
+
+#[tauri::command]
+pub fn get_sheet_properties(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                            path: String)
+                            -> Result<Vec<SheetProperties>, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    log::info!("data.get_sheet_properties start path={} kind={}",
+               path,
+               kind.as_str());
+
+    let properties = state.import_service
+                          .get_sheet_properties(&path)
+                          .map_err(|e| {
+                              log::error!("data.get_sheet_properties failed path={} kind={} err={}",
+                                          path,
+                                          kind.as_str(),
+                                          e);
+                              e
+                          })?;
+
+    log::info!("data.get_sheet_properties ok path={} kind={} sheets={}",
+               path,
+               kind.as_str(),
+               properties.len());
+    Ok(properties)
+}
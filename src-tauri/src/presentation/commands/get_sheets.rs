@@ -1,16 +1,23 @@
+use crate::domain::input::sheet::SheetInfo;
+
 #[tauri::command]
 pub fn get_sheets(state: tauri::State<'_, crate::bootstrap::state::AppState>,
-                  path: String)
-                  -> Result<Vec<String>, String> {
+                  path: String,
+                  include_hidden: Option<bool>)
+                  -> Result<Vec<SheetInfo>, String> {
     log::info!("get_sheets start path={}", path);
     let kind = crate::domain::input::source_kind::DataSourceKind::from_path(&path)?;
-    log::info!("get_sheets start path={} kind={}", path, kind.as_str());
-    let result = state.import_service.get_sheets(&path);
+    let include_hidden = include_hidden.unwrap_or(false);
+    log::info!("get_sheets start path={} kind={} include_hidden={}",
+               path,
+               kind.as_str(),
+               include_hidden);
+    let result = state.import_service.get_sheets(&path, include_hidden);
 
     match result {
-        Ok(names) => {
-            log::info!("get_sheets ok path={} count={}", path, names.len());
-            Ok(names)
+        Ok(sheets) => {
+            log::info!("get_sheets ok path={} count={}", path, sheets.len());
+            Ok(sheets)
         },
         Err(e) => {
             log::error!("get_sheets failed path={} err={}", path, e);
@@ -0,0 +1,44 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+use crate::domain::statistics::balance::GroupBalance;
+
+#[tauri::command]
+pub fn group_balance(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                     path: String,
+                     sheet: Option<String>,
+                     factor: String,
+                     response: String,
+                     read_options: Option<TableReadOptions>)
+                     -> Result<GroupBalance, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    log::info!("data.group_balance start path={} kind={} sheet={} factor={} response={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               factor,
+               response);
+
+    let balance = state.import_service
+                       .group_balance(&path,
+                                      sheet.as_deref(),
+                                      &read_options.unwrap_or_default(),
+                                      &factor,
+                                      &response)
+                       .map_err(|e| {
+                           log::error!("data.group_balance failed path={} kind={} sheet={} err={}",
+                                       path,
+                                       kind.as_str(),
+                                       sheet_label,
+                                       e);
+                           e
+                       })?;
+
+    log::info!("data.group_balance ok path={} kind={} sheet={} levels={} ratio={:?}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               balance.table.rows.len(),
+               balance.balance_ratio);
+    Ok(balance)
+}
@@ -0,0 +1,30 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn group_zscore(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                    dataset_cache_id: String,
+                    column: String,
+                    group: String,
+                    reference_level: String)
+                    -> Result<ParsedDataTable, String> {
+    log::info!("statistics.group_zscore start dataset_cache_id={} column={} group={} reference_level={}",
+               dataset_cache_id,
+               column,
+               group,
+               reference_level);
+
+    let table = state.statistics_service
+                     .group_zscore(&dataset_cache_id, &column, &group, &reference_level)
+                     .map_err(|e| {
+                         log::error!("statistics.group_zscore failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.group_zscore ok dataset_cache_id={} column={} rows={}",
+               dataset_cache_id,
+               column,
+               table.rows.len());
+    Ok(table)
+}
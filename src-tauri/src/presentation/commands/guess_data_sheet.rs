@@ -0,0 +1,23 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::sheet::DataSheetGuess;
+
+#[tauri::command]
+pub fn guess_data_sheet(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        path: String,
+                        read_options: Option<TableReadOptions>)
+                        -> Result<DataSheetGuess, String> {
+    log::info!("data.guess_data_sheet start path={}", path);
+
+    let guess = state.import_service
+                     .guess_data_sheet(&path, &read_options.unwrap_or_default())
+                     .map_err(|e| {
+                         log::error!("data.guess_data_sheet failed path={} err={}", path, e);
+                         e
+                     })?;
+
+    log::info!("data.guess_data_sheet ok path={} sheets={} best={}",
+               path,
+               guess.sheets.len(),
+               guess.best.as_deref().unwrap_or("-"));
+    Ok(guess)
+}
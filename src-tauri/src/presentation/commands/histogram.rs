@@ -0,0 +1,91 @@
+use serde::Deserialize;
+
+use crate::domain::analysis::error::{
+    AnalysisErrorKind,
+    classified_error,
+};
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::histogram::{
+    BinSpec,
+    HistogramSpec,
+    MAX_BINS,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistogramOptionsDto {
+    bins: Option<usize>,
+    binwidth: Option<f64>,
+    range_min: Option<f64>,
+    range_max: Option<f64>,
+    #[serde(default)]
+    density: bool,
+}
+
+impl TryFrom<HistogramOptionsDto> for HistogramSpec {
+    type Error = String;
+
+    fn try_from(value: HistogramOptionsDto) -> Result<Self, Self::Error> {
+        let bins = match (value.bins, value.binwidth) {
+            (Some(count), None) => BinSpec::Count(count),
+            (None, Some(width)) => BinSpec::Width(width),
+            _ => {
+                return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                            "specify exactly one of bins or binwidth"));
+            },
+        };
+        if let BinSpec::Count(count) = bins
+           && count > MAX_BINS
+        {
+            return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                        format!("bins must be at most {}: {}",
+                                                MAX_BINS, count)));
+        }
+        let range = match (value.range_min, value.range_max) {
+            (Some(min), Some(max)) => Some((min, max)),
+            (None, None) => None,
+            _ => {
+                return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                            "rangeMin and rangeMax must be given together"));
+            },
+        };
+        // A binwidth is only checked here when the range is known; otherwise the domain checks it
+        // against the observed range.
+        if let Some((min, max)) = range
+           && min < max
+        {
+            bins.layout(min, max)
+                .map_err(|e| classified_error(AnalysisErrorKind::InputValidation, e))?;
+        }
+        Ok(HistogramSpec { bins,
+                           range,
+                           density: value.density })
+    }
+}
+
+#[tauri::command]
+pub fn histogram(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                 dataset_cache_id: String,
+                 column: String,
+                 options: HistogramOptionsDto)
+                 -> Result<ParsedDataTable, String> {
+    log::info!("statistics.histogram start dataset_cache_id={} column={}",
+               dataset_cache_id,
+               column);
+
+    let table = HistogramSpec::try_from(options)
+        .and_then(|spec| state.statistics_service.histogram(&dataset_cache_id, &column, spec))
+        .map_err(|e| {
+            log::error!("statistics.histogram failed dataset_cache_id={} column={} err={}",
+                        dataset_cache_id,
+                        column,
+                        e);
+            e
+        })?;
+
+    log::info!("statistics.histogram ok dataset_cache_id={} column={} bins={}",
+               dataset_cache_id,
+               column,
+               table.rows.len());
+    Ok(table)
+}
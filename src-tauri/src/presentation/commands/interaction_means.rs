@@ -0,0 +1,30 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn interaction_means(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                         dataset_cache_id: String,
+                         response: String,
+                         factor_a: String,
+                         factor_b: String)
+                         -> Result<ParsedDataTable, String> {
+    log::info!("statistics.interaction_means start dataset_cache_id={} response={} factor_a={} factor_b={}",
+               dataset_cache_id,
+               response,
+               factor_a,
+               factor_b);
+
+    let table = state.statistics_service
+                     .interaction_means(&dataset_cache_id, &response, &factor_a, &factor_b)
+                     .map_err(|e| {
+                         log::error!("statistics.interaction_means failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.interaction_means ok dataset_cache_id={} response={} cells={}",
+               dataset_cache_id,
+               response,
+               table.rows.len());
+    Ok(table)
+}
@@ -0,0 +1,25 @@
+use crate::domain::transform::header_match::{
+    self,
+    HeaderMatches,
+};
+
+#[tauri::command]
+pub fn match_headers(headers_a: Vec<String>,
+                     headers_b: Vec<String>,
+                     threshold: f64)
+                     -> Result<HeaderMatches, String> {
+    log::info!("data.match_headers start columns_a={} columns_b={} threshold={}",
+               headers_a.len(),
+               headers_b.len(),
+               threshold);
+
+    let matches = header_match::match_headers(&headers_a, &headers_b, threshold).map_err(|e| {
+                      log::error!("data.match_headers failed threshold={} err={}", threshold, e);
+                      e
+                  })?;
+
+    log::info!("data.match_headers ok mappings={} ambiguous={}",
+               matches.mappings.rows.len(),
+               matches.ambiguous.rows.len());
+    Ok(matches)
+}
@@ -0,0 +1,29 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::categories::{
+    self,
+    CategoryStrategy,
+    NormalizedCategories,
+};
+
+#[tauri::command]
+pub fn normalize_categories(table: ParsedDataTable,
+                            column: String,
+                            strategy: CategoryStrategy)
+                            -> Result<NormalizedCategories, String> {
+    log::info!("data.normalize_categories start column={} strategy={:?} rows={}",
+               column,
+               strategy,
+               table.rows.len());
+
+    let result = table.validate()
+                      .and_then(|_| categories::normalize_categories(&table, &column, strategy))
+                      .map_err(|e| {
+                          log::error!("data.normalize_categories failed column={} err={}", column, e);
+                          e
+                      })?;
+
+    log::info!("data.normalize_categories ok column={} variants={}",
+               column,
+               result.mapping.rows.len());
+    Ok(result)
+}
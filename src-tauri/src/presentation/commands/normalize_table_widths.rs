@@ -0,0 +1,30 @@
+use crate::domain::input::table::{
+    ParsedDataTable,
+    WidthStrategy,
+    join_notes,
+};
+
+#[tauri::command]
+pub fn normalize_table_widths(mut table: ParsedDataTable,
+                              strategy: WidthStrategy)
+                              -> Result<ParsedDataTable, String> {
+    log::info!("data.normalize_table_widths start strategy={:?} columns={} rows={}",
+               strategy,
+               table.headers.len(),
+               table.rows.len());
+
+    let adjustment = table.normalize_widths(strategy)
+                          .and_then(|adjustment| table.validate().map(|_| adjustment))
+                          .map_err(|e| {
+                              log::error!("data.normalize_table_widths failed strategy={:?} err={}",
+                                          strategy,
+                                          e);
+                              e
+                          })?;
+    table.note = join_notes([table.note.take(), adjustment.note()]);
+
+    log::info!("data.normalize_table_widths ok padded={} truncated={}",
+               adjustment.padded,
+               adjustment.truncated);
+    Ok(table)
+}
@@ -0,0 +1,25 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn parse_pasted_tsv(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        text: String,
+                        has_header: bool,
+                        read_options: Option<TableReadOptions>)
+                        -> Result<ParsedDataTable, String> {
+    log::info!("data.parse_pasted_tsv start bytes={} has_header={}",
+               text.len(),
+               has_header);
+
+    let table = state.import_service
+                     .parse_pasted_tsv(&text, has_header, &read_options.unwrap_or_default())
+                     .map_err(|e| {
+                         log::error!("data.parse_pasted_tsv failed bytes={} err={}", text.len(), e);
+                         e
+                     })?;
+
+    log::info!("data.parse_pasted_tsv ok headers={} rows={}",
+               table.headers.len(),
+               table.rows.len());
+    Ok(table)
+}
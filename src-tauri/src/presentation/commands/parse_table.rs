@@ -1,9 +1,11 @@
+use crate::domain::input::read_options::TableReadOptions;
 use crate::domain::input::table::ParsedDataTable;
 
 #[tauri::command]
 pub fn parse_table(state: tauri::State<'_, crate::bootstrap::state::AppState>,
                    path: String,
-                   sheet: Option<String>)
+                   sheet: Option<String>,
+                   read_options: Option<TableReadOptions>)
                    -> Result<ParsedDataTable, String> {
     let kind = crate::domain::input::source_kind::DataSourceKind::from_path(&path)?;
     let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
@@ -13,7 +15,7 @@ pub fn parse_table(state: tauri::State<'_, crate::bootstrap::state::AppState>,
                sheet_label);
 
     let table = state.import_service
-                     .parse_table(&path, sheet.as_deref())
+                     .parse_table(&path, sheet.as_deref(), &read_options.unwrap_or_default())
                      .map_err(|e| {
                          log::error!("data.parse failed path={} kind={} sheet={} err={}",
                                      path,
@@ -0,0 +1,47 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+use crate::domain::transform::partition::RowPartition;
+
+#[tauri::command]
+pub fn partition_rows(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                      path: String,
+                      sheet: Option<String>,
+                      test_fraction: f64,
+                      seed: u64,
+                      stratify_by: Option<String>,
+                      read_options: Option<TableReadOptions>)
+                      -> Result<RowPartition, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    log::info!("data.partition_rows start path={} kind={} sheet={} test_fraction={} seed={} stratify_by={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               test_fraction,
+               seed,
+               stratify_by.as_deref().unwrap_or("-"));
+
+    let partition = state.import_service
+                         .partition_rows(&path,
+                                         sheet.as_deref(),
+                                         &read_options.unwrap_or_default(),
+                                         test_fraction,
+                                         seed,
+                                         stratify_by.as_deref())
+                         .map_err(|e| {
+                             log::error!("data.partition_rows failed path={} kind={} sheet={} err={}",
+                                         path,
+                                         kind.as_str(),
+                                         sheet_label,
+                                         e);
+                             e
+                         })?;
+
+    log::info!("data.partition_rows ok path={} kind={} sheet={} train={} test={}",
+               path,
+               kind.as_str(),
+               sheet_label,
+               partition.train.rows.len(),
+               partition.test.rows.len());
+    Ok(partition)
+}
@@ -0,0 +1,28 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::reshape;
+
+#[tauri::command]
+pub fn pivot_longer(table: ParsedDataTable,
+                    id_columns: Vec<String>,
+                    value_columns: Vec<String>,
+                    drop_nulls: Option<bool>)
+                    -> Result<ParsedDataTable, String> {
+    log::info!("data.pivot_longer start ids={} values={} rows={}",
+               id_columns.len(),
+               value_columns.len(),
+               table.rows.len());
+
+    let long =
+        table.validate()
+             .and_then(|_| {
+                 reshape::pivot_longer(&table, &id_columns, &value_columns, drop_nulls.unwrap_or(false))
+             })
+             .and_then(|long| long.validate().map(|_| long))
+             .map_err(|e| {
+                 log::error!("data.pivot_longer failed err={}", e);
+                 e
+             })?;
+
+    log::info!("data.pivot_longer ok rows={}", long.rows.len());
+    Ok(long)
+}
@@ -0,0 +1,37 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::reshape::{
+    self,
+    WideCollision,
+};
+
+#[tauri::command]
+pub fn pivot_wider(table: ParsedDataTable,
+                   id_columns: Vec<String>,
+                   name_column: String,
+                   value_column: String,
+                   on_collision: Option<WideCollision>)
+                   -> Result<ParsedDataTable, String> {
+    let on_collision = on_collision.unwrap_or_default();
+    log::info!("data.pivot_wider start ids={} name={} value={} on_collision={:?} rows={}",
+               id_columns.len(),
+               name_column,
+               value_column,
+               on_collision,
+               table.rows.len());
+
+    let wide =
+        table.validate()
+             .and_then(|_| {
+                 reshape::pivot_wider(&table, &id_columns, &name_column, &value_column, on_collision)
+             })
+             .and_then(|wide| wide.validate().map(|_| wide))
+             .map_err(|e| {
+                 log::error!("data.pivot_wider failed err={}", e);
+                 e
+             })?;
+
+    log::info!("data.pivot_wider ok rows={} cols={}",
+               wide.rows.len(),
+               wide.headers.len());
+    Ok(wide)
+}
@@ -0,0 +1,23 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn quick_normality(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                       dataset_cache_id: String)
+                       -> Result<ParsedDataTable, String> {
+    log::info!("statistics.quick_normality start dataset_cache_id={}",
+               dataset_cache_id);
+
+    let table = state.statistics_service
+                     .quick_normality(&dataset_cache_id)
+                     .map_err(|e| {
+                         log::error!("statistics.quick_normality failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.quick_normality ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
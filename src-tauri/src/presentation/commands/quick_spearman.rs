@@ -0,0 +1,23 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn quick_spearman(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                      dataset_cache_id: String)
+                      -> Result<ParsedDataTable, String> {
+    log::info!("statistics.quick_spearman start dataset_cache_id={}",
+               dataset_cache_id);
+
+    let table = state.statistics_service
+                     .quick_spearman(&dataset_cache_id)
+                     .map_err(|e| {
+                         log::error!("statistics.quick_spearman failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.quick_spearman ok dataset_cache_id={} vars={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
@@ -0,0 +1,27 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn read_excel_formulas(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                           path: String,
+                           sheet: String,
+                           read_options: Option<TableReadOptions>)
+                           -> Result<ParsedDataTable, String> {
+    log::info!("data.read_excel_formulas start path={} sheet={}", path, sheet);
+
+    let table = state.import_service
+                     .read_excel_formulas(&path, &sheet, &read_options.unwrap_or_default())
+                     .map_err(|e| {
+                         log::error!("data.read_excel_formulas failed path={} sheet={} err={}",
+                                     path,
+                                     sheet,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("data.read_excel_formulas ok path={} sheet={} formulas={}",
+               path,
+               sheet,
+               table.rows.iter().flatten().filter(|cell| !cell.is_null()).count());
+    Ok(table)
+}
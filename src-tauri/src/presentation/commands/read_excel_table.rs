@@ -0,0 +1,28 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn read_excel_table(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                        path: String,
+                        table_name: String,
+                        read_options: Option<TableReadOptions>)
+                        -> Result<ParsedDataTable, String> {
+    log::info!("data.read_excel_table start path={} table={}", path, table_name);
+
+    let table = state.import_service
+                     .read_excel_table(&path, &table_name, &read_options.unwrap_or_default())
+                     .map_err(|e| {
+                         log::error!("data.read_excel_table failed path={} table={} err={}",
+                                     path,
+                                     table_name,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("data.read_excel_table ok path={} table={} headers={} rows={}",
+               path,
+               table_name,
+               table.headers.len(),
+               table.rows.len());
+    Ok(table)
+}
@@ -0,0 +1,29 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn rolling_correlation(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                           dataset_cache_id: String,
+                           x: String,
+                           y: String,
+                           window: usize)
+                           -> Result<ParsedDataTable, String> {
+    log::info!("statistics.rolling_correlation start dataset_cache_id={} x={} y={} window={}",
+               dataset_cache_id,
+               x,
+               y,
+               window);
+
+    let table = state.statistics_service
+                     .rolling_correlation(&dataset_cache_id, &x, &y, window)
+                     .map_err(|e| {
+                         log::error!("statistics.rolling_correlation failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.rolling_correlation ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
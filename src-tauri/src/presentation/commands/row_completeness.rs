@@ -0,0 +1,29 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::quality::{
+    self,
+    RowCompleteness,
+};
+
+#[tauri::command]
+pub fn row_completeness(table: ParsedDataTable,
+                        min_fraction: f64,
+                        drop_sparse: Option<bool>)
+                        -> Result<RowCompleteness, String> {
+    let drop_sparse = drop_sparse.unwrap_or(false);
+    log::info!("data.row_completeness start rows={} columns={} min_fraction={} drop_sparse={}",
+               table.rows.len(),
+               table.headers.len(),
+               min_fraction,
+               drop_sparse);
+
+    let result = table.validate()
+                      .and_then(|_| quality::row_completeness(&table, min_fraction, drop_sparse))
+                      .map_err(|e| {
+                          log::error!("data.row_completeness failed err={}", e);
+                          e
+                      })?;
+
+    log::info!("data.row_completeness ok sparse={}",
+               result.sparse_row_indices.len());
+    Ok(result)
+}
@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use serde::Serialize;
 use serde_json::Value;
+use tauri::Emitter;
 
 use crate::domain::analysis::error::{
     AnalysisErrorKind,
@@ -9,9 +10,11 @@ use crate::domain::analysis::error::{
 };
 use crate::domain::analysis::method::Method;
 use crate::domain::analysis::model::{
+    AnalysisPartial,
     AnalysisResult,
     AnalysisRunResult,
 };
+use crate::domain::input::metadata::ColumnMetadataMap;
 use crate::domain::input::table::ParsedDataTable;
 
 #[derive(Debug, Serialize)]
@@ -34,22 +37,41 @@ pub(crate) struct AnalysisRunResponseDto {
     analysis_id: String,
     logged_at: String,
     result: AnalysisResultDto,
+    #[serde(skip_serializing_if = "ColumnMetadataMap::is_empty")]
+    column_metadata: ColumnMetadataMap,
 }
 
+/// Payload of the `r:partial` event emitted per column while a streamed analysis runs.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AnalysisPartialEventDto {
+    dataset_cache_id: String,
+    analysis_type: String,
+    column: String,
+    index: usize,
+    total: usize,
+    table: ParsedDataTable,
+}
+
+const PARTIAL_EVENT: &str = "r:partial";
+
 impl From<AnalysisRunResult> for AnalysisRunResponseDto {
     fn from(value: AnalysisRunResult) -> Self {
         let sections = map_sections(value.result);
         Self { analysis_id: value.analysis_id,
                logged_at: value.logged_at,
-               result: AnalysisResultDto { sections } }
+               result: AnalysisResultDto { sections },
+               column_metadata: value.column_metadata }
     }
 }
 
 #[tauri::command]
-pub fn run_analysis(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+pub fn run_analysis(app: tauri::AppHandle<tauri::Wry>,
+                    state: tauri::State<'_, crate::bootstrap::state::AppState>,
                     dataset_cache_id: String,
                     analysis_type: String,
-                    options: Option<Value>)
+                    options: Option<Value>,
+                    stream: Option<bool>)
                     -> Result<AnalysisRunResponseDto, String> {
     log::info!("analysis.run_analysis start dataset_cache_id={} type={}",
                dataset_cache_id,
@@ -64,15 +86,33 @@ pub fn run_analysis(state: tauri::State<'_, crate::bootstrap::state::AppState>,
                      err
                  })?;
 
-    let result = state.analysis_service
-                      .run_analysis(&dataset_cache_id, method, options)
-                      .map_err(|e| {
-                          log::error!("analysis.run_analysis failed dataset_cache_id={} type={} err={}",
-                                      dataset_cache_id,
-                                      method.as_str(),
-                                      e);
-                          e
-                      })?;
+    let result = if stream.unwrap_or(false) {
+        let mut emit_partial = |partial: AnalysisPartial| {
+            let event = AnalysisPartialEventDto { dataset_cache_id: dataset_cache_id.clone(),
+                                                  analysis_type: method.as_str().to_string(),
+                                                  column: partial.column,
+                                                  index: partial.index,
+                                                  total: partial.total,
+                                                  table: partial.table };
+            if let Err(e) = app.emit(PARTIAL_EVENT, event) {
+                log::warn!("analysis.run_analysis partial emit failed dataset_cache_id={} err={}",
+                           dataset_cache_id,
+                           e);
+            }
+        };
+        state.analysis_service
+             .run_analysis_streaming(&dataset_cache_id, method, options, &mut emit_partial)
+    } else {
+        state.analysis_service
+             .run_analysis(&dataset_cache_id, method, options)
+    };
+    let result = result.map_err(|e| {
+                           log::error!("analysis.run_analysis failed dataset_cache_id={} type={} err={}",
+                                       dataset_cache_id,
+                                       method.as_str(),
+                                       e);
+                           e
+                       })?;
 
     log::info!("analysis.run_analysis ok dataset_cache_id={} type={}",
                dataset_cache_id,
@@ -103,9 +143,25 @@ pub(super) fn map_sections(result: AnalysisResult) -> Vec<AnalysisSectionDto> {
                  section("t_values", "統計量", correlation.t_values),]
         },
         AnalysisResult::Regression { regression } => {
-            vec![section("model_summary", "モデル要約", regression.model_summary),
-                 section("coefficients", "係数", regression.coefficients),
-                 section("anova", "分散分析", regression.anova),]
+            let mut sections = vec![section("model_summary", "モデル要約", regression.model_summary),
+                                    section("coefficients", "係数", regression.coefficients),
+                                    section("anova", "分散分析", regression.anova),];
+            if let Some(diagnostics) = regression.diagnostics {
+                sections.push(section("diagnostics", "回帰診断", diagnostics));
+            }
+            sections
+        },
+        AnalysisResult::Influence { influence } => {
+            vec![section("diagnostics", "影響診断", influence.diagnostics),
+                 section("flagged", "影響大の行", influence.flagged),]
+        },
+        AnalysisResult::KMeans { kmeans } => {
+            vec![section("assignments", "クラスタ割り当て", kmeans.assignments),
+                 section("centers", "クラスタ中心", kmeans.centers),]
+        },
+        AnalysisResult::BoxCox { boxcox } => {
+            vec![section("summary", "Box-Cox 変換", boxcox.summary),
+                 section("transformed", "変換後の値", boxcox.transformed),]
         },
         AnalysisResult::Factor { factor } => {
             let mut eigen_section = section("eigen", "固有値", factor.eigen);
@@ -144,6 +200,8 @@ mod tests {
     use crate::domain::analysis::model::{
         AnalysisResult,
         FactorResult,
+        InfluenceResult,
+        KMeansResult,
         RegressionResult,
     };
     use crate::domain::input::table::ParsedDataTable;
@@ -156,7 +214,8 @@ mod tests {
         ParsedDataTable { headers: headers.iter().map(|v| v.to_string()).collect(),
                           rows,
                           note: None,
-                          title: None }
+                          title: None,
+                          column_types: Vec::new() }
     }
 
     #[test]
@@ -172,7 +231,8 @@ mod tests {
                                                                                              0.5.into()]]),
                                                                          anova: table(&["ss", "df"],
                                                                                       vec![vec![10.into(),
-                                                                                             2.into()]]) } };
+                                                                                             2.into()]]),
+                                                                         diagnostics: None } };
 
         let sections = map_sections(result);
         assert_eq!(sections.len(), 3);
@@ -181,6 +241,64 @@ mod tests {
         assert_eq!(sections[2].key, "anova");
     }
 
+    #[test]
+    fn map_sections_for_regression_appends_diagnostics_when_present() {
+        let regression = RegressionResult { model_summary: table(&["item"], vec![vec!["A".into()]]),
+                                            coefficients: table(&["name"], vec![vec!["x".into()]]),
+                                            anova: table(&["ss"], vec![vec![10.into()]]),
+                                            diagnostics: Some(table(&["row", "fitted"],
+                                                                    vec![vec![1.into(), 0.5.into()]])) };
+        let result = AnalysisResult::Regression { regression };
+
+        let sections = map_sections(result);
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[3].key, "diagnostics");
+    }
+
+    #[test]
+    fn map_sections_for_influence_lists_rows_then_flagged_rows() {
+        let diagnostics = table(&["行", "影響大"],
+                                vec![vec!["1".into(), false.into()], vec!["2".into(), true.into()]]);
+        let flagged = table(&["行"], vec![vec!["2".into()]]);
+        let result = AnalysisResult::Influence { influence: InfluenceResult { diagnostics, flagged } };
+
+        let sections = map_sections(result);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].key, "diagnostics");
+        assert_eq!(sections[1].key, "flagged");
+        assert_eq!(sections[1].table.rows, vec![vec![Value::from("2")]]);
+    }
+
+    #[test]
+    fn map_sections_for_kmeans_lists_assignments_then_centers() {
+        let assignments = table(&["行", "クラスタ"], vec![vec!["1".into(), "2".into()]]);
+        let centers = table(&["クラスタ", "n"], vec![vec!["1".into(), "4".into()]]);
+        let result = AnalysisResult::KMeans { kmeans: KMeansResult { assignments, centers } };
+
+        let sections = map_sections(result);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].key, "assignments");
+        assert_eq!(sections[1].key, "centers");
+    }
+
+    #[test]
+    fn box_cox_result_deserializes_and_lists_summary_then_transformed() {
+        let result: AnalysisResult = serde_json::from_value(serde_json::json!({
+            "kind": "boxcox",
+            "boxcox": {
+                "summary": { "headers": ["項目", "値"], "rows": [["λ", "0.05"]] },
+                "transformed": { "headers": ["行", "x", "変換後"], "rows": [["1", "2.5", "0.94"], ["2", null, null]] }
+            }
+        })).unwrap();
+        assert!(matches!(result, AnalysisResult::BoxCox { .. }));
+
+        let sections = map_sections(result);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].key, "summary");
+        assert_eq!(sections[1].key, "transformed");
+        assert_eq!(sections[1].table.rows[1][1], Value::Null);
+    }
+
     #[test]
     fn map_sections_for_factor_includes_optional_sections() {
         let result =
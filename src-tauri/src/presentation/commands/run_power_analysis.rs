@@ -58,7 +58,8 @@ mod tests {
         ParsedDataTable { headers: headers.iter().map(|v| v.to_string()).collect(),
                           rows,
                           note: None,
-                          title: None }
+                          title: None,
+                          column_types: Vec::new() }
     }
 
     #[test]
@@ -0,0 +1,28 @@
+use crate::domain::input::numeric::{
+    NumericDataset,
+    numeric_dataset_to_csv,
+};
+
+#[tauri::command]
+pub fn save_dataset_csv(path: String,
+                        dataset: NumericDataset)
+                        -> Result<(), String> {
+    log::info!("data.save_dataset_csv start path={} columns={}",
+               path,
+               dataset.len());
+
+    numeric_dataset_to_csv(&dataset).and_then(|csv| write_csv(&path, csv))
+                                    .map_err(|e| {
+                                        log::error!("data.save_dataset_csv failed path={} err={}", path, e);
+                                        e
+                                    })?;
+
+    log::info!("data.save_dataset_csv ok path={}", path);
+    Ok(())
+}
+
+fn write_csv(path: &str,
+             csv: String)
+             -> Result<(), String> {
+    std::fs::write(path, csv).map_err(|e| format!("failed to write CSV: {}", e))
+}
@@ -0,0 +1,31 @@
+use crate::domain::input::table::{
+    MarkdownFrontMatter,
+    ParsedDataTable,
+};
+
+#[tauri::command]
+pub fn save_table_markdown(path: String,
+                           table: ParsedDataTable,
+                           front_matter: Option<MarkdownFrontMatter>)
+                           -> Result<(), String> {
+    log::info!("data.save_table_markdown start path={} rows={} front_matter={}",
+               path,
+               table.rows.len(),
+               front_matter.is_some());
+
+    table.validate()
+         .and_then(|_| {
+             let markdown = match &front_matter {
+                 Some(meta) => table.to_markdown_with_frontmatter(meta),
+                 None => table.to_markdown(),
+             };
+             std::fs::write(&path, markdown).map_err(|e| format!("failed to write markdown: {}", e))
+         })
+         .map_err(|e| {
+             log::error!("data.save_table_markdown failed path={} err={}", path, e);
+             e
+         })?;
+
+    log::info!("data.save_table_markdown ok path={}", path);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::simpson::{
+    self,
+    SimpsonsCheck,
+};
+
+#[tauri::command]
+pub fn simpsons_check(table: ParsedDataTable,
+                      x: String,
+                      y: String,
+                      group: String)
+                      -> Result<SimpsonsCheck, String> {
+    log::info!("statistics.simpsons_check start x={} y={} group={} rows={}",
+               x,
+               y,
+               group,
+               table.rows.len());
+
+    let check = table.validate()
+                     .and_then(|_| simpson::simpsons_check(&table, &x, &y, &group))
+                     .map_err(|e| {
+                         log::error!("statistics.simpsons_check failed x={} y={} group={} err={}",
+                                     x,
+                                     y,
+                                     group,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.simpsons_check ok x={} y={} group={} levels={} pooled={:?} paradox={}",
+               x,
+               y,
+               group,
+               check.table.rows.len() - 1,
+               check.pooled,
+               check.paradox);
+    Ok(check)
+}
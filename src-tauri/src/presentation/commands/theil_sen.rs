@@ -0,0 +1,28 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn theil_sen(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                 dataset_cache_id: String,
+                 x: String,
+                 y: String)
+                 -> Result<ParsedDataTable, String> {
+    log::info!("statistics.theil_sen start dataset_cache_id={} x={} y={}",
+               dataset_cache_id,
+               x,
+               y);
+
+    let table = state.statistics_service
+                     .theil_sen(&dataset_cache_id, &x, &y)
+                     .map_err(|e| {
+                         log::error!("statistics.theil_sen failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.theil_sen ok dataset_cache_id={} x={} y={}",
+               dataset_cache_id,
+               x,
+               y);
+    Ok(table)
+}
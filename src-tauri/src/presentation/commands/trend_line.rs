@@ -0,0 +1,28 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn trend_line(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                  dataset_cache_id: String,
+                  x: String,
+                  y: String)
+                  -> Result<ParsedDataTable, String> {
+    log::info!("statistics.trend_line start dataset_cache_id={} x={} y={}",
+               dataset_cache_id,
+               x,
+               y);
+
+    let table = state.statistics_service
+                     .trend_line(&dataset_cache_id, &x, &y)
+                     .map_err(|e| {
+                         log::error!("statistics.trend_line failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.trend_line ok dataset_cache_id={} x={} y={}",
+               dataset_cache_id,
+               x,
+               y);
+    Ok(table)
+}
@@ -0,0 +1,23 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::sheet::SheetReadiness;
+
+#[tauri::command]
+pub fn validate_workbook(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                         path: String,
+                         read_options: Option<TableReadOptions>)
+                         -> Result<Vec<SheetReadiness>, String> {
+    log::info!("data.validate_workbook start path={}", path);
+
+    let results = state.import_service
+                       .validate_workbook(&path, &read_options.unwrap_or_default())
+                       .map_err(|e| {
+                           log::error!("data.validate_workbook failed path={} err={}", path, e);
+                           e
+                       })?;
+
+    log::info!("data.validate_workbook ok path={} sheets={} unreadable={}",
+               path,
+               results.len(),
+               results.iter().filter(|sheet| !sheet.readable).count());
+    Ok(results)
+}
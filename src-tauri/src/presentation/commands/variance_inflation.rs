@@ -0,0 +1,25 @@
+use crate::domain::input::table::ParsedDataTable;
+
+#[tauri::command]
+pub fn variance_inflation(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                          dataset_cache_id: String,
+                          columns: Vec<String>)
+                          -> Result<ParsedDataTable, String> {
+    log::info!("statistics.variance_inflation start dataset_cache_id={} columns={}",
+               dataset_cache_id,
+               columns.len());
+
+    let table = state.statistics_service
+                     .variance_inflation(&dataset_cache_id, &columns)
+                     .map_err(|e| {
+                         log::error!("statistics.variance_inflation failed dataset_cache_id={} err={}",
+                                     dataset_cache_id,
+                                     e);
+                         e
+                     })?;
+
+    log::info!("statistics.variance_inflation ok dataset_cache_id={} rows={}",
+               dataset_cache_id,
+               table.rows.len());
+    Ok(table)
+}
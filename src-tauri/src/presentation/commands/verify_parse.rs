@@ -0,0 +1,42 @@
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::source_kind::DataSourceKind;
+use crate::domain::input::verify::ParseMismatch;
+
+#[tauri::command]
+pub fn verify_parse(state: tauri::State<'_, crate::bootstrap::state::AppState>,
+                    path: String,
+                    sheet: Option<String>,
+                    read_options: Option<TableReadOptions>)
+                    -> Result<Vec<ParseMismatch>, String> {
+    let kind = DataSourceKind::from_path(&path)?;
+    let sheet_label = sheet.clone().unwrap_or_else(|| "-".to_string());
+    log::info!("data.verify_parse start path={} kind={} sheet={}",
+               path,
+               kind.as_str(),
+               sheet_label);
+
+    let mismatches = state.import_service
+                          .verify_parse(&path, sheet.as_deref(), &read_options.unwrap_or_default())
+                          .map_err(|e| {
+                              log::error!("data.verify_parse failed path={} kind={} sheet={} err={}",
+                                          path,
+                                          kind.as_str(),
+                                          sheet_label,
+                                          e);
+                              e
+                          })?;
+
+    if mismatches.is_empty() {
+        log::info!("data.verify_parse ok path={} kind={} sheet={} mismatches=0",
+                   path,
+                   kind.as_str(),
+                   sheet_label);
+    } else {
+        log::warn!("data.verify_parse ok path={} kind={} sheet={} mismatches={}",
+                   path,
+                   kind.as_str(),
+                   sheet_label,
+                   mismatches.len());
+    }
+    Ok(mismatches)
+}
@@ -0,0 +1,25 @@
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::transform::winsorize;
+
+#[tauri::command]
+pub fn winsorize(table: ParsedDataTable,
+                 columns: Vec<String>,
+                 lower: f64,
+                 upper: f64)
+                 -> Result<ParsedDataTable, String> {
+    log::info!("data.winsorize start columns={} lower={} upper={} rows={}",
+               columns.len(),
+               lower,
+               upper,
+               table.rows.len());
+
+    let cleaned = table.validate()
+                       .and_then(|_| winsorize::winsorize(&table, &columns, lower, upper))
+                       .map_err(|e| {
+                           log::error!("data.winsorize failed err={}", e);
+                           e
+                       })?;
+
+    log::info!("data.winsorize ok rows={}", cleaned.rows.len());
+    Ok(cleaned)
+}
@@ -1,3 +1,4 @@
 pub(crate) mod analysis;
 pub(crate) mod analysis_log;
 pub(crate) mod import;
+pub(crate) mod statistics;
@@ -1,10 +1,26 @@
 mod anova;
+mod auto_compare;
+mod box_cox;
+mod chi_square;
+mod confidence_bands;
 mod correlation;
 mod descriptive;
 mod factor;
+mod forecast;
+mod influence;
+mod kaplan_meier;
+mod kmeans;
+mod mcnemar;
+mod pairwise_effect_sizes;
+mod pairwise_t_tests;
 mod power;
+mod quantiles;
 mod regression;
 mod reliability;
+mod rolling_ttest;
+mod t_test;
+mod target_correlation;
+mod yuen_t_test;
 
 use serde_json::Value;
 
@@ -16,6 +32,25 @@ pub(crate) trait AnalysisMethodHandler: Send + Sync {
                          options: Option<Value>)
                          -> Value;
 
+    /// Option keys whose value names a single dataset column. The service checks them against
+    /// the dataset before R is started, as it does for `columns`.
+    fn column_options(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Option keys whose value names grouping columns, as one name or a list of names. The service
+    /// checks that each one is in the dataset and has at least two levels before R is started.
+    fn factor_options(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Rejects option combinations before R is started. Runs on the normalized options.
+    fn validate_options(&self,
+                        _normalized_options: &Value)
+                        -> Result<(), String> {
+        Ok(())
+    }
+
     fn post_process(&self,
                     _result: &mut AnalysisResult,
                     _normalized_options: &Value)
@@ -27,16 +62,48 @@ pub(crate) trait AnalysisMethodHandler: Send + Sync {
 pub(crate) fn resolve_handler(method: Method) -> &'static dyn AnalysisMethodHandler {
     if method == Method::ANOVA {
         &anova::ANOVA_HANDLER
+    } else if method == Method::AUTO_COMPARE {
+        &auto_compare::AUTO_COMPARE_HANDLER
+    } else if method == Method::BOX_COX {
+        &box_cox::BOX_COX_HANDLER
+    } else if method == Method::CHI_SQUARE {
+        &chi_square::CHI_SQUARE_HANDLER
+    } else if method == Method::CONFIDENCE_BANDS {
+        &confidence_bands::CONFIDENCE_BANDS_HANDLER
     } else if method == Method::DESCRIPTIVE {
         &descriptive::DESCRIPTIVE_HANDLER
     } else if method == Method::FACTOR {
         &factor::FACTOR_HANDLER
+    } else if method == Method::FORECAST {
+        &forecast::FORECAST_HANDLER
+    } else if method == Method::INFLUENCE {
+        &influence::INFLUENCE_HANDLER
+    } else if method == Method::KAPLAN_MEIER {
+        &kaplan_meier::KAPLAN_MEIER_HANDLER
+    } else if method == Method::KMEANS {
+        &kmeans::KMEANS_HANDLER
+    } else if method == Method::MCNEMAR {
+        &mcnemar::MCNEMAR_HANDLER
+    } else if method == Method::PAIRWISE_EFFECT_SIZES {
+        &pairwise_effect_sizes::PAIRWISE_EFFECT_SIZES_HANDLER
+    } else if method == Method::PAIRWISE_T_TESTS {
+        &pairwise_t_tests::PAIRWISE_T_TESTS_HANDLER
     } else if method == Method::POWER {
         &power::POWER_HANDLER
+    } else if method == Method::QUANTILES {
+        &quantiles::QUANTILES_HANDLER
     } else if method == Method::REGRESSION {
         &regression::REGRESSION_HANDLER
     } else if method == Method::RELIABILITY {
         &reliability::RELIABILITY_HANDLER
+    } else if method == Method::ROLLING_TTEST {
+        &rolling_ttest::ROLLING_TTEST_HANDLER
+    } else if method == Method::T_TEST {
+        &t_test::T_TEST_HANDLER
+    } else if method == Method::TARGET_CORRELATION {
+        &target_correlation::TARGET_CORRELATION_HANDLER
+    } else if method == Method::YUEN_T_TEST {
+        &yuen_t_test::YUEN_T_TEST_HANDLER
     } else {
         &correlation::CORRELATION_HANDLER
     }
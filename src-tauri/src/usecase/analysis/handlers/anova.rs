@@ -13,6 +13,75 @@ impl AnalysisMethodHandler for AnovaHandler {
     fn normalize_options(&self,
                          options: Option<Value>)
                          -> Value {
-        Value::Object(normalize_options_object(options))
+        let mut normalized = normalize_options_object(options);
+
+        // `effect_size: true` asks for every effect size column (η², 偏η², ω²); `false` for none.
+        if let Some(enabled) = normalized.get("effect_size").and_then(Value::as_bool) {
+            let effect_size = if enabled { "all" } else { "none" };
+            normalized.insert("effect_size".to_string(), Value::from(effect_size));
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn factor_options(&self) -> &'static [&'static str] {
+        &["between_factors"]
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        let Some(posthoc) = normalized_options.get("posthoc").filter(|value| !value.is_null()) else {
+            return Ok(());
+        };
+        if posthoc.as_str() != Some("tukey") {
+            return Err("posthoc must be tukey".to_string());
+        }
+        let is_set = |key: &str| match normalized_options.get(key) {
+            Some(Value::Array(values)) => !values.is_empty(),
+            Some(Value::String(value)) => !value.trim().is_empty(),
+            _ => false,
+        };
+        if is_set("within_factor_levels") || is_set("covariates") {
+            return Err("posthoc tukey is only available for between-subjects designs without covariates".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ANOVA_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn boolean_effect_size_maps_to_all_or_none() {
+        let enabled = ANOVA_HANDLER.normalize_options(Some(json!({ "effect_size": true })));
+        let disabled = ANOVA_HANDLER.normalize_options(Some(json!({ "effect_size": false })));
+        let single = ANOVA_HANDLER.normalize_options(Some(json!({ "effect_size": "omega" })));
+
+        assert_eq!(enabled["effect_size"], json!("all"));
+        assert_eq!(disabled["effect_size"], json!("none"));
+        assert_eq!(single["effect_size"], json!("omega"));
+    }
+
+    #[test]
+    fn tukey_posthoc_is_limited_to_between_subjects_designs() {
+        let between =
+            ANOVA_HANDLER.normalize_options(Some(json!({ "dependent": "y", "between_factors": ["g"],
+                                                                   "posthoc": "tukey" })));
+        let within =
+            ANOVA_HANDLER.normalize_options(Some(json!({ "subject": "id", "within_factor_name": "time",
+                                                                  "within_factor_levels": ["t1", "t2"],
+                                                                  "posthoc": "tukey" })));
+        let unknown =
+            ANOVA_HANDLER.normalize_options(Some(json!({ "dependent": "y", "between_factors": ["g"],
+                                                                   "posthoc": "scheffe" })));
+
+        assert!(ANOVA_HANDLER.validate_options(&between).is_ok());
+        assert!(ANOVA_HANDLER.validate_options(&within).is_err());
+        assert!(ANOVA_HANDLER.validate_options(&unknown).is_err());
     }
 }
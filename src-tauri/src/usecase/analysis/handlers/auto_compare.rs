@@ -0,0 +1,46 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+/// Significance level for the normality and equal-variance checks that pick the test.
+const DEFAULT_ALPHA: f64 = 0.05;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct AutoCompareHandler;
+
+pub(super) static AUTO_COMPARE_HANDLER: AutoCompareHandler = AutoCompareHandler;
+
+impl AnalysisMethodHandler for AutoCompareHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // A numeric alpha is kept even outside (0, 1), so that RunAutoCompare rejects it instead of
+        // the checks running at a level the user did not ask for.
+        let alpha = normalized.get("alpha")
+                              .and_then(Value::as_f64)
+                              .unwrap_or(DEFAULT_ALPHA);
+        normalized.insert("alpha".to_string(), Value::from(alpha));
+
+        Value::Object(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::AUTO_COMPARE_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn alpha_defaults_to_five_percent() {
+        let options = AUTO_COMPARE_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g" })));
+
+        assert_eq!(options["alpha"], json!(0.05));
+        assert_eq!(options["group"], json!("g"));
+    }
+}
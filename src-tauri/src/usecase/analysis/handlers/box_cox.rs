@@ -0,0 +1,65 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_string_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct BoxCoxHandler;
+
+pub(super) static BOX_COX_HANDLER: BoxCoxHandler = BoxCoxHandler;
+
+impl AnalysisMethodHandler for BoxCoxHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // No shift unless asked for: non-positive values are rejected rather than silently moved.
+        if normalized.get("shift").is_none_or(Value::is_null) {
+            normalized.insert("shift".to_string(), Value::from(0.0));
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn column_options(&self) -> &'static [&'static str] {
+        &["column"]
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        if option_string_from_value(normalized_options.get("column")).is_none() {
+            return Err("column is required".to_string());
+        }
+        // Positivity of the shifted values is checked in R, where the values are known.
+        match normalized_options.get("shift").and_then(Value::as_f64) {
+            Some(shift) if shift.is_finite() => Ok(()),
+            _ => Err("shift must be a finite number".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::BOX_COX_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn shift_defaults_to_zero_and_column_is_required() {
+        let options = BOX_COX_HANDLER.normalize_options(Some(json!({ "column": "income" })));
+
+        assert_eq!(options["shift"], json!(0.0));
+        assert!(BOX_COX_HANDLER.validate_options(&options).is_ok());
+        assert!(BOX_COX_HANDLER.validate_options(&BOX_COX_HANDLER.normalize_options(None))
+                               .is_err());
+        assert!(BOX_COX_HANDLER.validate_options(&json!({ "column": "income", "shift": "one" }))
+                               .is_err());
+    }
+}
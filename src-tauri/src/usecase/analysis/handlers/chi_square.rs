@@ -0,0 +1,83 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    clamped_count_option,
+    normalize_options_object,
+};
+
+use super::AnalysisMethodHandler;
+
+/// Simulated tables when `B` is not given, as in R's chisq.test().
+const DEFAULT_REPLICATES: u64 = 2000;
+
+/// Upper bound on simulated tables, so that a large `B` cannot stall the analysis.
+const MAX_REPLICATES: u64 = 100_000;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ChiSquareHandler;
+
+pub(super) static CHI_SQUARE_HANDLER: ChiSquareHandler = ChiSquareHandler;
+
+impl AnalysisMethodHandler for ChiSquareHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // The asymptotic p-value unless the simulation is asked for.
+        let simulate_p = normalized.get("simulate_p")
+                                   .and_then(Value::as_bool)
+                                   .unwrap_or(false);
+        normalized.insert("simulate_p".to_string(), Value::from(simulate_p));
+
+        clamped_count_option(&mut normalized, "B", DEFAULT_REPLICATES, MAX_REPLICATES);
+
+        // Forwarded as given; R only uses it for the simulation.
+        if normalized.get("seed").and_then(Value::as_i64).is_none() {
+            normalized.remove("seed");
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        match normalized_options.get("B").and_then(Value::as_u64) {
+            Some(replicates) if replicates >= 1 => Ok(()),
+            _ => Err("B must be at least 1".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::CHI_SQUARE_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn simulation_is_off_by_default() {
+        let options = CHI_SQUARE_HANDLER.normalize_options(Some(json!({ "row": "a", "column": "b" })));
+
+        assert_eq!(options,
+                   json!({ "row": "a", "column": "b", "simulate_p": false, "B": 2000 }));
+        assert!(CHI_SQUARE_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn replicates_are_capped_and_the_seed_is_forwarded() {
+        let options =
+            CHI_SQUARE_HANDLER.normalize_options(Some(json!({ "simulate_p": true, "B": 5_000_000, "seed": 42 })));
+
+        assert_eq!(options["B"], json!(100_000));
+        assert_eq!(options["seed"], json!(42));
+
+        let invalid_seed = CHI_SQUARE_HANDLER.normalize_options(Some(json!({ "seed": "abc" })));
+        assert!(invalid_seed.get("seed").is_none());
+
+        let zero = CHI_SQUARE_HANDLER.normalize_options(Some(json!({ "simulate_p": true, "B": 0 })));
+        assert!(CHI_SQUARE_HANDLER.validate_options(&zero).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+/// Grid points when `grid_size` is not given.
+const DEFAULT_GRID_SIZE: u64 = 50;
+
+/// Upper bound on `grid_size`; the band table has one row per grid point.
+const MAX_GRID_SIZE: u64 = 1000;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ConfidenceBandsHandler;
+
+pub(super) static CONFIDENCE_BANDS_HANDLER: ConfidenceBandsHandler = ConfidenceBandsHandler;
+
+impl AnalysisMethodHandler for ConfidenceBandsHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Only a missing size is filled in; any given value is left for validation.
+        if normalized.get("grid_size").is_none_or(Value::is_null) {
+            normalized.insert("grid_size".to_string(), Value::from(DEFAULT_GRID_SIZE));
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        match normalized_options.get("grid_size").and_then(Value::as_u64) {
+            Some(grid_size) if (2..=MAX_GRID_SIZE).contains(&grid_size) => Ok(()),
+            _ => Err(format!("grid_size must be an integer between 2 and {}", MAX_GRID_SIZE)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::CONFIDENCE_BANDS_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn grid_size_defaults_to_fifty_points() {
+        let options =
+            CONFIDENCE_BANDS_HANDLER.normalize_options(Some(json!({ "x": "dose", "y": "response" })));
+
+        assert_eq!(options, json!({ "x": "dose", "y": "response", "grid_size": 50 }));
+        assert!(CONFIDENCE_BANDS_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn keeps_requested_grid_size() {
+        let options = CONFIDENCE_BANDS_HANDLER.normalize_options(Some(json!({ "grid_size": 200 })));
+
+        assert_eq!(options["grid_size"], json!(200));
+        assert!(CONFIDENCE_BANDS_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn grid_size_that_is_not_a_count_in_range_is_rejected() {
+        for grid_size in [json!(1), json!(-5), json!(2.5), json!("50"), json!(1001)] {
+            let options = CONFIDENCE_BANDS_HANDLER.normalize_options(Some(json!({ "grid_size": grid_size })));
+
+            assert!(CONFIDENCE_BANDS_HANDLER.validate_options(&options).is_err(),
+                    "grid_size {}",
+                    grid_size);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use serde_json::Value;
 
-use crate::domain::analysis::rule::normalize_options_object;
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_string_from_value,
+};
 
 use super::AnalysisMethodHandler;
 
@@ -13,6 +16,108 @@ impl AnalysisMethodHandler for CorrelationHandler {
     fn normalize_options(&self,
                          options: Option<Value>)
                          -> Value {
-        Value::Object(normalize_options_object(options))
+        let mut normalized = normalize_options_object(options);
+
+        // A blank group means the ungrouped matrix, which R only runs on numeric datasets.
+        match option_string_from_value(normalized.get("group")) {
+            Some(group) => {
+                normalized.insert("group".to_string(), Value::String(group));
+            },
+            None => {
+                normalized.remove("group");
+            },
+        }
+
+        match option_string_from_value(normalized.get("weights")) {
+            Some(weights) => {
+                normalized.insert("weights".to_string(), Value::String(weights));
+            },
+            None => {
+                normalized.remove("weights");
+            },
+        }
+
+        // The p-value matrix for heatmaps is opt-in.
+        let p_matrix = normalized.get("p_matrix")
+                                 .and_then(Value::as_bool)
+                                 .unwrap_or(false);
+        normalized.insert("p_matrix".to_string(), Value::from(p_matrix));
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        if normalized_options.get("weights").is_none() {
+            return Ok(());
+        }
+        let method = option_string_from_value(normalized_options.get("method")).unwrap_or_default();
+        if !method.eq_ignore_ascii_case("pearson") {
+            return Err(format!("weights are only supported for Pearson correlation, not '{}'",
+                               method));
+        }
+        if normalized_options.get("group").is_some() {
+            return Err("weights cannot be combined with group".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::CORRELATION_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn group_is_trimmed() {
+        let options =
+            CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "pearson", "group": " sex " })));
+
+        assert_eq!(options,
+                   json!({ "method": "pearson", "group": "sex", "p_matrix": false }));
+    }
+
+    #[test]
+    fn blank_group_is_dropped() {
+        let options =
+            CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "pearson", "group": "  " })));
+
+        assert_eq!(options, json!({ "method": "pearson", "p_matrix": false }));
+        assert!(CORRELATION_HANDLER.normalize_options(Some(json!({ "group": null })))
+                                   .get("group")
+                                   .is_none());
+    }
+
+    #[test]
+    fn keeps_p_matrix_when_requested() {
+        let options = CORRELATION_HANDLER.normalize_options(Some(json!({ "p_matrix": true })));
+
+        assert_eq!(options["p_matrix"], json!(true));
+    }
+
+    #[test]
+    fn weights_are_limited_to_ungrouped_pearson() {
+        let pearson =
+            CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "pearson", "weights": " w " })));
+        assert_eq!(pearson["weights"], json!("w"));
+        assert!(CORRELATION_HANDLER.validate_options(&pearson).is_ok());
+
+        let spearman =
+            CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "spearman", "weights": "w" })));
+        let err = CORRELATION_HANDLER.validate_options(&spearman).unwrap_err();
+        assert!(err.contains("only supported for Pearson"), "{}", err);
+
+        let grouped = CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "pearson",
+                                                                          "weights": "w",
+                                                                          "group": "g" })));
+        assert!(CORRELATION_HANDLER.validate_options(&grouped).is_err());
+
+        let blank =
+            CORRELATION_HANDLER.normalize_options(Some(json!({ "method": "kendall", "weights": "" })));
+        assert!(blank.get("weights").is_none());
+        assert!(CORRELATION_HANDLER.validate_options(&blank).is_ok());
     }
 }
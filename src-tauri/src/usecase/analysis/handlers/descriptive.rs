@@ -30,19 +30,88 @@ impl AnalysisMethodHandler for DescriptiveHandler {
         let kurtosis = normalized.get("kurtosis")
                                  .and_then(option_bool_from_value)
                                  .unwrap_or(false);
+        let mode = normalized.get("mode")
+                             .and_then(option_bool_from_value)
+                             .unwrap_or(false);
+        let dispersion = normalized.get("dispersion")
+                                   .and_then(option_bool_from_value)
+                                   .unwrap_or(false);
+        let mad_constant = normalized.get("mad_constant")
+                                     .and_then(option_bool_from_value)
+                                     .unwrap_or(true);
         let histogram =
             option_string_from_value(normalized.get("histogram")).unwrap_or_else(|| "none".to_string());
         let breaks =
             option_string_from_value(normalized.get("breaks")).unwrap_or_else(|| "Sturges".to_string());
         // histogram_variables is passed through as-is (array of strings)
 
+        // A blank weights column means unweighted statistics.
+        match option_string_from_value(normalized.get("weights")) {
+            Some(weights) => {
+                normalized.insert("weights".to_string(), Value::String(weights));
+            },
+            None => {
+                normalized.remove("weights");
+            },
+        }
+
         normalized.insert("order".to_string(), Value::String(order));
         normalized.insert("na_ignore".to_string(), Value::Bool(na_ignore));
         normalized.insert("skewness".to_string(), Value::Bool(skewness));
         normalized.insert("kurtosis".to_string(), Value::Bool(kurtosis));
+        normalized.insert("mode".to_string(), Value::Bool(mode));
+        normalized.insert("dispersion".to_string(), Value::Bool(dispersion));
+        normalized.insert("mad_constant".to_string(), Value::Bool(mad_constant));
         normalized.insert("histogram".to_string(), Value::String(histogram));
         normalized.insert("breaks".to_string(), Value::String(breaks));
 
         Value::Object(normalized)
     }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        if normalized_options.get("weights").is_none() {
+            return Ok(());
+        }
+        // Only the mean, median, variance and SD have weighted versions.
+        let enabled = |key: &&str| normalized_options[*key] == Value::Bool(true);
+        let unweighted: Vec<&str> = ["skewness", "kurtosis", "mode", "dispersion"].into_iter()
+                                                                                  .filter(enabled)
+                                                                                  .collect();
+        if unweighted.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("weights cannot be combined with {}", unweighted.join(", ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::DESCRIPTIVE_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn blank_weights_are_dropped() {
+        let options = DESCRIPTIVE_HANDLER.normalize_options(Some(json!({ "weights": " " })));
+        assert!(options.get("weights").is_none());
+        assert!(DESCRIPTIVE_HANDLER.validate_options(&options).is_ok());
+
+        let options = DESCRIPTIVE_HANDLER.normalize_options(Some(json!({ "weights": " w " })));
+        assert_eq!(options["weights"], json!("w"));
+    }
+
+    #[test]
+    fn weights_reject_statistics_without_a_weighted_version() {
+        let options =
+            DESCRIPTIVE_HANDLER.normalize_options(Some(json!({ "weights": "w", "skewness": true, "mode": true })));
+
+        let err = DESCRIPTIVE_HANDLER.validate_options(&options).unwrap_err();
+        assert_eq!(err, "weights cannot be combined with skewness, mode");
+        let options = DESCRIPTIVE_HANDLER.normalize_options(Some(json!({ "skewness": true })));
+        assert!(DESCRIPTIVE_HANDLER.validate_options(&options).is_ok());
+    }
 }
@@ -0,0 +1,113 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+const DEFAULT_METHOD: &str = "linear";
+const DEFAULT_HORIZON: u64 = 5;
+
+/// Methods RunForecast implements.
+const METHODS: [&str; 2] = ["linear", "holt"];
+
+/// Upper bound on `horizon`; prediction intervals this far out are too wide to be of use.
+const MAX_HORIZON: u64 = 100;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct ForecastHandler;
+
+pub(super) static FORECAST_HANDLER: ForecastHandler = ForecastHandler;
+
+impl AnalysisMethodHandler for ForecastHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Method names are matched case-insensitively. A value that is not a string is kept as
+        // given, so that validation rejects it instead of forecasting with the default.
+        let method = match normalized.get("method") {
+            None | Some(Value::Null) => Value::from(DEFAULT_METHOD),
+            Some(Value::String(method)) if method.trim().is_empty() => Value::from(DEFAULT_METHOD),
+            Some(Value::String(method)) => Value::from(method.trim().to_ascii_lowercase()),
+            Some(other) => other.clone(),
+        };
+        normalized.insert("method".to_string(), method);
+
+        if normalized.get("horizon").is_none_or(Value::is_null) {
+            normalized.insert("horizon".to_string(), Value::from(DEFAULT_HORIZON));
+        }
+
+        let interpolate = normalized.get("interpolate")
+                                    .and_then(Value::as_bool)
+                                    .unwrap_or(false);
+        normalized.insert("interpolate".to_string(), Value::from(interpolate));
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        let method = normalized_options.get("method").and_then(Value::as_str);
+        if !method.is_some_and(|method| METHODS.contains(&method)) {
+            return Err(format!("method must be one of: {}", METHODS.join(", ")));
+        }
+        match normalized_options.get("horizon").and_then(Value::as_u64) {
+            Some(horizon) if (1..=MAX_HORIZON).contains(&horizon) => Ok(()),
+            _ => Err(format!("horizon must be an integer between 1 and {}", MAX_HORIZON)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::FORECAST_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_a_linear_five_step_forecast_without_interpolation() {
+        let options = FORECAST_HANDLER.normalize_options(Some(json!({ "column": "sales" })));
+
+        assert_eq!(options["column"], json!("sales"));
+        assert_eq!(options["method"], json!("linear"));
+        assert_eq!(options["horizon"], json!(5));
+        assert_eq!(options["interpolate"], json!(false));
+        assert!(FORECAST_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn keeps_requested_method_and_horizon() {
+        let options = FORECAST_HANDLER.normalize_options(Some(json!({
+                                                                  "column": "sales",
+                                                                  "method": "Holt",
+                                                                  "horizon": 12,
+                                                                  "interpolate": true
+                                                              })));
+
+        assert_eq!(options["method"], json!("holt"));
+        assert_eq!(options["horizon"], json!(12));
+        assert_eq!(options["interpolate"], json!(true));
+        assert!(FORECAST_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn unknown_method_and_out_of_range_horizon_are_rejected() {
+        for method in [json!("arima"), json!(1)] {
+            let options = FORECAST_HANDLER.normalize_options(Some(json!({ "method": method })));
+
+            assert!(FORECAST_HANDLER.validate_options(&options).is_err(),
+                    "method {}",
+                    method);
+        }
+        for horizon in [json!(0), json!(-1), json!(2.5), json!("5"), json!(101)] {
+            let options = FORECAST_HANDLER.normalize_options(Some(json!({ "horizon": horizon })));
+
+            assert!(FORECAST_HANDLER.validate_options(&options).is_err(),
+                    "horizon {}",
+                    horizon);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct InfluenceHandler;
+
+pub(super) static INFLUENCE_HANDLER: InfluenceHandler = InfluenceHandler;
+
+impl AnalysisMethodHandler for InfluenceHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        Value::Object(normalize_options_object(options))
+    }
+}
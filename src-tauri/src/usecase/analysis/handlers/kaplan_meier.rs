@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_string_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct KaplanMeierHandler;
+
+pub(super) static KAPLAN_MEIER_HANDLER: KaplanMeierHandler = KaplanMeierHandler;
+
+impl AnalysisMethodHandler for KaplanMeierHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // A blank group means a single curve without the log-rank test.
+        match option_string_from_value(normalized.get("group")) {
+            Some(group) => {
+                normalized.insert("group".to_string(), Value::from(group));
+            },
+            None => {
+                normalized.remove("group");
+            },
+        }
+
+        Value::Object(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::KAPLAN_MEIER_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn blank_group_is_dropped() {
+        let options =
+            KAPLAN_MEIER_HANDLER.normalize_options(Some(json!({ "time": "t", "event": "e", "group": " " })));
+
+        assert_eq!(options, json!({ "time": "t", "event": "e" }));
+    }
+
+    #[test]
+    fn group_is_trimmed() {
+        let options = KAPLAN_MEIER_HANDLER.normalize_options(Some(json!({ "group": " arm " })));
+
+        assert_eq!(options["group"], json!("arm"));
+    }
+}
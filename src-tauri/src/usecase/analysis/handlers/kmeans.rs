@@ -0,0 +1,85 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    clamped_count_option,
+    normalize_options_object,
+    option_bool_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+/// Iterations per start when `max_iter` is not given.
+const DEFAULT_MAX_ITER: u64 = 100;
+
+/// Upper bound on `max_iter`, so that a large value cannot stall the analysis.
+const MAX_ITER_CAP: u64 = 1000;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct KMeansHandler;
+
+pub(super) static KMEANS_HANDLER: KMeansHandler = KMeansHandler;
+
+impl AnalysisMethodHandler for KMeansHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        let scale = normalized.get("scale")
+                              .and_then(option_bool_from_value)
+                              .unwrap_or(false);
+        normalized.insert("scale".to_string(), Value::Bool(scale));
+
+        clamped_count_option(&mut normalized, "max_iter", DEFAULT_MAX_ITER, MAX_ITER_CAP);
+
+        if normalized.get("seed").and_then(Value::as_i64).is_none() {
+            normalized.remove("seed");
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        // The upper bound (k <= complete rows) is checked in R, where the rows are known.
+        match normalized_options.get("k").and_then(Value::as_u64) {
+            Some(k) if k >= 2 => {},
+            _ => return Err("k must be an integer of at least 2".to_string()),
+        }
+        if normalized_options.get("max_iter").and_then(Value::as_u64) == Some(0) {
+            return Err("max_iter must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::KMEANS_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn fills_defaults_and_caps_iterations() {
+        let options = KMEANS_HANDLER.normalize_options(Some(json!({ "columns": ["x", "y"], "k": 3 })));
+        assert_eq!(options,
+                   json!({ "columns": ["x", "y"], "k": 3, "scale": false, "max_iter": 100 }));
+        assert!(KMEANS_HANDLER.validate_options(&options).is_ok());
+
+        let capped =
+            KMEANS_HANDLER.normalize_options(Some(json!({ "k": 2, "max_iter": 1_000_000, "seed": 7 })));
+        assert_eq!(capped["max_iter"], json!(1000));
+        assert_eq!(capped["seed"], json!(7));
+    }
+
+    #[test]
+    fn k_below_two_is_rejected() {
+        for k in [json!(1), json!(0), json!(2.5), json!(null)] {
+            let options = KMEANS_HANDLER.normalize_options(Some(json!({ "k": k })));
+
+            assert!(KMEANS_HANDLER.validate_options(&options).is_err(), "k {}", k);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct McNemarHandler;
+
+pub(super) static MCNEMAR_HANDLER: McNemarHandler = McNemarHandler;
+
+impl AnalysisMethodHandler for McNemarHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Continuity correction on by default, as in R's mcnemar.test().
+        let correct = normalized.get("correct").and_then(Value::as_bool).unwrap_or(true);
+        normalized.insert("correct".to_string(), Value::from(correct));
+
+        Value::Object(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::MCNEMAR_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn continuity_correction_defaults_to_on() {
+        let options = MCNEMAR_HANDLER.normalize_options(Some(json!({ "pre": "before", "post": "after" })));
+
+        assert_eq!(options,
+                   json!({ "pre": "before", "post": "after", "correct": true }));
+    }
+
+    #[test]
+    fn keeps_uncorrected_statistic_when_requested() {
+        let options = MCNEMAR_HANDLER.normalize_options(Some(json!({ "correct": false })));
+
+        assert_eq!(options["correct"], json!(false));
+    }
+}
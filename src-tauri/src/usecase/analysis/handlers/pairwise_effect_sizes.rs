@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    clamped_count_option,
+    normalize_options_object,
+    option_string_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+/// Bootstrap replicates when `reps` is not given.
+const DEFAULT_REPS: u64 = 2000;
+
+/// Upper bound on bootstrap replicates, so that a large `reps` cannot stall the analysis.
+const MAX_REPS: u64 = 10_000;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct PairwiseEffectSizesHandler;
+
+pub(super) static PAIRWISE_EFFECT_SIZES_HANDLER: PairwiseEffectSizesHandler = PairwiseEffectSizesHandler;
+
+impl AnalysisMethodHandler for PairwiseEffectSizesHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Cohen's d unless Hedges' small-sample correction is requested.
+        let hedges = normalized.get("hedges").and_then(Value::as_bool).unwrap_or(false);
+        normalized.insert("hedges".to_string(), Value::from(hedges));
+
+        // Normal-approximation intervals unless the bootstrap is asked for.
+        let ci_method =
+            option_string_from_value(normalized.get("ci_method")).map(|method| method.to_lowercase())
+                                                                 .unwrap_or_else(|| "analytic".to_string());
+        normalized.insert("ci_method".to_string(), Value::String(ci_method));
+
+        clamped_count_option(&mut normalized, "reps", DEFAULT_REPS, MAX_REPS);
+
+        if normalized.get("seed").and_then(Value::as_i64).is_none() {
+            normalized.remove("seed");
+        }
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        match normalized_options.get("ci_method").and_then(Value::as_str) {
+            Some("analytic") => Ok(()),
+            Some("bootstrap") => match normalized_options.get("reps").and_then(Value::as_u64) {
+                Some(reps) if reps >= 1 => Ok(()),
+                _ => Err("reps must be at least 1".to_string()),
+            },
+            other => Err(format!("ci_method must be 'analytic' or 'bootstrap', not {:?}",
+                                 other.unwrap_or(""))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::PAIRWISE_EFFECT_SIZES_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_cohens_d() {
+        let options =
+            PAIRWISE_EFFECT_SIZES_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g" })));
+
+        assert_eq!(options["response"], json!("y"));
+        assert_eq!(options["hedges"], json!(false));
+    }
+
+    #[test]
+    fn keeps_hedges_correction_when_requested() {
+        let options = PAIRWISE_EFFECT_SIZES_HANDLER.normalize_options(Some(json!({ "hedges": true })));
+
+        assert_eq!(options["hedges"], json!(true));
+    }
+
+    #[test]
+    fn analytic_intervals_by_default_and_bootstrap_reps_capped() {
+        let options = PAIRWISE_EFFECT_SIZES_HANDLER.normalize_options(None);
+        assert_eq!(options["ci_method"], json!("analytic"));
+        assert!(PAIRWISE_EFFECT_SIZES_HANDLER.validate_options(&options).is_ok());
+
+        let bootstrap =
+            PAIRWISE_EFFECT_SIZES_HANDLER.normalize_options(Some(json!({ "ci_method": "Bootstrap",
+                                                                                    "reps": 1_000_000,
+                                                                                    "seed": 3 })));
+        assert_eq!(bootstrap,
+                   json!({ "hedges": false, "ci_method": "bootstrap", "reps": 10_000, "seed": 3 }));
+        assert!(PAIRWISE_EFFECT_SIZES_HANDLER.validate_options(&bootstrap).is_ok());
+
+        let unknown =
+            PAIRWISE_EFFECT_SIZES_HANDLER.normalize_options(Some(json!({ "ci_method": "jackknife" })));
+        assert!(PAIRWISE_EFFECT_SIZES_HANDLER.validate_options(&unknown).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+/// Same default correction as the ANOVA post-hoc comparisons.
+const DEFAULT_P_ADJUST: &str = "holm";
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct PairwiseTTestsHandler;
+
+pub(super) static PAIRWISE_T_TESTS_HANDLER: PairwiseTTestsHandler = PairwiseTTestsHandler;
+
+impl AnalysisMethodHandler for PairwiseTTestsHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // The method is checked against stats::p.adjust.methods in RunPairwiseTTests, so no copy of
+        // that list is kept here.
+        let p_adjust = normalized.get("p_adjust")
+                                 .and_then(Value::as_str)
+                                 .map(|method| method.trim().to_ascii_lowercase())
+                                 .filter(|method| !method.is_empty())
+                                 .unwrap_or_else(|| DEFAULT_P_ADJUST.to_string());
+        normalized.insert("p_adjust".to_string(), Value::from(p_adjust));
+
+        let pool_sd = normalized.get("pool_sd")
+                                .and_then(Value::as_bool)
+                                .unwrap_or(false);
+        normalized.insert("pool_sd".to_string(), Value::from(pool_sd));
+
+        Value::Object(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::PAIRWISE_T_TESTS_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_holm_without_pooled_sd() {
+        let options =
+            PAIRWISE_T_TESTS_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g" })));
+
+        assert_eq!(options["p_adjust"], json!("holm"));
+        assert_eq!(options["pool_sd"], json!(false));
+    }
+
+    #[test]
+    fn keeps_explicit_method_and_pooling() {
+        let options = PAIRWISE_T_TESTS_HANDLER.normalize_options(Some(json!({ "p_adjust": " Bonferroni ",
+                                                                               "pool_sd": true })));
+
+        assert_eq!(options["p_adjust"], json!("bonferroni"));
+        assert_eq!(options["pool_sd"], json!(true));
+    }
+}
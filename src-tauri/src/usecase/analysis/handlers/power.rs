@@ -15,4 +15,55 @@ impl AnalysisMethodHandler for PowerHandler {
                          -> Value {
         Value::Object(normalize_options_object(options))
     }
+
+    /// The solved quantity is whichever of `power` and `n` is left out, so exactly one must be given.
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        let has_power = is_provided(normalized_options.get("power"));
+        let has_n = is_provided(normalized_options.get("n"));
+        match (has_power, has_n) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err("specify either power or n, not both".to_string()),
+            (false, false) => {
+                Err("specify either power (to solve for n) or n (to solve for power)".to_string())
+            },
+        }
+    }
+}
+
+/// Empty strings count as missing, as they do on the R side.
+fn is_provided(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(value)) => !value.trim().is_empty(),
+        Some(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::POWER_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    fn validate(options: serde_json::Value) -> Result<(), String> {
+        POWER_HANDLER.validate_options(&POWER_HANDLER.normalize_options(Some(options)))
+    }
+
+    #[test]
+    fn exactly_one_of_power_and_n_is_accepted() {
+        assert!(validate(json!({ "test": "t", "effect": "medium", "sig_level": 0.05, "power": 0.8 })).is_ok());
+        assert!(validate(json!({ "test": "t", "effect": "medium", "sig_level": 0.05, "n": 30 })).is_ok());
+    }
+
+    #[test]
+    fn both_or_neither_is_rejected() {
+        let err = validate(json!({ "test": "t", "power": 0.8, "n": 30 })).unwrap_err();
+        assert!(err.contains("not both"));
+
+        assert!(validate(json!({ "test": "t" })).is_err());
+        assert!(validate(json!({ "test": "t", "power": "", "n": null })).is_err());
+    }
 }
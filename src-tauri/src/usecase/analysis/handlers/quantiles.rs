@@ -0,0 +1,64 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+const DEFAULT_PROBS: [f64; 3] = [0.25, 0.5, 0.75];
+/// R's default `stats::quantile` algorithm.
+const DEFAULT_TYPE: u64 = 7;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct QuantilesHandler;
+
+pub(super) static QUANTILES_HANDLER: QuantilesHandler = QuantilesHandler;
+
+impl AnalysisMethodHandler for QuantilesHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Only the shape is fixed here: a single number becomes a one-element list. .ValidateProbs in
+        // describe.R checks that every probability lies in [0, 1].
+        let probs = match normalized.get("probs") {
+            Some(Value::Array(items)) if !items.is_empty() => Value::Array(items.clone()),
+            Some(Value::Number(prob)) => Value::Array(vec![Value::Number(prob.clone())]),
+            _ => Value::from(DEFAULT_PROBS.to_vec()),
+        };
+        normalized.insert("probs".to_string(), probs);
+
+        let quantile_type = normalized.get("type")
+                                      .and_then(Value::as_u64)
+                                      .unwrap_or(DEFAULT_TYPE);
+        normalized.insert("type".to_string(), Value::from(quantile_type));
+
+        Value::Object(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::QUANTILES_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_quartiles_with_type_7() {
+        let options = QUANTILES_HANDLER.normalize_options(None);
+
+        assert_eq!(options["probs"], json!([0.25, 0.5, 0.75]));
+        assert_eq!(options["type"], json!(7));
+    }
+
+    #[test]
+    fn keeps_requested_probs_and_wraps_a_single_prob() {
+        let options = QUANTILES_HANDLER.normalize_options(Some(json!({ "probs": [0.95, 0.99], "type": 6 })));
+        assert_eq!(options["probs"], json!([0.95, 0.99]));
+        assert_eq!(options["type"], json!(6));
+
+        let options = QUANTILES_HANDLER.normalize_options(Some(json!({ "probs": 0.5 })));
+        assert_eq!(options["probs"], json!([0.5]));
+    }
+}
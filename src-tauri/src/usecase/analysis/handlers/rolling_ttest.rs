@@ -0,0 +1,62 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::normalize_options_object;
+
+use super::AnalysisMethodHandler;
+
+/// Rows per window when `window` is not given: five before and five after each candidate row.
+const DEFAULT_WINDOW: u64 = 10;
+
+/// Smallest window whose halves both have the two values a t-test needs.
+const MIN_WINDOW: u64 = 4;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct RollingTTestHandler;
+
+pub(super) static ROLLING_TTEST_HANDLER: RollingTTestHandler = RollingTTestHandler;
+
+impl AnalysisMethodHandler for RollingTTestHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        // Small windows are kept so that validation can reject them.
+        let window = normalized.get("window")
+                               .and_then(Value::as_u64)
+                               .unwrap_or(DEFAULT_WINDOW);
+        normalized.insert("window".to_string(), Value::from(window));
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        match normalized_options.get("window").and_then(Value::as_u64) {
+            Some(window) if window >= MIN_WINDOW => Ok(()),
+            _ => Err(format!("window must be at least {}", MIN_WINDOW)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ROLLING_TTEST_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn window_defaults_to_ten_and_must_be_at_least_four() {
+        let options = ROLLING_TTEST_HANDLER.normalize_options(Some(json!({ "column": "sales" })));
+        assert_eq!(options, json!({ "column": "sales", "window": 10 }));
+        assert!(ROLLING_TTEST_HANDLER.validate_options(&options).is_ok());
+
+        let smallest = ROLLING_TTEST_HANDLER.normalize_options(Some(json!({ "window": 4 })));
+        assert!(ROLLING_TTEST_HANDLER.validate_options(&smallest).is_ok());
+
+        let too_small = ROLLING_TTEST_HANDLER.normalize_options(Some(json!({ "window": 3 })));
+        assert!(ROLLING_TTEST_HANDLER.validate_options(&too_small).is_err());
+    }
+}
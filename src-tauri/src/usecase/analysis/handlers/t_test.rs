@@ -0,0 +1,87 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_string_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+const ALTERNATIVES: [&str; 3] = ["two.sided", "less", "greater"];
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct TTestHandler;
+
+pub(super) static T_TEST_HANDLER: TTestHandler = TTestHandler;
+
+impl AnalysisMethodHandler for TTestHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        for flag in ["paired", "equal_variance"] {
+            let value = normalized.get(flag).and_then(Value::as_bool).unwrap_or(false);
+            normalized.insert(flag.to_string(), Value::Bool(value));
+        }
+
+        let alternative = option_string_from_value(normalized.get("alternative")).unwrap_or_else(|| {
+                                                                                     "two.sided".to_string()
+                                                                                 });
+        normalized.insert("alternative".to_string(), Value::String(alternative));
+
+        Value::Object(normalized)
+    }
+
+    fn column_options(&self) -> &'static [&'static str] {
+        &["response", "group"]
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        for key in self.column_options() {
+            if option_string_from_value(normalized_options.get(*key)).is_none() {
+                return Err(format!("{} is required", key));
+            }
+        }
+        let alternative = normalized_options.get("alternative").and_then(Value::as_str);
+        if !alternative.is_some_and(|alternative| ALTERNATIVES.contains(&alternative)) {
+            return Err("alternative must be one of two.sided, less or greater".to_string());
+        }
+        let flag = |key: &str| normalized_options.get(key).and_then(Value::as_bool) == Some(true);
+        if flag("paired") && flag("equal_variance") {
+            return Err("equal_variance does not apply to a paired t test".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::T_TEST_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_a_two_sided_welch_test() {
+        let options = T_TEST_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g" })));
+
+        assert_eq!(options,
+                   json!({ "response": "y", "group": "g", "paired": false, "equal_variance": false,
+                           "alternative": "two.sided" }));
+        assert!(T_TEST_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn paired_test_rejects_equal_variance_and_columns_are_required() {
+        let both =
+            T_TEST_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g", "paired": true,
+                                                                 "equal_variance": true })));
+        assert!(T_TEST_HANDLER.validate_options(&both).is_err());
+
+        let no_group = T_TEST_HANDLER.normalize_options(Some(json!({ "response": "y" })));
+        assert!(T_TEST_HANDLER.validate_options(&no_group).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use serde_json::Value;
+
+use crate::domain::analysis::model::AnalysisResult;
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_bool_from_value,
+    option_string_from_value,
+    sort_table_rows_by_abs_value,
+};
+
+use super::AnalysisMethodHandler;
+
+const CORRELATION_COLUMN: usize = 1;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct TargetCorrelationHandler;
+
+pub(super) static TARGET_CORRELATION_HANDLER: TargetCorrelationHandler = TargetCorrelationHandler;
+
+impl AnalysisMethodHandler for TargetCorrelationHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        let abs_sort = normalized.get("abs_sort")
+                                 .and_then(option_bool_from_value)
+                                 .unwrap_or(false);
+        normalized.insert("abs_sort".to_string(), Value::Bool(abs_sort));
+
+        let method =
+            option_string_from_value(normalized.get("method")).unwrap_or_else(|| "pearson".to_string());
+        normalized.insert("method".to_string(), Value::String(method));
+
+        Value::Object(normalized)
+    }
+
+    fn post_process(&self,
+                    result: &mut AnalysisResult,
+                    normalized_options: &Value)
+                    -> Result<(), String> {
+        let AnalysisResult::Table { table } = result else {
+            return Ok(());
+        };
+
+        // R already skips the target; keep the guarantee even if it was listed among the features.
+        if let Some(target) = option_string_from_value(normalized_options.get("target")) {
+            table.rows
+                 .retain(|row| row.first().and_then(Value::as_str) != Some(target.as_str()));
+        }
+
+        let abs_sort = normalized_options.get("abs_sort")
+                                         .and_then(option_bool_from_value)
+                                         .unwrap_or(false);
+        if abs_sort {
+            sort_table_rows_by_abs_value(table, CORRELATION_COLUMN);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use super::TARGET_CORRELATION_HANDLER;
+    use crate::domain::analysis::model::AnalysisResult;
+    use crate::domain::input::table::ParsedDataTable;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    fn result(rows: Vec<Vec<Value>>) -> AnalysisResult {
+        AnalysisResult::Table { table: ParsedDataTable { headers: vec!["変数".to_string(),
+                                                                       "相関係数".to_string(),
+                                                                       "有意確率".to_string(),
+                                                                       "度数(n)".to_string()],
+                                                         rows,
+                                                         note: None,
+                                                         title: None,
+                                                         column_types: Vec::new() } }
+    }
+
+    fn feature_order(result: &AnalysisResult) -> Vec<&str> {
+        let AnalysisResult::Table { table } = result else {
+            panic!("expected table result");
+        };
+        table.rows.iter().map(|row| row[0].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn excludes_target_and_orders_by_absolute_correlation() {
+        let options =
+            TARGET_CORRELATION_HANDLER.normalize_options(Some(json!({ "target": "y", "abs_sort": true })));
+        let mut result = result(vec![vec![json!("a"), json!("0.200"), json!("0.300"), json!("10")],
+                                     vec![json!("y"), json!("1.000"), Value::Null, json!("10")],
+                                     vec![json!("b"), json!("-0.800**"), json!("0.005"), json!("10")],
+                                     vec![json!("c"), Value::Null, Value::Null, json!("2")],
+                                     vec![json!("d"), json!("0.500"), json!("0.140"), json!("10")]]);
+
+        TARGET_CORRELATION_HANDLER.post_process(&mut result, &options)
+                                  .unwrap();
+
+        assert_eq!(feature_order(&result), vec!["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn keeps_feature_order_without_abs_sort() {
+        let options = TARGET_CORRELATION_HANDLER.normalize_options(Some(json!({ "target": "y" })));
+        let mut result = result(vec![vec![json!("a"), json!("0.200"), json!("0.300"), json!("10")],
+                                     vec![json!("b"), json!("-0.800"), json!("0.005"), json!("10")]]);
+
+        TARGET_CORRELATION_HANDLER.post_process(&mut result, &options)
+                                  .unwrap();
+
+        assert_eq!(options["method"], json!("pearson"));
+        assert_eq!(feature_order(&result), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use crate::domain::analysis::rule::{
+    normalize_options_object,
+    option_string_from_value,
+};
+
+use super::AnalysisMethodHandler;
+
+/// 20% trimming, the usual choice for Yuen's test (and the WRS2 default).
+const DEFAULT_TRIM: f64 = 0.2;
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct YuenTTestHandler;
+
+pub(super) static YUEN_T_TEST_HANDLER: YuenTTestHandler = YuenTTestHandler;
+
+impl AnalysisMethodHandler for YuenTTestHandler {
+    fn normalize_options(&self,
+                         options: Option<Value>)
+                         -> Value {
+        let mut normalized = normalize_options_object(options);
+
+        let trim = normalized.get("trim")
+                             .and_then(Value::as_f64)
+                             .unwrap_or(DEFAULT_TRIM);
+        normalized.insert("trim".to_string(), Value::from(trim));
+
+        let alternative = option_string_from_value(normalized.get("alternative")).unwrap_or_else(|| {
+                                                                                     "two.sided".to_string()
+                                                                                 });
+        normalized.insert("alternative".to_string(), Value::String(alternative));
+
+        Value::Object(normalized)
+    }
+
+    fn validate_options(&self,
+                        normalized_options: &Value)
+                        -> Result<(), String> {
+        // Trimming half of each tail would leave nothing to average.
+        match normalized_options.get("trim").and_then(Value::as_f64) {
+            Some(trim) if (0.0..0.5).contains(&trim) => Ok(()),
+            _ => Err("trim must be at least 0 and below 0.5".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::YUEN_T_TEST_HANDLER;
+    use crate::usecase::analysis::handlers::AnalysisMethodHandler;
+
+    #[test]
+    fn defaults_to_twenty_percent_two_sided() {
+        let options = YUEN_T_TEST_HANDLER.normalize_options(Some(json!({ "response": "y", "group": "g" })));
+
+        assert_eq!(options,
+                   json!({ "response": "y", "group": "g", "trim": 0.2, "alternative": "two.sided" }));
+        assert!(YUEN_T_TEST_HANDLER.validate_options(&options).is_ok());
+    }
+
+    #[test]
+    fn trim_must_be_below_one_half() {
+        for trim in [0.5, -0.1] {
+            let options = YUEN_T_TEST_HANDLER.normalize_options(Some(json!({ "trim": trim })));
+
+            assert!(YUEN_T_TEST_HANDLER.validate_options(&options).is_err(),
+                    "trim {}",
+                    trim);
+        }
+        let untrimmed = YUEN_T_TEST_HANDLER.normalize_options(Some(json!({ "trim": 0 })));
+        assert!(YUEN_T_TEST_HANDLER.validate_options(&untrimmed).is_ok());
+    }
+}
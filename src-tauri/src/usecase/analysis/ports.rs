@@ -3,7 +3,10 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::domain::analysis::method::Method;
-use crate::domain::analysis::model::AnalysisResult;
+use crate::domain::analysis::model::{
+    AnalysisPartial,
+    AnalysisResult,
+};
 use crate::domain::input::numeric::{
     NumericDataset,
     NumericDatasetEntry,
@@ -20,6 +23,17 @@ pub(crate) trait AnalysisRunner: Send + Sync {
                       options: &Value)
                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String>;
 
+    /// Runs a per-column analysis and reports each column through `on_partial` as it completes.
+    /// Runners without streaming support run the batch analysis and report nothing.
+    fn run_r_analysis_streaming(&self,
+                                method: Method,
+                                dataset: &NumericDataset,
+                                options: &Value,
+                                _on_partial: &mut dyn FnMut(AnalysisPartial))
+                                -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+        self.run_r_analysis(method, dataset, options)
+    }
+
     fn run_r_analysis_without_dataset(&self,
                                       method: Method,
                                       options: &Value)
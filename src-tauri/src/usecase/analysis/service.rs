@@ -1,4 +1,5 @@
 use chrono::Local;
+use indexmap::IndexMap;
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -14,12 +15,17 @@ use crate::domain::analysis::error::{
     classified_error_with_source,
 };
 use crate::domain::analysis::method::Method;
-use crate::domain::analysis::model::AnalysisRunResult;
+use crate::domain::analysis::model::{
+    AnalysisPartial,
+    AnalysisRunResult,
+};
+use crate::domain::analysis::rule::validate_memory_limit;
 use crate::domain::analysis_log::model::{
     ANALYSIS_LOG_SCHEMA_VERSION,
     AnalysisDatasetRef,
     AnalysisLogRecord,
 };
+use crate::domain::input::metadata::ColumnMetadataMap;
 use crate::usecase::analysis_log::ports::AnalysisLogWriter;
 
 pub(crate) struct AnalysisService<C: DatasetCacheStore, R: AnalysisRunner, L: AnalysisLogWriter> {
@@ -43,49 +49,91 @@ impl<C: DatasetCacheStore, R: AnalysisRunner, L: AnalysisLogWriter> AnalysisServ
                                method: Method,
                                options: Option<Value>)
                                -> Result<AnalysisRunResult, String> {
+        self.run_analysis_inner(dataset_cache_id, method, options, None)
+    }
+
+    /// Same as `run_analysis`, but per-column methods on numeric datasets report each column through
+    /// `on_partial` while R is still working. Other methods run as a batch and report nothing.
+    pub(crate) fn run_analysis_streaming(&self,
+                                         dataset_cache_id: &str,
+                                         method: Method,
+                                         options: Option<Value>,
+                                         on_partial: &mut dyn FnMut(AnalysisPartial))
+                                         -> Result<AnalysisRunResult, String> {
+        self.run_analysis_inner(dataset_cache_id, method, options, Some(on_partial))
+    }
+
+    fn run_analysis_inner(&self,
+                          dataset_cache_id: &str,
+                          method: Method,
+                          options: Option<Value>,
+                          on_partial: Option<&mut dyn FnMut(AnalysisPartial)>)
+                          -> Result<AnalysisRunResult, String> {
         if dataset_cache_id.trim().is_empty() {
             return Err(classified_error(AnalysisErrorKind::InputValidation, "dataset cache id is empty"));
         }
 
         let handler = resolve_handler(method);
         let normalized = handler.normalize_options(options);
+        handler.validate_options(&normalized)
+               .and_then(|_| validate_memory_limit(&normalized))
+               .map_err(|e| classified_error(AnalysisErrorKind::InputValidation, e))?;
 
         // Try string_mixed first, then numeric.
         // The dataset type is determined by which build command the frontend called.
-        let (dataset_ref, variables, mut result, n, n_note) = if let Some(entry) =
-            self.try_get_string_mixed(dataset_cache_id)?
-        {
-            log::info!("analysis.run_analysis (string_mixed) source path={} sheet={} vars={}",
-                       entry.path.as_str(),
-                       entry.sheet.as_str(),
-                       entry.variables.len());
-            let (r, n, n_note) = self.runner
-                                     .run_r_analysis_string_mixed(method, &entry.dataset, &normalized)?;
-            (to_dataset_ref(entry.path.as_str(), entry.sheet.as_str()), entry.variables.clone(), r, n, n_note)
-        } else {
-            let entry = self.cache
-                            .get_numeric_dataset(dataset_cache_id)
-                            .map_err(|e| {
-                                classified_error_with_source(AnalysisErrorKind::DatasetNotFound,
-                                                             "failed to read dataset cache",
-                                                             e)
-                            })?
-                            .ok_or_else(|| {
-                                classified_error(AnalysisErrorKind::DatasetNotFound,
-                                                 format!("dataset cache id '{}' was not found",
-                                                         dataset_cache_id))
-                            })?;
-            log::info!("analysis.run_analysis source path={} sheet={} vars={}",
-                       entry.path.as_str(),
-                       entry.sheet.as_str(),
-                       entry.variables.len());
-            let (r, n, n_note) = self.runner.run_r_analysis(method, &entry.dataset, &normalized)?;
-            (to_dataset_ref(entry.path.as_str(), entry.sheet.as_str()), entry.variables.clone(), r, n, n_note)
-        };
+        let (dataset_ref, variables, column_metadata, mut result, n, n_note) =
+            if let Some(entry) = self.try_get_string_mixed(dataset_cache_id)? {
+                log::info!("analysis.run_analysis (string_mixed) source path={} sheet={} vars={}",
+                           entry.path.as_str(),
+                           entry.sheet.as_str(),
+                           entry.variables.len());
+                check_option_columns(&normalized, handler.column_options(), entry.dataset.keys())?;
+                check_factor_levels(&normalized, handler.factor_options(), &entry.dataset)?;
+                let (r, n, n_note) = self.runner
+                                         .run_r_analysis_string_mixed(method, &entry.dataset, &normalized)?;
+                (to_dataset_ref(entry.path.as_str(), entry.sheet.as_str()),
+                 entry.variables.clone(),
+                 entry.column_metadata.clone(),
+                 r,
+                 n,
+                 n_note)
+            } else {
+                let entry = self.cache
+                                .get_numeric_dataset(dataset_cache_id)
+                                .map_err(|e| {
+                                    classified_error_with_source(AnalysisErrorKind::DatasetNotFound,
+                                                                 "failed to read dataset cache",
+                                                                 e)
+                                })?
+                                .ok_or_else(|| {
+                                    classified_error(AnalysisErrorKind::DatasetNotFound,
+                                                     format!("dataset cache id '{}' was not found",
+                                                             dataset_cache_id))
+                                })?;
+                log::info!("analysis.run_analysis source path={} sheet={} vars={}",
+                           entry.path.as_str(),
+                           entry.sheet.as_str(),
+                           entry.variables.len());
+                check_option_columns(&normalized, handler.column_options(), entry.dataset.keys())?;
+                check_factor_levels(&normalized, handler.factor_options(), &entry.dataset)?;
+                let (r, n, n_note) = match on_partial {
+                    Some(on_partial) if method.streams_by_column() => {
+                        self.runner
+                            .run_r_analysis_streaming(method, &entry.dataset, &normalized, on_partial)?
+                    },
+                    _ => self.runner.run_r_analysis(method, &entry.dataset, &normalized)?,
+                };
+                (to_dataset_ref(entry.path.as_str(), entry.sheet.as_str()),
+                 entry.variables.clone(),
+                 entry.column_metadata.clone(),
+                 r,
+                 n,
+                 n_note)
+            };
 
         handler.post_process(&mut result, &normalized)?;
 
-        let run_result = build_run_result(result, n, n_note);
+        let run_result = build_run_result(result, n, n_note, column_metadata);
         let log_record = AnalysisLogRecord { schema_version: ANALYSIS_LOG_SCHEMA_VERSION,
                                              id: run_result.analysis_id.clone(),
                                              timestamp: run_result.logged_at.clone(),
@@ -95,7 +143,8 @@ impl<C: DatasetCacheStore, R: AnalysisRunner, L: AnalysisLogWriter> AnalysisServ
                                              options: normalized,
                                              result: run_result.result.clone(),
                                              n: run_result.n,
-                                             n_note: run_result.n_note.clone() };
+                                             n_note: run_result.n_note.clone(),
+                                             column_metadata: run_result.column_metadata.clone() };
         self.log_store.append(&log_record).map_err(|e| {
                                          classified_error_with_source(AnalysisErrorKind::AnalysisLogFailure,
                                                                       "failed to append analysis log",
@@ -111,11 +160,14 @@ impl<C: DatasetCacheStore, R: AnalysisRunner, L: AnalysisLogWriter> AnalysisServ
                                           -> Result<AnalysisRunResult, String> {
         let handler = resolve_handler(method);
         let normalized = handler.normalize_options(options);
+        handler.validate_options(&normalized)
+               .and_then(|_| validate_memory_limit(&normalized))
+               .map_err(|e| classified_error(AnalysisErrorKind::InputValidation, e))?;
 
         let (mut result, n, n_note) = self.runner.run_r_analysis_without_dataset(method, &normalized)?;
         handler.post_process(&mut result, &normalized)?;
 
-        Ok(build_run_result(result, n, n_note))
+        Ok(build_run_result(result, n, n_note, ColumnMetadataMap::new()))
     }
 
     fn try_get_string_mixed(
@@ -133,9 +185,91 @@ impl<C: DatasetCacheStore, R: AnalysisRunner, L: AnalysisLogWriter> AnalysisServ
     }
 }
 
+/// Rejects a `columns` option, or one of the handler's single-column options, that names columns
+/// the dataset does not have, so that R never works from a partial column list. Dataset columns
+/// left out of `columns` are only logged.
+fn check_option_columns<'a>(normalized: &Value,
+                            column_options: &[&str],
+                            dataset_columns: impl Iterator<Item = &'a String>)
+                            -> Result<(), String> {
+    let dataset_columns: Vec<&String> = dataset_columns.collect();
+    for key in column_options {
+        if let Some(column) = normalized.get(*key).and_then(Value::as_str)
+           && !dataset_columns.iter().any(|c| c.as_str() == column)
+        {
+            return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                        format!("column '{}' ({}) was not found in the dataset",
+                                                column, key)));
+        }
+    }
+
+    let requested: Vec<&str> = match normalized.get("columns") {
+        Some(Value::Array(columns)) => columns.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(column)) => vec![column.as_str()],
+        _ => return Ok(()),
+    };
+    if requested.is_empty() {
+        return Ok(());
+    }
+
+    let missing: Vec<&str> = requested.iter()
+                                      .copied()
+                                      .filter(|column| !dataset_columns.iter().any(|c| c == column))
+                                      .collect();
+    if !missing.is_empty() {
+        return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                    format!("columns not found in the dataset: {}",
+                                            missing.join(", "))));
+    }
+
+    let omitted: Vec<&str> = dataset_columns.iter()
+                                            .map(|column| column.as_str())
+                                            .filter(|column| !requested.contains(column))
+                                            .collect();
+    if !omitted.is_empty() {
+        log::warn!("analysis.run_analysis columns option omits dataset columns: {}",
+                   omitted.join(", "));
+    }
+    Ok(())
+}
+
+/// Rejects a grouping column that is missing from the dataset or has fewer than two distinct
+/// non-missing values, which R would otherwise only report after it has started. The messages are
+/// in Japanese because they tell the user which group column to fix.
+fn check_factor_levels<T: PartialEq>(normalized: &Value,
+                                     factor_options: &[&str],
+                                     dataset: &IndexMap<String, Vec<Option<T>>>)
+                                     -> Result<(), String> {
+    for key in factor_options {
+        let columns: Vec<&str> = match normalized.get(*key) {
+            Some(Value::Array(columns)) => columns.iter().filter_map(Value::as_str).collect(),
+            Some(Value::String(column)) => vec![column.as_str()],
+            _ => continue,
+        };
+        for column in columns {
+            let values =
+                dataset.get(column).ok_or_else(|| {
+                                        classified_error(AnalysisErrorKind::InputValidation,
+                                                         format!("群の列 '{}' ({}) がデータにありません",
+                                                                 column, key))
+                                    })?;
+            let mut present = values.iter().flatten();
+            let has_two_levels = present.next()
+                                        .is_some_and(|first| present.any(|value| value != first));
+            if !has_two_levels {
+                return Err(classified_error(AnalysisErrorKind::InputValidation,
+                                            format!("群の列 '{}' ({}) には欠損でない水準が 2 つ以上必要です",
+                                                    column, key)));
+            }
+        }
+    }
+    Ok(())
+}
+
 fn build_run_result(result: crate::domain::analysis::model::AnalysisResult,
                     n: Option<u32>,
-                    n_note: Option<String>)
+                    n_note: Option<String>,
+                    column_metadata: ColumnMetadataMap)
                     -> AnalysisRunResult {
     let logged_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
     let analysis_id = Uuid::new_v4().to_string();
@@ -143,7 +277,8 @@ fn build_run_result(result: crate::domain::analysis::model::AnalysisResult,
                         logged_at,
                         result,
                         n,
-                        n_note }
+                        n_note,
+                        column_metadata }
 }
 
 fn to_dataset_ref(path: &str,
@@ -157,3 +292,231 @@ fn to_dataset_ref(path: &str,
                              Some(sheet.to_string())
                          } }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        Mutex,
+    };
+
+    use indexmap::IndexMap;
+    use serde_json::{
+        Value,
+        json,
+    };
+
+    use crate::domain::analysis::method::Method;
+    use crate::domain::analysis::model::{
+        AnalysisResult,
+        DescriptiveResult,
+    };
+    use crate::domain::analysis_log::model::AnalysisLogRecord;
+    use crate::domain::input::metadata::{
+        ColumnMetadata,
+        ColumnMetadataMap,
+    };
+    use crate::domain::input::numeric::{
+        NumericDataset,
+        NumericDatasetEntry,
+    };
+    use crate::domain::input::string_mixed::{
+        StringMixedDataset,
+        StringMixedDatasetEntry,
+    };
+    use crate::domain::input::table::ParsedDataTable;
+    use crate::usecase::analysis::ports::{
+        AnalysisRunner,
+        DatasetCacheStore,
+    };
+    use crate::usecase::analysis_log::ports::AnalysisLogWriter;
+
+    use super::AnalysisService;
+
+    struct FakeCache(Arc<NumericDatasetEntry>);
+
+    impl DatasetCacheStore for FakeCache {
+        fn get_numeric_dataset(&self,
+                               _dataset_cache_id: &str)
+                               -> Result<Option<Arc<NumericDatasetEntry>>, String> {
+            Ok(Some(self.0.clone()))
+        }
+
+        fn get_string_mixed_dataset(&self,
+                                    _dataset_cache_id: &str)
+                                    -> Result<Option<Arc<StringMixedDatasetEntry>>, String> {
+            Ok(None)
+        }
+    }
+
+    struct FakeRunner;
+
+    impl FakeRunner {
+        fn descriptive() -> AnalysisResult {
+            let table = ParsedDataTable { headers: vec!["変数".to_string(), "平均".to_string()],
+                                          rows: vec![vec!["length".into(), 12.5.into()]],
+                                          note: None,
+                                          title: None,
+                                          column_types: Vec::new() };
+            AnalysisResult::Descriptive { descriptive: DescriptiveResult { table,
+                                                                           histogram: None } }
+        }
+    }
+
+    impl AnalysisRunner for FakeRunner {
+        fn run_r_analysis(&self,
+                          _method: Method,
+                          _dataset: &NumericDataset,
+                          _options: &Value)
+                          -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+            Ok((Self::descriptive(), Some(2), None))
+        }
+
+        fn run_r_analysis_without_dataset(
+            &self,
+            _method: Method,
+            _options: &Value)
+            -> Result<(AnalysisResult, Option<u32>, Option<String>), String> {
+            Err("not used".to_string())
+        }
+
+        fn run_r_analysis_string_mixed(&self,
+                                       _method: Method,
+                                       _dataset: &StringMixedDataset,
+                                       _options: &Value)
+                                       -> Result<(AnalysisResult, Option<u32>, Option<String>), String>
+        {
+            Err("not used".to_string())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingLog(Mutex<Vec<AnalysisLogRecord>>);
+
+    impl AnalysisLogWriter for &RecordingLog {
+        fn append(&self,
+                  record: &AnalysisLogRecord)
+                  -> Result<(), String> {
+            self.0.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+    }
+
+    fn length_entry(column_metadata: ColumnMetadataMap) -> NumericDatasetEntry {
+        let mut dataset = IndexMap::new();
+        dataset.insert("length".to_string(), vec![Some(10.0), Some(15.0)]);
+        NumericDatasetEntry { dataset,
+                              path: "/tmp/data.xlsx".to_string(),
+                              sheet: "data".to_string(),
+                              variables: vec!["length".to_string()],
+                              column_metadata }
+    }
+
+    #[test]
+    fn column_units_are_preserved_through_descriptive_analysis() {
+        let mut column_metadata = ColumnMetadataMap::new();
+        column_metadata.insert("length".to_string(),
+                               ColumnMetadata { unit: Some("mm".to_string()),
+                                                description: None });
+        let log = RecordingLog::default();
+        let service = AnalysisService::new(FakeCache(Arc::new(length_entry(column_metadata.clone()))),
+                                           FakeRunner,
+                                           &log);
+
+        let run = service.run_analysis("dataset-1", Method::DESCRIPTIVE, Some(json!({})))
+                         .unwrap();
+
+        assert_eq!(run.column_metadata, column_metadata);
+        let records = log.0.lock().unwrap();
+        assert_eq!(records[0].column_metadata, column_metadata);
+        let stored = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(stored["columnMetadata"], json!({ "length": { "unit": "mm" } }));
+    }
+    #[test]
+    fn columns_option_naming_a_missing_column_is_rejected_before_r_runs() {
+        let log = RecordingLog::default();
+        let service = AnalysisService::new(FakeCache(Arc::new(length_entry(ColumnMetadataMap::new()))),
+                                           FakeRunner,
+                                           &log);
+
+        let err = service.run_analysis("dataset-1",
+                                Method::TARGET_CORRELATION,
+                                Some(json!({ "target": "length", "columns": ["width", "length", "depth"] })))
+                  .unwrap_err();
+
+        assert!(err.contains("columns not found in the dataset: width, depth"),
+                "{}",
+                err);
+        assert!(log.0.lock().unwrap().is_empty());
+        assert!(service.run_analysis("dataset-1",
+                                     Method::TARGET_CORRELATION,
+                                     Some(json!({ "target": "length", "columns": ["length"] })))
+                       .is_ok());
+    }
+
+    #[test]
+    fn t_test_column_option_naming_a_missing_column_is_rejected_before_r_runs() {
+        let log = RecordingLog::default();
+        let service = AnalysisService::new(FakeCache(Arc::new(length_entry(ColumnMetadataMap::new()))),
+                                           FakeRunner,
+                                           &log);
+
+        let err = service.run_analysis("dataset-1",
+                                       Method::T_TEST,
+                                       Some(json!({ "response": "length", "group": "species" })))
+                         .unwrap_err();
+
+        assert!(err.contains("column 'species' (group) was not found in the dataset"),
+                "{}",
+                err);
+        assert!(log.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn anova_group_with_a_single_level_is_rejected_before_r_runs() {
+        let log = RecordingLog::default();
+        let mut entry = length_entry(ColumnMetadataMap::new());
+        entry.dataset.insert("batch".to_string(), vec![Some(1.0), None]);
+        let service = AnalysisService::new(FakeCache(Arc::new(entry)), FakeRunner, &log);
+
+        let err = service.run_analysis("dataset-1",
+                                       Method::ANOVA,
+                                       Some(json!({ "dependent": "length", "between_factors": ["batch"] })))
+                         .unwrap_err();
+
+        assert!(err.contains("群の列 'batch' (between_factors) には欠損でない水準が 2 つ以上必要です"),
+                "{}",
+                err);
+        let err = service.run_analysis("dataset-1",
+                                       Method::ANOVA,
+                                       Some(json!({ "dependent": "length", "between_factors": ["site"] })))
+                         .unwrap_err();
+        assert!(err.contains("群の列 'site' (between_factors) がデータにありません"),
+                "{}",
+                err);
+        assert!(log.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn memory_limit_that_is_not_a_positive_integer_is_rejected_before_r_runs() {
+        let log = RecordingLog::default();
+        let service = AnalysisService::new(FakeCache(Arc::new(length_entry(ColumnMetadataMap::new()))),
+                                           FakeRunner,
+                                           &log);
+
+        for memory_limit_mb in [json!(0), json!(-1), json!("512")] {
+            let err = service.run_analysis("dataset-1",
+                                           Method::DESCRIPTIVE,
+                                           Some(json!({ "memory_limit_mb": memory_limit_mb })))
+                             .unwrap_err();
+            assert!(err.contains("memory_limit_mb must be a positive integer"),
+                    "{}",
+                    err);
+        }
+        assert!(log.0.lock().unwrap().is_empty());
+        assert!(service.run_analysis("dataset-1",
+                                     Method::DESCRIPTIVE,
+                                     Some(json!({ "memory_limit_mb": 512 })))
+                       .is_ok());
+    }
+}
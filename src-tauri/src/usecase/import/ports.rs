@@ -2,17 +2,26 @@ use crate::domain::input::numeric::{
     NumericDataset,
     NumericDatasetEntry,
 };
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::sheet::{
+    SheetInfo,
+    SheetProperties,
+    SheetReadiness,
+};
 use crate::domain::input::source_kind::DataSourceKind;
 use crate::domain::input::string_mixed::{
     StringMixedDataset,
     StringMixedDatasetEntry,
 };
 use crate::domain::input::table::ParsedDataTable;
+use crate::domain::input::verify::ParseMismatch;
 
 #[derive(Clone, Debug)]
 pub(crate) struct LoadedNumericDataset {
     pub dataset: NumericDataset,
     pub sheet_name: String,
+    /// Cells that loaded with a caveat, such as integers rounded beyond the exact `f64` range.
+    pub warnings: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -24,27 +33,74 @@ pub(crate) struct LoadedStringMixedDataset {
 pub(crate) trait TableReader: Send + Sync {
     fn read_sheets(&self,
                    kind: DataSourceKind,
-                   path: &str)
-                   -> Result<Vec<String>, String>;
+                   path: &str,
+                   include_hidden: bool)
+                   -> Result<Vec<SheetInfo>, String>;
+
+    /// Visibility of every sheet, plus protection and tab color where the format exposes them.
+    fn read_sheet_properties(&self,
+                             kind: DataSourceKind,
+                             path: &str)
+                             -> Result<Vec<SheetProperties>, String>;
 
     fn read_table(&self,
                   kind: DataSourceKind,
                   path: &str,
-                  sheet: Option<&str>)
+                  sheet: Option<&str>,
+                  options: &TableReadOptions)
                   -> Result<ParsedDataTable, String>;
 
+    /// Column names exactly as `read_table` would compute them, without materializing the data rows.
+    fn read_headers(&self,
+                    kind: DataSourceKind,
+                    path: &str,
+                    sheet: Option<&str>,
+                    options: &TableReadOptions)
+                    -> Result<Vec<String>, String>;
+
+    /// Parses the table as `read_table` does, re-reads the raw source cells and lists the parsed
+    /// cells that no longer match them.
+    fn verify_parse(&self,
+                    kind: DataSourceKind,
+                    path: &str,
+                    sheet: Option<&str>,
+                    options: &TableReadOptions)
+                    -> Result<Vec<ParseMismatch>, String>;
+
+    fn validate_workbook(&self,
+                         kind: DataSourceKind,
+                         path: &str,
+                         options: &TableReadOptions)
+                         -> Result<Vec<SheetReadiness>, String>;
+
+    fn read_formula_table(&self,
+                          kind: DataSourceKind,
+                          path: &str,
+                          sheet: &str,
+                          options: &TableReadOptions)
+                          -> Result<ParsedDataTable, String>;
+
+    fn read_named_table(&self,
+                        kind: DataSourceKind,
+                        path: &str,
+                        table_name: &str,
+                        options: &TableReadOptions)
+                        -> Result<ParsedDataTable, String>;
+
     fn build_numeric_dataset(&self,
                              kind: DataSourceKind,
                              path: &str,
                              sheet: Option<&str>,
-                             variables: &[String])
+                             variables: &[String],
+                             options: &TableReadOptions)
                              -> Result<LoadedNumericDataset, String>;
 
     fn build_string_mixed_dataset(&self,
                                   kind: DataSourceKind,
                                   path: &str,
                                   sheet: Option<&str>,
-                                  variables: &[String])
+                                  variables: &[String],
+                                  options: &TableReadOptions)
                                   -> Result<LoadedStringMixedDataset, String>;
 }
 
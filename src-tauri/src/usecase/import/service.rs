@@ -5,16 +5,47 @@ use super::ports::{
     TableReader,
 };
 
+use crate::domain::input::metadata::{
+    ColumnMetadataMap,
+    METADATA_SHEET_NAME,
+    column_metadata_from_table,
+    select_column_metadata,
+};
 use crate::domain::input::numeric::NumericDatasetEntry;
+use crate::domain::input::paste::parse_pasted_tsv;
+use crate::domain::input::read_options::TableReadOptions;
+use crate::domain::input::sheet::{
+    DataSheetGuess,
+    SheetInfo,
+    SheetProperties,
+    SheetReadiness,
+    guess_data_sheet,
+};
 use crate::domain::input::source_kind::DataSourceKind;
 use crate::domain::input::string_mixed::StringMixedDatasetEntry;
 use crate::domain::input::table::ParsedDataTable;
+use crate::domain::input::verify::ParseMismatch;
+use crate::domain::statistics::balance::{
+    GroupBalance,
+    group_balance,
+};
+use crate::domain::statistics::quality::{
+    DataQuality,
+    data_quality,
+    low_variance_columns,
+};
+use crate::domain::transform::concat::concat_tables;
+use crate::domain::transform::partition::{
+    RowPartition,
+    partition_rows,
+};
 
 pub(crate) struct BuiltNumericDataset {
     pub dataset_cache_id: String,
     pub sheet_name: String,
     pub variable_count: usize,
     pub row_count: usize,
+    pub warnings: Vec<String>,
 }
 
 pub(crate) struct BuiltStringMixedDataset {
@@ -39,30 +70,169 @@ impl<R: TableReader, C: NumericDatasetCacheStore> ImportService<R, C> {
     pub(crate) fn build_numeric_dataset(&self,
                                         path: &str,
                                         sheet: Option<&str>,
-                                        variables: &[String])
+                                        variables: &[String],
+                                        options: &TableReadOptions,
+                                        column_metadata: Option<ColumnMetadataMap>)
                                         -> Result<BuiltNumericDataset, String> {
         let kind = DataSourceKind::from_path(path)?;
-        let loaded = self.reader.build_numeric_dataset(kind, path, sheet, variables)?;
-        self.cache_loaded_numeric_dataset(path, loaded)
+        let loaded = self.reader
+                         .build_numeric_dataset(kind, path, sheet, variables, options)?;
+        let column_metadata = self.resolve_column_metadata(kind, path, &loaded.sheet_name, column_metadata)?;
+        self.cache_loaded_numeric_dataset(path, loaded, &column_metadata)
     }
 
     pub(crate) fn get_sheets(&self,
-                             path: &str)
-                             -> Result<Vec<String>, String> {
+                             path: &str,
+                             include_hidden: bool)
+                             -> Result<Vec<SheetInfo>, String> {
         let kind = DataSourceKind::from_path(path)?;
-        self.reader.read_sheets(kind, path)
+        self.reader.read_sheets(kind, path, include_hidden)
     }
 
     pub(crate) fn parse_table(&self,
                               path: &str,
-                              sheet: Option<&str>)
+                              sheet: Option<&str>,
+                              options: &TableReadOptions)
                               -> Result<ParsedDataTable, String> {
         let kind = DataSourceKind::from_path(path)?;
-        let table = self.reader.read_table(kind, path, sheet)?;
+        let table = self.reader.read_table(kind, path, sheet, options)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Parses clipboard text instead of a file; see `parse_pasted_tsv`.
+    pub(crate) fn parse_pasted_tsv(&self,
+                                   text: &str,
+                                   has_header: bool,
+                                   options: &TableReadOptions)
+                                   -> Result<ParsedDataTable, String> {
+        let table = parse_pasted_tsv(text, has_header, options)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub(crate) fn get_headers(&self,
+                              path: &str,
+                              sheet: Option<&str>,
+                              options: &TableReadOptions)
+                              -> Result<Vec<String>, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        self.reader.read_headers(kind, path, sheet, options)
+    }
+
+    pub(crate) fn get_sheet_properties(&self,
+                                       path: &str)
+                                       -> Result<Vec<SheetProperties>, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        self.reader.read_sheet_properties(kind, path)
+    }
+
+    pub(crate) fn verify_parse(&self,
+                               path: &str,
+                               sheet: Option<&str>,
+                               options: &TableReadOptions)
+                               -> Result<Vec<ParseMismatch>, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        self.reader.verify_parse(kind, path, sheet, options)
+    }
+
+    pub(crate) fn validate_workbook(&self,
+                                    path: &str,
+                                    options: &TableReadOptions)
+                                    -> Result<Vec<SheetReadiness>, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        self.reader.validate_workbook(kind, path, options)
+    }
+
+    pub(crate) fn guess_data_sheet(&self,
+                                   path: &str,
+                                   options: &TableReadOptions)
+                                   -> Result<DataSheetGuess, String> {
+        Ok(guess_data_sheet(&self.validate_workbook(path, options)?))
+    }
+
+    pub(crate) fn read_excel_formulas(&self,
+                                      path: &str,
+                                      sheet: &str,
+                                      options: &TableReadOptions)
+                                      -> Result<ParsedDataTable, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        self.reader.read_formula_table(kind, path, sheet, options)
+    }
+
+    pub(crate) fn read_excel_table(&self,
+                                   path: &str,
+                                   table_name: &str,
+                                   options: &TableReadOptions)
+                                   -> Result<ParsedDataTable, String> {
+        let kind = DataSourceKind::from_path(path)?;
+        let table = self.reader.read_named_table(kind, path, table_name, options)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub(crate) fn partition_rows(&self,
+                                 path: &str,
+                                 sheet: Option<&str>,
+                                 options: &TableReadOptions,
+                                 test_fraction: f64,
+                                 seed: u64,
+                                 stratify_by: Option<&str>)
+                                 -> Result<RowPartition, String> {
+        let table = self.parse_table(path, sheet, options)?;
+        partition_rows(&table, test_fraction, seed, stratify_by)
+    }
+
+    /// Reads every file with the same sheet and read options and row-binds them.
+    pub(crate) fn concat_files(&self,
+                               paths: &[String],
+                               sheet: Option<&str>,
+                               options: &TableReadOptions,
+                               add_source_column: bool)
+                               -> Result<ParsedDataTable, String> {
+        let tables = paths.iter()
+                          .map(|path| {
+                              let table = self.parse_table(path, sheet, options)
+                                              .map_err(|e| format!("{}: {}", path, e))?;
+                              Ok((file_label(path), table))
+                          })
+                          .collect::<Result<Vec<_>, String>>()?;
+        let table = concat_tables(tables, add_source_column)?;
         table.validate()?;
         Ok(table)
     }
 
+    pub(crate) fn group_balance(&self,
+                                path: &str,
+                                sheet: Option<&str>,
+                                options: &TableReadOptions,
+                                factor: &str,
+                                response: &str)
+                                -> Result<GroupBalance, String> {
+        let table = self.parse_table(path, sheet, options)?;
+        group_balance(&table, factor, response)
+    }
+
+    pub(crate) fn data_quality(&self,
+                               path: &str,
+                               sheet: Option<&str>,
+                               options: &TableReadOptions,
+                               key_columns: &[String])
+                               -> Result<DataQuality, String> {
+        let table = self.parse_table(path, sheet, options)?;
+        data_quality(&table, key_columns)
+    }
+
+    pub(crate) fn find_low_variance_columns(&self,
+                                            path: &str,
+                                            sheet: Option<&str>,
+                                            options: &TableReadOptions,
+                                            threshold: f64)
+                                            -> Result<ParsedDataTable, String> {
+        let table = self.parse_table(path, sheet, options)?;
+        low_variance_columns(&table, threshold)
+    }
+
     pub(crate) fn clear_numeric_dataset_cache(&self) -> Result<(), String> {
         self.cache.clear_numeric_dataset_cache()
     }
@@ -70,17 +240,61 @@ impl<R: TableReader, C: NumericDatasetCacheStore> ImportService<R, C> {
     pub(crate) fn build_string_mixed_dataset(&self,
                                              path: &str,
                                              sheet: Option<&str>,
-                                             variables: &[String])
+                                             variables: &[String],
+                                             options: &TableReadOptions,
+                                             column_metadata: Option<ColumnMetadataMap>)
                                              -> Result<BuiltStringMixedDataset, String> {
         let kind = DataSourceKind::from_path(path)?;
         let loaded = self.reader
-                         .build_string_mixed_dataset(kind, path, sheet, variables)?;
-        self.cache_loaded_string_mixed_dataset(path, loaded)
+                         .build_string_mixed_dataset(kind, path, sheet, variables, options)?;
+        let column_metadata = self.resolve_column_metadata(kind, path, &loaded.sheet_name, column_metadata)?;
+        self.cache_loaded_string_mixed_dataset(path, loaded, &column_metadata)
+    }
+
+    /// Uses the supplied metadata, or else the workbook's `metadata` sheet when it has one.
+    fn resolve_column_metadata(&self,
+                               kind: DataSourceKind,
+                               path: &str,
+                               data_sheet: &str,
+                               supplied: Option<ColumnMetadataMap>)
+                               -> Result<ColumnMetadataMap, String> {
+        if let Some(supplied) = supplied {
+            return Ok(supplied);
+        }
+        if kind != DataSourceKind::Xlsx || data_sheet == METADATA_SHEET_NAME {
+            return Ok(ColumnMetadataMap::new());
+        }
+        // Metadata is display-only, so a malformed sheet must not block the analysis.
+        Ok(self.read_metadata_sheet(kind, path).unwrap_or_else(|e| {
+                                                   log::warn!("import.column_metadata ignored path={} err={}",
+                                                              path,
+                                                              e);
+                                                   ColumnMetadataMap::new()
+                                               }))
+    }
+
+    fn read_metadata_sheet(&self,
+                           kind: DataSourceKind,
+                           path: &str)
+                           -> Result<ColumnMetadataMap, String> {
+        let has_metadata_sheet = self.reader
+                                     .read_sheets(kind, path, true)?
+                                     .iter()
+                                     .any(|sheet| sheet.name == METADATA_SHEET_NAME);
+        if !has_metadata_sheet {
+            return Ok(ColumnMetadataMap::new());
+        }
+        let table = self.reader.read_table(kind,
+                                            path,
+                                            Some(METADATA_SHEET_NAME),
+                                            &TableReadOptions::default())?;
+        column_metadata_from_table(&table)
     }
 
     fn cache_loaded_numeric_dataset(&self,
                                     path: &str,
-                                    loaded: LoadedNumericDataset)
+                                    loaded: LoadedNumericDataset,
+                                    column_metadata: &ColumnMetadataMap)
                                     -> Result<BuiltNumericDataset, String> {
         let row_count = loaded.dataset.values().next().map(|col| col.len()).unwrap_or(0);
         let variable_count = loaded.dataset.len();
@@ -91,16 +305,21 @@ impl<R: TableReader, C: NumericDatasetCacheStore> ImportService<R, C> {
                 .insert_numeric_dataset(NumericDatasetEntry { dataset: loaded.dataset,
                                                               path: path.to_string(),
                                                               sheet: sheet_name.clone(),
+                                                              column_metadata:
+                                                                  select_column_metadata(column_metadata,
+                                                                                         &variables_in_order),
                                                               variables: variables_in_order })?;
         Ok(BuiltNumericDataset { dataset_cache_id,
                                  sheet_name,
                                  variable_count,
-                                 row_count })
+                                 row_count,
+                                 warnings: loaded.warnings })
     }
 
     fn cache_loaded_string_mixed_dataset(&self,
                                          path: &str,
-                                         loaded: LoadedStringMixedDataset)
+                                         loaded: LoadedStringMixedDataset,
+                                         column_metadata: &ColumnMetadataMap)
                                          -> Result<BuiltStringMixedDataset, String> {
         let row_count = loaded.dataset.values().next().map(|col| col.len()).unwrap_or(0);
         let variable_count = loaded.dataset.len();
@@ -111,6 +330,9 @@ impl<R: TableReader, C: NumericDatasetCacheStore> ImportService<R, C> {
                 .insert_string_mixed_dataset(StringMixedDatasetEntry { dataset: loaded.dataset,
                                                                        path: path.to_string(),
                                                                        sheet: sheet_name.clone(),
+                                                                       column_metadata:
+                                                                           select_column_metadata(column_metadata,
+                                                                                                  &variables_in_order),
                                                                        variables: variables_in_order })?;
         Ok(BuiltStringMixedDataset { dataset_cache_id,
                                      sheet_name,
@@ -118,3 +340,10 @@ impl<R: TableReader, C: NumericDatasetCacheStore> ImportService<R, C> {
                                      row_count })
     }
 }
+
+/// File name shown for a path in merged tables, falling back to the full path.
+fn file_label(path: &str) -> String {
+    std::path::Path::new(path).file_name()
+                              .map(|name| name.to_string_lossy().into_owned())
+                              .unwrap_or_else(|| path.to_string())
+}
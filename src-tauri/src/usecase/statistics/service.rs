@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use crate::domain::analysis::error::{
+    AnalysisErrorKind,
+    classified_error,
+    classified_error_with_source,
+};
+use crate::domain::input::numeric::NumericDatasetEntry;
+use crate::domain::input::string_mixed::StringMixedDatasetEntry;
+use crate::domain::input::table::ParsedDataTable;
+use crate::domain::statistics::{
+    acf,
+    anomaly,
+    association,
+    boxplot,
+    collinearity,
+    cumulative,
+    diversity,
+    ecdf,
+    group_zscore,
+    histogram,
+    interaction,
+    normality,
+    rolling,
+    spearman,
+    trend,
+};
+use crate::usecase::analysis::ports::DatasetCacheStore;
+
+/// Rust-native statistics computed directly from cached datasets, without spawning R.
+pub(crate) struct StatisticsService<C: DatasetCacheStore> {
+    cache: C,
+}
+
+impl<C: DatasetCacheStore> StatisticsService<C> {
+    pub(crate) fn new(cache: C) -> Self {
+        Self { cache }
+    }
+
+    pub(crate) fn quick_normality(&self,
+                                  dataset_cache_id: &str)
+                                  -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        Ok(normality::quick_normality(&entry.dataset))
+    }
+
+    pub(crate) fn categorical_diversity(&self,
+                                        dataset_cache_id: &str,
+                                        include_missing: bool)
+                                        -> Result<ParsedDataTable, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        Ok(diversity::categorical_diversity(&entry.dataset, include_missing))
+    }
+
+    pub(crate) fn cramers_v(&self,
+                            dataset_cache_id: &str,
+                            row: &str,
+                            column: &str,
+                            bias_corrected: bool)
+                            -> Result<ParsedDataTable, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        association::cramers_v(&entry.dataset, row, column, bias_corrected).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn contingency_table(&self,
+                                    dataset_cache_id: &str,
+                                    row: &str,
+                                    column: &str,
+                                    options: &association::ContingencyOptions)
+                                    -> Result<association::ContingencyTable, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        association::contingency_table(&entry.dataset, row, column, options).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn trend_line(&self,
+                             dataset_cache_id: &str,
+                             x: &str,
+                             y: &str)
+                             -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        trend::trend_line(&entry.dataset, x, y).map_err(|e| {
+                                                   classified_error(AnalysisErrorKind::InputValidation, e)
+                                               })
+    }
+
+    pub(crate) fn theil_sen(&self,
+                            dataset_cache_id: &str,
+                            x: &str,
+                            y: &str)
+                            -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        trend::theil_sen(&entry.dataset, x, y).map_err(|e| {
+                                                  classified_error(AnalysisErrorKind::InputValidation, e)
+                                              })
+    }
+
+    pub(crate) fn rolling_correlation(&self,
+                                      dataset_cache_id: &str,
+                                      x: &str,
+                                      y: &str,
+                                      window: usize)
+                                      -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        rolling::rolling_correlation(&entry.dataset, x, y, window).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn cumulative_stats(&self,
+                                   dataset_cache_id: &str,
+                                   column: &str,
+                                   stat: cumulative::CumulativeStat,
+                                   reset_on_missing: bool)
+                                   -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        cumulative::cumulative_stats(&entry.dataset, column, stat, reset_on_missing).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn acf(&self,
+                      dataset_cache_id: &str,
+                      column: &str,
+                      max_lag: usize,
+                      confidence: bool,
+                      interpolate: bool)
+                      -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        acf::acf(&entry.dataset, column, max_lag, confidence, interpolate).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn anomaly_detect(&self,
+                                 dataset_cache_id: &str,
+                                 column: &str,
+                                 threshold: f64)
+                                 -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        anomaly::anomaly_detect(&entry.dataset, column, threshold).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn quick_spearman(&self,
+                                 dataset_cache_id: &str)
+                                 -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        spearman::quick_spearman(&entry.dataset).map_err(|e| {
+                                                    classified_error(AnalysisErrorKind::InputValidation, e)
+                                                })
+    }
+
+    pub(crate) fn collinearity_screen(&self,
+                                      dataset_cache_id: &str,
+                                      threshold: f64,
+                                      include_vif: bool)
+                                      -> Result<collinearity::CollinearityScreen, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        collinearity::collinearity_screen(&entry.dataset, threshold, include_vif).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn variance_inflation(&self,
+                                     dataset_cache_id: &str,
+                                     columns: &[String])
+                                     -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        collinearity::vif_for_columns(&entry.dataset, columns).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn boxplot_stats(&self,
+                                dataset_cache_id: &str,
+                                columns: &[String],
+                                group: Option<&str>)
+                                -> Result<boxplot::BoxplotStats, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        boxplot::boxplot_stats(&entry.dataset, columns, group).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn group_zscore(&self,
+                               dataset_cache_id: &str,
+                               column: &str,
+                               group: &str,
+                               reference_level: &str)
+                               -> Result<ParsedDataTable, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        group_zscore::group_zscore(&entry.dataset, column, group, reference_level).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn interaction_means(&self,
+                                    dataset_cache_id: &str,
+                                    response: &str,
+                                    factor_a: &str,
+                                    factor_b: &str)
+                                    -> Result<ParsedDataTable, String> {
+        let entry = self.string_mixed_dataset(dataset_cache_id)?;
+        interaction::interaction_means(&entry.dataset, response, factor_a, factor_b).map_err(|e| {
+            classified_error(AnalysisErrorKind::InputValidation, e)
+        })
+    }
+
+    pub(crate) fn histogram(&self,
+                            dataset_cache_id: &str,
+                            column: &str,
+                            spec: histogram::HistogramSpec)
+                            -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        histogram::histogram(&entry.dataset, column, spec).map_err(|e| classified_error(AnalysisErrorKind::InputValidation, e))
+    }
+
+    pub(crate) fn ecdf(&self,
+                       dataset_cache_id: &str,
+                       column: &str)
+                       -> Result<ParsedDataTable, String> {
+        let entry = self.numeric_dataset(dataset_cache_id)?;
+        ecdf::ecdf(&entry.dataset, column).map_err(|e| {
+                                              classified_error(AnalysisErrorKind::InputValidation, e)
+                                          })
+    }
+
+    fn numeric_dataset(&self,
+                       dataset_cache_id: &str)
+                       -> Result<Arc<NumericDatasetEntry>, String> {
+        if dataset_cache_id.trim().is_empty() {
+            return Err(classified_error(AnalysisErrorKind::InputValidation, "dataset cache id is empty"));
+        }
+        self.cache
+            .get_numeric_dataset(dataset_cache_id)
+            .map_err(|e| {
+                classified_error_with_source(AnalysisErrorKind::DatasetNotFound,
+                                             "failed to read dataset cache",
+                                             e)
+            })?
+            .ok_or_else(|| {
+                classified_error(AnalysisErrorKind::DatasetNotFound,
+                                 format!("dataset cache id '{}' was not found", dataset_cache_id))
+            })
+    }
+
+    fn string_mixed_dataset(&self,
+                            dataset_cache_id: &str)
+                            -> Result<Arc<StringMixedDatasetEntry>, String> {
+        if dataset_cache_id.trim().is_empty() {
+            return Err(classified_error(AnalysisErrorKind::InputValidation, "dataset cache id is empty"));
+        }
+        self.cache
+            .get_string_mixed_dataset(dataset_cache_id)
+            .map_err(|e| {
+                classified_error_with_source(AnalysisErrorKind::DatasetNotFound,
+                                             "failed to read dataset cache",
+                                             e)
+            })?
+            .ok_or_else(|| {
+                classified_error(AnalysisErrorKind::DatasetNotFound,
+                                 format!("dataset cache id '{}' was not found", dataset_cache_id))
+            })
+    }
+}
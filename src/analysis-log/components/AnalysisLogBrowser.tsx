@@ -1,7 +1,7 @@
 import { Box, Flex, Stack, Text } from '@chakra-ui/react';
 import { useMemo } from 'react';
 import type { MethodModule, SupportedAnalysisType } from '../../analysis/api';
-import { buildSelectedLabel, findMethodLabel } from '../services/display';
+import { buildSelectedLabel, buildVariablesLabel, findMethodLabel } from '../services/display';
 import { formatAnalysisOptions } from '../services/formatOptions';
 import { useAnalysisLogBrowser } from '../state/useAnalysisLogBrowser';
 import { type AnalysisLogBrowserOptions, LOG_SOURCE_LABEL, PERSISTENT_LOG_LIMIT } from '../types';
@@ -23,7 +23,10 @@ export const AnalysisLogBrowser = (options: AnalysisLogBrowserOptions = {}) => {
   const datasetLabel = buildSelectedLabel(
     browser.selectedSummary?.dataset ?? browser.selectedRecord?.dataset ?? null
   );
-  const variablesLabel = browser.selectedRecord?.variables.join(', ') || 'なし';
+  const variablesLabel = buildVariablesLabel(
+    browser.selectedRecord?.variables ?? [],
+    browser.selectedRecord?.columnMetadata
+  );
   const formattedOptions = browser.selectedRecord
     ? formatAnalysisOptions(browser.selectedRecord.type, browser.selectedRecord.options)
     : [];
@@ -1,4 +1,9 @@
-import type { AnalysisLogSummary, MethodModule, SupportedAnalysisType } from '../../analysis/api';
+import type {
+  AnalysisLogSummary,
+  ColumnMetadata,
+  MethodModule,
+  SupportedAnalysisType,
+} from '../../analysis/api';
 import type { Dataset } from '../../types';
 import { ALL_ANALYSIS_FILTER, type AnalysisFilter, type LogSource } from '../types';
 
@@ -9,6 +14,18 @@ export const buildSelectedLabel = (selection: Dataset | null): string | null =>
   return selection.sheet ? `${selection.path}（${selection.sheet}）` : selection.path;
 };
 
+// 単位が登録されている変数は「変数名 [単位]」と表示する
+export const buildVariablesLabel = (
+  variables: readonly string[],
+  columnMetadata: Record<string, ColumnMetadata> | undefined
+): string => {
+  const labels = variables.map((variable) => {
+    const unit = columnMetadata?.[variable]?.unit;
+    return unit ? `${variable} [${unit}]` : variable;
+  });
+  return labels.join(', ') || 'なし';
+};
+
 const findMethodByType = (
   methods: readonly MethodModule[],
   type: SupportedAnalysisType | null
@@ -78,6 +78,12 @@ const formatRegression = (options: AnalysisOptions): FormattedOption[] => {
 
   result.push({ label: '切片', value: options.intercept === false ? 'なし' : 'あり' });
   result.push({ label: '中心化', value: options.center === true ? 'あり' : 'なし' });
+  if (options.diagnostics === true) {
+    result.push({ label: '残差・予測値', value: '出力' });
+  }
+  if (options.standardized === true) {
+    result.push({ label: '標準化係数', value: '出力' });
+  }
 
   if (options.interactions !== undefined) {
     const label = formatInteractions(options.interactions);
@@ -183,6 +189,7 @@ const formatAnova = (options: AnalysisOptions): FormattedOption[] => {
     peta: '偏η²',
     eta: 'η²',
     omega: 'ω²',
+    all: 'すべて (η², 偏η², ω²)',
     none: 'なし',
   };
   if (options.effect_size !== undefined) {
@@ -191,6 +198,9 @@ const formatAnova = (options: AnalysisOptions): FormattedOption[] => {
       value: effectSizeLabels[String(options.effect_size)] ?? String(options.effect_size),
     });
   }
+  if (options.posthoc === 'tukey') {
+    result.push({ label: '多重比較', value: 'Tukey HSD' });
+  }
 
   return result;
 };
@@ -211,6 +221,14 @@ const formatDescriptive = (options: AnalysisOptions): FormattedOption[] => {
   const stats: string[] = [];
   if (options.skewness) stats.push('歪度');
   if (options.kurtosis) stats.push('尖度');
+  if (options.mode) stats.push('最頻値');
+  if (options.dispersion) {
+    stats.push(
+      '変動係数',
+      options.mad_constant === false ? '中央絶対偏差 (一致性定数なし)' : '中央絶対偏差',
+      '四分位範囲'
+    );
+  }
   if (stats.length > 0) {
     result.push({ label: '統計量', value: stats.join('、') });
   }
@@ -14,6 +14,7 @@ export type {
   AnalysisResultPayload,
   AnalysisRunRequest,
   AnalysisSection,
+  ColumnMetadata,
   DatasetKind,
   SupportedAnalysisType,
 } from './types';
@@ -14,7 +14,7 @@ type AnovaInteractionMode = 'all' | 'manual';
 
 export type AnovaInteractions = 'all' | InteractionTerm[];
 
-type EffectSizeType = 'peta' | 'eta' | 'omega' | 'none';
+type EffectSizeType = 'peta' | 'eta' | 'omega' | 'all' | 'none';
 
 export interface AnovaOptions extends AnalysisOptions {
   dependent?: string;
@@ -36,6 +36,7 @@ const EFFECT_SIZE_OPTIONS = [
   { label: '偏η²', value: 'peta' },
   { label: 'η²', value: 'eta' },
   { label: 'ω²', value: 'omega' },
+  { label: 'すべて (η², 偏η², ω²)', value: 'all' },
   { label: 'なし', value: 'none' },
 ] as const satisfies ReadonlyArray<{ label: string; value: EffectSizeType }>;
 
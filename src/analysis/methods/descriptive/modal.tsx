@@ -14,6 +14,9 @@ export interface DescriptiveOptions extends AnalysisOptions {
   order: DescriptiveOrder;
   skewness: boolean;
   kurtosis: boolean;
+  mode: boolean;
+  dispersion: boolean;
+  mad_constant: boolean;
   histogram: HistogramMode;
   histogram_variables?: string[];
   breaks?: BreaksMethod;
@@ -48,6 +51,9 @@ export const DescriptiveModal = ({
   const [order, setOrder] = useState<DescriptiveOrder>(DEFAULT_ORDER);
   const [skewness, setSkewness] = useState(false);
   const [kurtosis, setKurtosis] = useState(false);
+  const [mode, setMode] = useState(false);
+  const [dispersion, setDispersion] = useState(false);
+  const [madConstant, setMadConstant] = useState(true);
   const [showHistogram, setShowHistogram] = useState(false);
   const [histogramScope, setHistogramScope] = useState<'all' | 'selected'>('all');
   const [histogramVariables, setHistogramVariables] = useState<string[]>([]);
@@ -62,6 +68,9 @@ export const DescriptiveModal = ({
       setOrder(DEFAULT_ORDER);
       setSkewness(false);
       setKurtosis(false);
+      setMode(false);
+      setDispersion(false);
+      setMadConstant(true);
       setShowHistogram(false);
       setHistogramScope('all');
       setHistogramVariables([]);
@@ -109,6 +118,9 @@ export const DescriptiveModal = ({
         order,
         skewness,
         kurtosis,
+        mode,
+        dispersion,
+        mad_constant: madConstant,
         histogram,
         ...(histogram === 'selected' ? { histogram_variables: histogramVariables } : {}),
         ...(histogram !== 'none' ? { breaks } : {}),
@@ -183,7 +195,31 @@ export const DescriptiveModal = ({
                   <Checkbox.Control />
                   <Checkbox.Label>尖度</Checkbox.Label>
                 </Checkbox.Root>
+                <Checkbox.Root checked={mode} onCheckedChange={(e) => setMode(!!e.checked)}>
+                  <Checkbox.HiddenInput />
+                  <Checkbox.Control />
+                  <Checkbox.Label>最頻値</Checkbox.Label>
+                </Checkbox.Root>
+                <Checkbox.Root
+                  checked={dispersion}
+                  onCheckedChange={(e) => setDispersion(!!e.checked)}
+                >
+                  <Checkbox.HiddenInput />
+                  <Checkbox.Control />
+                  <Checkbox.Label>変動係数・中央絶対偏差・四分位範囲</Checkbox.Label>
+                </Checkbox.Root>
               </HStack>
+              {dispersion ? (
+                <Checkbox.Root
+                  pl="2"
+                  checked={madConstant}
+                  onCheckedChange={(e) => setMadConstant(!!e.checked)}
+                >
+                  <Checkbox.HiddenInput />
+                  <Checkbox.Control />
+                  <Checkbox.Label>中央絶対偏差に一致性定数 (1.4826) を掛ける</Checkbox.Label>
+                </Checkbox.Root>
+              ) : null}
             </Stack>
 
             <Stack gap="2">
@@ -38,6 +38,8 @@ export interface RegressionOptions extends AnalysisOptions {
   interactions: RegressionInteractions;
   intercept: boolean;
   center: boolean;
+  diagnostics: boolean;
+  standardized: boolean;
 }
 
 const INTERACTION_MODE_OPTIONS = [
@@ -56,6 +58,16 @@ const CENTER_OPTIONS = [
   { label: 'する', value: 'on' },
 ] as const satisfies ReadonlyArray<{ label: string; value: BinaryToggle }>;
 
+const DIAGNOSTICS_OPTIONS = [
+  { label: 'しない', value: 'off' },
+  { label: 'する', value: 'on' },
+] as const satisfies ReadonlyArray<{ label: string; value: BinaryToggle }>;
+
+const STANDARDIZED_OPTIONS = [
+  { label: 'しない', value: 'off' },
+  { label: 'する', value: 'on' },
+] as const satisfies ReadonlyArray<{ label: string; value: BinaryToggle }>;
+
 const DEFAULT_SELECTION: RegressionVariableSelection = {
   dependent: null,
   independent: [],
@@ -64,6 +76,8 @@ const DEFAULT_SELECTION: RegressionVariableSelection = {
 const DEFAULT_INTERACTION_MODE: InteractionMode = 'none';
 const DEFAULT_INTERCEPT = INTERCEPT_OPTIONS[0]?.value ?? 'on';
 const DEFAULT_CENTER = CENTER_OPTIONS[0]?.value ?? 'off';
+const DEFAULT_DIAGNOSTICS = DIAGNOSTICS_OPTIONS[0]?.value ?? 'off';
+const DEFAULT_STANDARDIZED = STANDARDIZED_OPTIONS[0]?.value ?? 'off';
 
 export const RegressionModal = ({
   open,
@@ -76,6 +90,8 @@ export const RegressionModal = ({
   const [manualTerms, setManualTerms] = useState<InteractionTerm[]>([]);
   const [intercept, setIntercept] = useState<BinaryToggle>(DEFAULT_INTERCEPT);
   const [center, setCenter] = useState<BinaryToggle>(DEFAULT_CENTER);
+  const [diagnostics, setDiagnostics] = useState<BinaryToggle>(DEFAULT_DIAGNOSTICS);
+  const [standardized, setStandardized] = useState<BinaryToggle>(DEFAULT_STANDARDIZED);
   const [error, setError] = useState<string | null>(null);
   const [loading, setLoading] = useState(false);
   const previousVariablesKeyRef = useRef('');
@@ -87,6 +103,8 @@ export const RegressionModal = ({
       setManualTerms([]);
       setIntercept(DEFAULT_INTERCEPT);
       setCenter(DEFAULT_CENTER);
+      setDiagnostics(DEFAULT_DIAGNOSTICS);
+      setStandardized(DEFAULT_STANDARDIZED);
       setError(null);
       setLoading(false);
     }
@@ -142,6 +160,8 @@ export const RegressionModal = ({
         interactions,
         intercept: intercept === 'on',
         center: center === 'on',
+        diagnostics: diagnostics === 'on',
+        standardized: standardized === 'on',
       });
     } catch (executeError: unknown) {
       setError(executeError instanceof Error ? executeError.message : String(executeError));
@@ -224,6 +244,26 @@ export const RegressionModal = ({
                 onChange={(value) => setCenter(value as BinaryToggle)}
               />
             </Stack>
+
+            <Stack gap="2">
+              <Text fontWeight="semibold">残差・予測値の出力</Text>
+              <BaseRadioButton
+                contents={DIAGNOSTICS_OPTIONS}
+                orientation="horizontal"
+                value={diagnostics}
+                onChange={(value) => setDiagnostics(value as BinaryToggle)}
+              />
+            </Stack>
+
+            <Stack gap="2">
+              <Text fontWeight="semibold">標準化係数の出力</Text>
+              <BaseRadioButton
+                contents={STANDARDIZED_OPTIONS}
+                orientation="horizontal"
+                value={standardized}
+                onChange={(value) => setStandardized(value as BinaryToggle)}
+              />
+            </Stack>
           </Stack>
         </Box>
       </Stack>
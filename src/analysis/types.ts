@@ -15,6 +15,12 @@ export interface AnalysisLogSummary {
   dataset: Dataset;
 }
 
+// 列の単位・説明 (表示専用。分析結果には影響しない)
+export interface ColumnMetadata {
+  unit?: string;
+  description?: string;
+}
+
 export interface AnalysisResultPayload {
   schemaVersion: number;
   id: string;
@@ -26,6 +32,7 @@ export interface AnalysisResultPayload {
   result: AnalysisResult;
   n?: number;
   nNote?: string;
+  columnMetadata?: Record<string, ColumnMetadata>;
 }
 
 export type DatasetKind = 'numeric' | 'string_mixed';
@@ -7,7 +7,7 @@ import type {
   AnalysisResultPayload,
   SupportedAnalysisType,
 } from './analysis/api';
-import type { Dataset, ParsedDataTable } from './types';
+import type { Dataset, ParsedDataTable, SheetInfo } from './types';
 import type { PowerAnalysisOptions } from './windows/components/PowerAnalysisDialog';
 
 interface IpcResponse {
@@ -35,8 +35,8 @@ class TauriIpc {
     await invoke('clear_numeric_dataset_cache');
   }
 
-  async getSheets(path: string): Promise<string[]> {
-    return invoke<string[]>('get_sheets', { path });
+  async getSheets(path: string, includeHidden = false): Promise<SheetInfo[]> {
+    return invoke<SheetInfo[]>('get_sheets', { path, includeHidden });
   }
 
   async parseTable(path: string, sheet?: string): Promise<ParsedDataTable> {
@@ -8,6 +8,14 @@ export interface ParsedDataTable {
   rows: ParsedCell[][];
   note?: string;
   title?: string;
+  column_types?: ColumnType[];
 }
 
 export type ParsedCell = string | number | boolean | null;
+
+export type ColumnType = 'numeric' | 'integer' | 'boolean' | 'date' | 'text' | 'mixed';
+
+export interface SheetInfo {
+  name: string;
+  visible: boolean;
+}
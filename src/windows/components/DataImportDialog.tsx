@@ -55,8 +55,9 @@ export const DataImportDialog = ({ onLoaded }: Props) => {
         if (cancelled) {
           return;
         }
-        setSheetNames(sheets);
-        setSelectedSheet(sheets[0] ?? null);
+        const names = sheets.map((sheet) => sheet.name);
+        setSheetNames(names);
+        setSelectedSheet(names[0] ?? null);
       })
       .catch((err: unknown) => {
         if (cancelled) {
@@ -79,7 +80,7 @@ export const DataImportDialog = ({ onLoaded }: Props) => {
     const result = await open({
       multiple: false,
       directory: false,
-      filters: [{ name: 'DataFiles', extensions: ['xlsx', 'csv'] }],
+      filters: [{ name: 'DataFiles', extensions: ['xlsx', 'csv', 'tsv'] }],
     });
     if (!result) {
       return;
@@ -0,0 +1,99 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { ANALYSIS_READY_EVENT, RESULT_WINDOW_LABEL } from '../events';
+import { listenForResultWindowReady, openResultWindow } from './ToResultWindow';
+
+type ReadyHandler = (event: { payload: unknown }) => void;
+
+const mocks = vi.hoisted(() => ({
+  handlers: new Map<string, ReadyHandler>(),
+  unlisten: vi.fn(),
+  createdAfterListen: [] as boolean[],
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  emitTo: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/webviewWindow', () => {
+  class WebviewWindow {
+    static getByLabel = vi.fn(async () => null);
+
+    constructor() {
+      mocks.createdAfterListen.push(mocks.handlers.has(ANALYSIS_READY_EVENT));
+    }
+
+    once(event: string, handler: (event: unknown) => void) {
+      if (event === 'tauri://created') {
+        // The window reports ready right after it is created, before the caller awaits `ready`.
+        queueMicrotask(() => {
+          handler({});
+          mocks.handlers.get(ANALYSIS_READY_EVENT)?.({ payload: { label: RESULT_WINDOW_LABEL } });
+        });
+      }
+    }
+  }
+
+  return {
+    WebviewWindow,
+    getCurrentWebviewWindow: () => ({
+      listen: async (event: string, handler: ReadyHandler) => {
+        mocks.handlers.set(event, handler);
+        return mocks.unlisten;
+      },
+    }),
+  };
+});
+
+describe('listenForResultWindowReady', () => {
+  beforeEach(() => {
+    mocks.handlers.clear();
+    mocks.unlisten.mockClear();
+    mocks.createdAfterListen.length = 0;
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('registers the listener before the result window is created', async () => {
+    await openResultWindow();
+
+    expect(mocks.createdAfterListen).toEqual([true]);
+    expect(mocks.unlisten).toHaveBeenCalledTimes(1);
+  });
+
+  it('settles only on the ready event of the result window', async () => {
+    const { ready } = await listenForResultWindowReady();
+    const handler = mocks.handlers.get(ANALYSIS_READY_EVENT);
+
+    handler?.({ payload: { label: 'dataView' } });
+    expect(mocks.unlisten).not.toHaveBeenCalled();
+
+    handler?.({ payload: { label: RESULT_WINDOW_LABEL } });
+    await expect(ready).resolves.toBeUndefined();
+    expect(mocks.unlisten).toHaveBeenCalledTimes(1);
+  });
+
+  it('rejects and stops listening when the window never reports ready', async () => {
+    vi.useFakeTimers();
+    const { ready } = await listenForResultWindowReady(1000);
+    const rejected = expect(ready).rejects.toThrow('RESULT_WINDOW_READY_TIMEOUT');
+
+    await vi.advanceTimersByTimeAsync(1000);
+
+    await rejected;
+    expect(mocks.unlisten).toHaveBeenCalledTimes(1);
+    mocks.handlers.get(ANALYSIS_READY_EVENT)?.({ payload: { label: RESULT_WINDOW_LABEL } });
+    expect(mocks.unlisten).toHaveBeenCalledTimes(1);
+  });
+
+  it('stops listening when cancelled', async () => {
+    vi.useFakeTimers();
+    const { cancel } = await listenForResultWindowReady(1000);
+
+    cancel();
+    await vi.advanceTimersByTimeAsync(1000);
+
+    expect(mocks.unlisten).toHaveBeenCalledTimes(1);
+  });
+});
@@ -35,52 +35,56 @@ const toTaggedWindowError = (code: string, message: string): Error => {
   return new Error(`[${code}] ${message}`);
 };
 
-const waitForResultWindowReady = async () => {
+// Resolves once the readiness listener is registered; `ready` then settles on the window's signal,
+// or rejects after `timeoutMs`. Registration must finish before the window is created, or a fast
+// window's ready event is lost. The listener is removed when `ready` settles or `cancel` is called.
+export const listenForResultWindowReady = async (
+  timeoutMs: number = RESULT_WINDOW_READY_TIMEOUT_MS
+): Promise<{ ready: Promise<void>; cancel: () => void }> => {
   const currentWindow = getCurrentWebviewWindow();
 
-  let resolveReady: (() => void) | null = null;
-  let rejectReady: ((error: Error) => void) | null = null;
-  let settled = false;
+  let resolveReady: () => void = () => {};
+  let rejectReady: (error: Error) => void = () => {};
   const ready = new Promise<void>((resolve, reject) => {
     resolveReady = resolve;
-    rejectReady = (error) => reject(error);
+    rejectReady = reject;
   });
 
-  const unlisten = await currentWindow.listen(ANALYSIS_READY_EVENT, (event) => {
+  let settled = false;
+  let unlisten: (() => void) | null = null;
+  let timeoutId: ReturnType<typeof setTimeout> | undefined;
+  const cancel = () => {
     if (settled) {
       return;
     }
+    settled = true;
+    clearTimeout(timeoutId);
+    unlisten?.();
+  };
+
+  unlisten = await currentWindow.listen(ANALYSIS_READY_EVENT, (event) => {
     const payload = event.payload as { label?: unknown } | null;
-    if (payload?.label !== RESULT_WINDOW_LABEL) {
+    if (settled || payload?.label !== RESULT_WINDOW_LABEL) {
       return;
     }
-    settled = true;
-    if (resolveReady) {
-      resolveReady();
-    }
+    cancel();
+    resolveReady();
   });
 
-  const timeoutId = setTimeout(() => {
+  timeoutId = setTimeout(() => {
     if (settled) {
       return;
     }
-    settled = true;
-    if (rejectReady) {
-      rejectReady(
-        toTaggedWindowError(
-          'RESULT_WINDOW_READY_TIMEOUT',
-          `結果ウィンドウの初期化待機がタイムアウトしました (${RESULT_WINDOW_READY_TIMEOUT_MS}ms)`
-        )
-      );
-    }
-  }, RESULT_WINDOW_READY_TIMEOUT_MS);
+    cancel();
+    rejectReady(
+      toTaggedWindowError(
+        'RESULT_WINDOW_READY_TIMEOUT',
+        `結果ウィンドウの初期化待機がタイムアウトしました (${timeoutMs}ms)`
+      )
+    );
+  }, timeoutMs);
 
-  try {
-    await ready;
-  } finally {
-    clearTimeout(timeoutId);
-    unlisten();
-  }
+  return { ready, cancel };
 };
 
 export const openResultWindow = async () => {
@@ -91,7 +95,7 @@ export const openResultWindow = async () => {
     return;
   }
 
-  const ready = waitForResultWindowReady();
+  const { ready, cancel } = await listenForResultWindowReady();
   const resultWindow = new WebviewWindow(RESULT_WINDOW_LABEL, {
     url: '/windows/result-window.html',
     title: 'SAI (結果ビュー)',
@@ -111,6 +115,7 @@ export const openResultWindow = async () => {
 
     await ready;
   } catch (error: unknown) {
+    cancel();
     const normalized = error instanceof Error ? error : new Error(String(error));
     console.error(normalized.message);
     throw normalized;
@@ -2,7 +2,8 @@ import { tauriIpc } from '../../ipc';
 import type { Dataset, ParsedDataTable } from '../../types';
 
 export const getSheetNamesByPath = async (path: string): Promise<string[]> => {
-  return tauriIpc.getSheets(path);
+  const sheets = await tauriIpc.getSheets(path);
+  return sheets.map((sheet) => sheet.name);
 };
 
 export interface SwitchedSheetResult {